@@ -81,7 +81,7 @@ where
             .map(rcl::rcl_action_client_options_t::from)
             .unwrap_or_else(rcl::MTSafeFn::rcl_action_client_get_default_options);
         println!("action_name: {}", action_name);
-        let action_name = CString::new(action_name).unwrap_or_default();
+        let action_name = CString::new(action_name).map_err(|_| RCLActionError::NameInvalid)?;
 
         {
             let guard = rcl::MT_UNSAFE_FN.lock();
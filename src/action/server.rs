@@ -120,7 +120,7 @@ where
             .unwrap_or_else(rcl::MTSafeFn::rcl_action_server_get_default_options);
         // TODO: reconcile RCLResult and RCLActionResult to avoid unwrap
         let clock = Clock::new().unwrap();
-        let action_name = CString::new(action_name).unwrap_or_default();
+        let action_name = CString::new(action_name).map_err(|_| RCLActionError::NameInvalid)?;
 
         {
             let guard = rcl::MT_UNSAFE_FN.lock();
@@ -267,12 +267,7 @@ where
     }
 
     fn get_timestamp(&mut self) -> UnsafeTime {
-        let now_nanosec = self.clock.get_now().unwrap();
-        let now_sec = now_nanosec / 10_i64.pow(9);
-        UnsafeTime {
-            sec: now_sec as i32,
-            nanosec: (now_nanosec - now_sec * 10_i64.pow(9)) as u32,
-        }
+        self.clock.now().unwrap().to_stamp_saturating()
     }
 }
 
@@ -1,25 +1,74 @@
 use std::mem::MaybeUninit;
 
-use crate::{error::RCLResult, get_allocator, rcl};
+use crate::{error::RCLResult, get_allocator, rcl, time::Time};
 
-/// A clock. For now only SystemTime/ROSTime is implemented.
+/// Type of a clock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ClockType {
+    /// The system time, which can be overridden by a simulated time.
+    Ros,
+
+    /// The system time, which is never overridden.
+    System,
+
+    /// A monotonic time, which is not related to the wall clock.
+    Steady,
+}
+
+#[cfg(feature = "galactic")]
+impl From<ClockType> for rcl::rcl_clock_type_t {
+    fn from(clock_type: ClockType) -> Self {
+        match clock_type {
+            ClockType::Ros => rcl::rcl_clock_type_t_RCL_ROS_TIME,
+            ClockType::System => rcl::rcl_clock_type_t_RCL_SYSTEM_TIME,
+            ClockType::Steady => rcl::rcl_clock_type_t_RCL_STEADY_TIME,
+        }
+    }
+}
+
+#[cfg(any(feature = "humble", feature = "iron"))]
+impl From<ClockType> for rcl::rcl_clock_type_t {
+    fn from(clock_type: ClockType) -> Self {
+        match clock_type {
+            ClockType::Ros => rcl::rcl_clock_type_e_RCL_ROS_TIME,
+            ClockType::System => rcl::rcl_clock_type_e_RCL_SYSTEM_TIME,
+            ClockType::Steady => rcl::rcl_clock_type_e_RCL_STEADY_TIME,
+        }
+    }
+}
+
+/// A clock.
+///
+/// The ROS time can be overridden by a simulated time.
+/// See `safe_drive::time_source`.
 pub struct Clock {
     pub(crate) clock: Box<rcl::rcl_clock_t>,
+    clock_type: ClockType,
 }
 
 impl Clock {
-    /// Create a clock.
+    /// Create a clock of the ROS time.
     pub fn new() -> RCLResult<Self> {
+        Self::with_type(ClockType::Ros)
+    }
+
+    /// Create a clock of `clock_type`.
+    pub fn with_type(clock_type: ClockType) -> RCLResult<Self> {
         let mut clock = unsafe { MaybeUninit::zeroed().assume_init() };
 
         let guard = rcl::MT_UNSAFE_FN.lock();
-        guard.rcl_ros_clock_init(&mut clock, &mut get_allocator())?;
+        guard.rcl_clock_init(clock_type.into(), &mut clock, &mut get_allocator())?;
 
         Ok(Self {
             clock: Box::new(clock),
+            clock_type,
         })
     }
 
+    pub fn get_clock_type(&self) -> ClockType {
+        self.clock_type
+    }
+
     pub(crate) fn as_ptr_mut(&self) -> *mut rcl::rcl_clock_t {
         &*self.clock as *const _ as *mut _
     }
@@ -29,11 +78,45 @@ impl Clock {
         rcl::MTSafeFn::rcl_clock_get_now(&mut *self.clock, &mut now)?;
         Ok(now)
     }
+
+    /// Get the current time of this clock.
+    pub fn now(&mut self) -> RCLResult<Time> {
+        Ok(Time::new(self.get_now()?, self.clock_type))
+    }
+
+    /// Use the time set by `set_ros_time_override` instead of the system time.
+    pub fn enable_ros_time_override(&mut self) -> RCLResult<()> {
+        let guard = rcl::MT_UNSAFE_FN.lock();
+        guard.rcl_enable_ros_time_override(&mut *self.clock)
+    }
+
+    /// Use the system time again.
+    pub fn disable_ros_time_override(&mut self) -> RCLResult<()> {
+        let guard = rcl::MT_UNSAFE_FN.lock();
+        guard.rcl_disable_ros_time_override(&mut *self.clock)
+    }
+
+    pub fn is_ros_time_override_enabled(&mut self) -> RCLResult<bool> {
+        let guard = rcl::MT_UNSAFE_FN.lock();
+        guard.rcl_is_enabled_ros_time_override(&mut *self.clock)
+    }
+
+    /// Set the current time in nanoseconds,
+    /// which is returned by `get_now` while the override is enabled.
+    pub fn set_ros_time_override(
+        &mut self,
+        nanoseconds: rcl::rcl_time_point_value_t,
+    ) -> RCLResult<()> {
+        let guard = rcl::MT_UNSAFE_FN.lock();
+        guard.rcl_set_ros_time_override(&mut *self.clock, nanoseconds)
+    }
 }
 
 impl Drop for Clock {
     fn drop(&mut self) {
         let guard = rcl::MT_UNSAFE_FN.lock();
-        let _ = guard.rcl_ros_clock_fini(&mut *self.clock);
+        let _ = guard.rcl_clock_fini(&mut *self.clock);
     }
 }
+
+unsafe impl Send for Clock {}
@@ -0,0 +1,773 @@
+//! Component container, which loads nodes into a process at runtime.
+//!
+//! A component container serves the services of `composition_interfaces`,
+//! so nodes written in Rust can be loaded by `ros2 component load`.
+//!
+//! - `<container name>/_container/load_node`
+//! - `<container name>/_container/unload_node`
+//! - `<container name>/_container/list_nodes`
+//!
+//! A component is created by a factory function registered with a plugin name.
+//! Factories are registered to `ComponentRegistry` statically,
+//! or loaded from a plugin library which exports them by `export_components!`.
+//! When a plugin is not registered, the container looks up `lib<package name>.so`
+//! in `<prefix>/lib` of `AMENT_PREFIX_PATH` and in the default search path of the dynamic linker.
+//!
+//! All components share the context and the selector of the container,
+//! so intra-process communication is available among them.
+//! If `use_intra_process_comms` is set to `true` in the extra arguments of `LoadNode`,
+//! `ComponentArgs::use_intra_process_comms` returns `true`.
+//!
+//! # Example
+//!
+//! ```
+//! use safe_drive::{
+//!     component::{Component, ComponentArgs, ComponentContainer},
+//!     context::Context,
+//!     error::DynError,
+//!     msg::common_interfaces::std_msgs,
+//!     node::Node,
+//! };
+//! use std::{sync::Arc, time::Duration};
+//!
+//! struct Talker {
+//!     node: Arc<Node>,
+//! }
+//!
+//! impl Component for Talker {
+//!     fn get_node(&self) -> &Arc<Node> {
+//!         &self.node
+//!     }
+//! }
+//!
+//! fn create_talker(args: &mut ComponentArgs) -> Result<Box<dyn Component>, DynError> {
+//!     let node = args.create_node("talker", None)?;
+//!     let publisher = node.create_publisher::<std_msgs::msg::UInt32>("chatter", None)?;
+//!
+//!     args.selector().add_wall_timer(
+//!         "talker",
+//!         Duration::from_millis(100),
+//!         Box::new(move || {
+//!             let msg = std_msgs::msg::UInt32::new().unwrap();
+//!             publisher.send(&msg).unwrap();
+//!         }),
+//!     );
+//!
+//!     Ok(Box::new(Talker { node }))
+//! }
+//!
+//! let ctx = Context::new().unwrap();
+//! let mut container = ComponentContainer::new(ctx, "component_rs", None).unwrap();
+//! container.registry_mut().register("talker_rs::Talker", create_talker);
+//!
+//! // Load a component directly, or by `ros2 component load`.
+//! let id = container.load("talker_rs", "talker_rs::Talker", Default::default()).unwrap();
+//!
+//! container.spin_once().unwrap();
+//! container.unload(id).unwrap();
+//! ```
+//!
+//! A plugin library exports its components as follows,
+//! which defines an `extern "C"` entry point returning `ComponentPlugin`.
+//!
+//! ```ignore
+//! safe_drive::export_components!("talker_rs::Talker" => create_talker);
+//! ```
+//!
+//! Components share the statics of `safe_drive`, such as the shared context,
+//! the lock of MT-unsafe functions of `rcl`, and the signal handler.
+//! So, the container and plugins must link the same shared library of `safe_drive`,
+//! and the container rejects plugins which link their own copy of `safe_drive`.
+//! To do this, create a crate re-exporting `safe_drive` with `crate-type = ["dylib"]`,
+//! make the container and plugins (`crate-type = ["dylib"]`) depend on it,
+//! and build them by the same compiler with `RUSTFLAGS="-C prefer-dynamic"`.
+
+use crate::{
+    context::Context,
+    error::DynError,
+    logger::{pr_error_in, Logger},
+    msg::{
+        interfaces::{
+            composition_interfaces::srv::{
+                ListNodes, ListNodesResponse, LoadNode, LoadNodeRequest, LoadNodeResponse,
+                UnloadNode, UnloadNodeResponse,
+            },
+            rcl_interfaces::msg::ParameterSeq,
+        },
+        RosStringSeq, ServiceMsg, U64Seq,
+    },
+    node::{Node, NodeOptions},
+    parameter::Value,
+    qos::Profile,
+    selector::{CallbackResult, EntityKeys, Selector},
+    service::server::{Server, ServerSend},
+    RecvResult,
+};
+use std::{cell::Cell, collections::BTreeMap, rc::Rc, sync::Arc, time::Duration};
+
+/// Name of the entry point exported by `export_components!`.
+pub const PLUGIN_SYMBOL: &str = "safe_drive_component_plugin";
+
+/// Version of the layout of `ComponentPlugin`.
+pub const PLUGIN_ABI_VERSION: u32 = 1;
+
+/// Static whose address identifies the copy of `safe_drive` linked by a plugin.
+#[doc(hidden)]
+pub static SAFE_DRIVE_INSTANCE: u8 = 0;
+
+/// Entry of a plugin library returned by the function exported by `export_components!`.
+#[repr(C)]
+pub struct ComponentPlugin {
+    /// Must be `PLUGIN_ABI_VERSION`.
+    pub abi_version: u32,
+
+    /// Address of `SAFE_DRIVE_INSTANCE` seen by the plugin,
+    /// which equals that of the container if they link the same `safe_drive`.
+    pub instance: *const u8,
+
+    /// Register factories of the plugin.
+    pub register: unsafe extern "C" fn(registry: *mut ComponentRegistry),
+}
+
+/// Type of the function exported by `export_components!`.
+pub type ComponentPluginEntry = unsafe extern "C" fn() -> ComponentPlugin;
+
+/// Component loaded into a container.
+/// A component is dropped when it is unloaded.
+pub trait Component {
+    /// Get the node of the component.
+    fn get_node(&self) -> &Arc<Node>;
+}
+
+/// Factory function to create a component.
+pub type ComponentFactory = fn(&mut ComponentArgs) -> Result<Box<dyn Component>, DynError>;
+
+/// Registry of factories, whose keys are plugin names.
+#[derive(Default)]
+pub struct ComponentRegistry {
+    factories: BTreeMap<String, ComponentFactory>,
+}
+
+impl ComponentRegistry {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Register a factory.
+    /// If the plugin name has been already registered, the factory is replaced.
+    pub fn register(&mut self, plugin_name: &str, factory: ComponentFactory) {
+        self.factories.insert(plugin_name.to_string(), factory);
+    }
+
+    /// Get a factory.
+    pub fn get(&self, plugin_name: &str) -> Option<ComponentFactory> {
+        self.factories.get(plugin_name).copied()
+    }
+
+    /// Get registered plugin names.
+    pub fn plugin_names(&self) -> Vec<String> {
+        self.factories.keys().cloned().collect()
+    }
+}
+
+/// Export factories of a plugin library, which can be loaded by `ComponentContainer`.
+/// See the module document for how to build plugins.
+///
+/// # Example
+///
+/// ```ignore
+/// safe_drive::export_components!(
+///     "my_package::Talker" => create_talker,
+///     "my_package::Listener" => create_listener,
+/// );
+/// ```
+#[macro_export]
+macro_rules! export_components {
+    ($($name:expr => $factory:expr),* $(,)?) => {
+        #[no_mangle]
+        pub extern "C" fn safe_drive_component_plugin() -> $crate::component::ComponentPlugin {
+            unsafe extern "C" fn register(registry: *mut $crate::component::ComponentRegistry) {
+                let registry = &mut *registry;
+                $(registry.register($name, $factory);)*
+            }
+
+            $crate::component::ComponentPlugin {
+                abi_version: $crate::component::PLUGIN_ABI_VERSION,
+                instance: &$crate::component::SAFE_DRIVE_INSTANCE,
+                register,
+            }
+        }
+    };
+}
+
+/// Options to load a component.
+/// These correspond to the request of `composition_interfaces/srv/LoadNode`.
+#[derive(Debug, Clone, Default)]
+pub struct LoadOptions {
+    /// Name of the node, which overrides the default name of the component.
+    pub node_name: Option<String>,
+
+    /// Namespace of the node, which overrides the default namespace of the component.
+    pub node_namespace: Option<String>,
+
+    /// Remapping rules like `from:=to`.
+    pub remap_rules: Vec<String>,
+
+    /// Parameters of the node.
+    pub parameters: Vec<(String, Value)>,
+
+    /// Extra arguments. `use_intra_process_comms` is supported.
+    pub extra_arguments: Vec<(String, Value)>,
+}
+
+impl LoadOptions {
+    fn from_request(req: &LoadNodeRequest) -> Self {
+        let to_option = |s: String| if s.is_empty() { None } else { Some(s) };
+        let to_params = |seq: &ParameterSeq<0>| {
+            seq.iter()
+                .map(|p| (p.name.get_string(), Value::from(&p.value)))
+                .collect()
+        };
+
+        LoadOptions {
+            node_name: to_option(req.node_name.get_string()),
+            node_namespace: to_option(req.node_namespace.get_string()),
+            remap_rules: req.remap_rules.iter().map(|s| s.get_string()).collect(),
+            parameters: to_params(&req.parameters),
+            extra_arguments: to_params(&req.extra_arguments),
+        }
+    }
+
+    /// Arguments of the node, which consist of the remapping rules and the parameters.
+    fn arguments(&self) -> Result<Vec<String>, DynError> {
+        let mut args = vec!["--ros-args".to_string()];
+
+        for rule in self.remap_rules.iter() {
+            args.push("-r".to_string());
+            args.push(rule.clone());
+        }
+
+        for (name, value) in self.parameters.iter() {
+            args.push("-p".to_string());
+            args.push(format!("{name}:={}", to_yaml(value)?));
+        }
+
+        Ok(args)
+    }
+}
+
+fn to_yaml(value: &Value) -> Result<String, DynError> {
+    let quote = |s: &String| format!("'{}'", s.replace('\'', "''"));
+    let s = match value {
+        Value::Bool(x) => format!("{x}"),
+        Value::I64(x) => format!("{x}"),
+        Value::F64(x) => format!("{x:?}"),
+        Value::String(x) => quote(x),
+        Value::VecBool(x) => format!("{x:?}"),
+        Value::VecI64(x) => format!("{x:?}"),
+        Value::VecU8(x) => format!("{x:?}"),
+        Value::VecF64(x) => format!("{x:?}"),
+        Value::VecString(x) => {
+            let v: Vec<_> = x.iter().map(quote).collect();
+            format!("[{}]", v.join(", "))
+        }
+        Value::NotSet => return Err("a parameter of a component is not set".into()),
+    };
+    Ok(s)
+}
+
+/// Arguments given to a factory.
+pub struct ComponentArgs<'a> {
+    context: &'a Arc<Context>,
+    selector: &'a mut Selector,
+    options: &'a LoadOptions,
+}
+
+impl<'a> ComponentArgs<'a> {
+    /// Get the context shared by all components.
+    pub fn context(&self) -> &Arc<Context> {
+        self.context
+    }
+
+    /// Get the selector shared by all components.
+    /// Entities added to the selector in the factory are removed when the component is unloaded.
+    pub fn selector(&mut self) -> &mut Selector {
+        self.selector
+    }
+
+    /// Get the options to load the component.
+    pub fn options(&self) -> &LoadOptions {
+        self.options
+    }
+
+    /// Return `true` if `use_intra_process_comms` is set to `true` in the extra arguments.
+    pub fn use_intra_process_comms(&self) -> bool {
+        self.options
+            .extra_arguments
+            .iter()
+            .any(|(k, v)| k == "use_intra_process_comms" && matches!(v, Value::Bool(true)))
+    }
+
+    /// Create the node of the component.
+    /// `name` and `namespace` are overridden by the options.
+    /// The remapping rules and the parameters of the options are applied to the node.
+    pub fn create_node(&self, name: &str, namespace: Option<&str>) -> Result<Arc<Node>, DynError> {
+        let name = self.options.node_name.as_deref().unwrap_or(name);
+        let namespace = self.options.node_namespace.as_deref().or(namespace);
+        let options = NodeOptions::new().arguments(self.options.arguments()?);
+        Ok(self.context.create_node(name, namespace, options)?)
+    }
+}
+
+struct LoadedComponent {
+    component: Box<dyn Component>,
+    entities: EntityKeys,
+}
+
+/// Component container.
+/// See the module document.
+pub struct ComponentContainer {
+    node: Arc<Node>,
+    selector: Selector,
+    registry: ComponentRegistry,
+    components: BTreeMap<u64, LoadedComponent>,
+    next_id: u64,
+
+    srv_load: Option<Server<LoadNode>>,
+    srv_unload: Option<Server<UnloadNode>>,
+    srv_list: Option<Server<ListNodes>>,
+    is_requested: Rc<Cell<bool>>,
+
+    /// Handles of loaded libraries, which are never closed
+    /// because callbacks of the selector may refer their code.
+    #[cfg(not(target_os = "windows"))]
+    libraries: Vec<(String, usize)>,
+}
+
+impl ComponentContainer {
+    /// Create a container, which creates a node to serve the services.
+    ///
+    /// # Errors
+    ///
+    /// Return errors occurred when creating the node, the selector, or the services.
+    pub fn new(
+        context: Arc<Context>,
+        name: &str,
+        namespace: Option<&str>,
+    ) -> Result<Self, DynError> {
+        let node = context.create_node(name, namespace, Default::default())?;
+        let mut selector = context.create_selector()?;
+
+        let srv_load = node.create_server::<LoadNode>(
+            &format!("{name}/_container/load_node"),
+            Some(Profile::default()),
+        )?;
+        let srv_unload = node.create_server::<UnloadNode>(
+            &format!("{name}/_container/unload_node"),
+            Some(Profile::default()),
+        )?;
+        let srv_list = node.create_server::<ListNodes>(
+            &format!("{name}/_container/list_nodes"),
+            Some(Profile::default()),
+        )?;
+
+        // Requests are taken after waiting, because factories need the selector.
+        let is_requested = Rc::new(Cell::new(false));
+        for data in [
+            srv_load.data.clone(),
+            srv_unload.data.clone(),
+            srv_list.data.clone(),
+        ] {
+            let flag = is_requested.clone();
+            selector.add_server_data(
+                data,
+                Some(Box::new(move || {
+                    flag.set(true);
+                    CallbackResult::Ok
+                })),
+                false,
+            );
+        }
+
+        Ok(ComponentContainer {
+            node,
+            selector,
+            registry: ComponentRegistry::new(),
+            components: BTreeMap::new(),
+            next_id: 1,
+            srv_load: Some(srv_load),
+            srv_unload: Some(srv_unload),
+            srv_list: Some(srv_list),
+            is_requested,
+            #[cfg(not(target_os = "windows"))]
+            libraries: Vec::new(),
+        })
+    }
+
+    /// Get the node of the container.
+    pub fn get_node(&self) -> &Arc<Node> {
+        &self.node
+    }
+
+    pub fn registry(&self) -> &ComponentRegistry {
+        &self.registry
+    }
+
+    /// Get the registry to register factories statically.
+    pub fn registry_mut(&mut self) -> &mut ComponentRegistry {
+        &mut self.registry
+    }
+
+    /// Get the selector shared by all components.
+    pub fn selector(&mut self) -> &mut Selector {
+        &mut self.selector
+    }
+
+    /// Load factories from a plugin library which exports them by `export_components!`.
+    /// A library is loaded only once.
+    ///
+    /// # Errors
+    ///
+    /// Return an error if the library cannot be loaded, it does not export the entry point,
+    /// the ABI version differs, or it links another copy of `safe_drive`.
+    ///
+    /// # Safety
+    ///
+    /// The library must be built as described in the module document,
+    /// and `PLUGIN_SYMBOL` must be the function defined by `export_components!`.
+    #[cfg(not(target_os = "windows"))]
+    pub unsafe fn load_library(&mut self, path: &str) -> Result<(), DynError> {
+        if self.libraries.iter().any(|(p, _)| p == path) {
+            return Ok(());
+        }
+
+        let c_path = std::ffi::CString::new(path)?;
+        let handle = libc::dlopen(c_path.as_ptr(), libc::RTLD_NOW | libc::RTLD_LOCAL);
+        if handle.is_null() {
+            return Err(format!("failed to load {path}: {}", dlerror()).into());
+        }
+
+        let symbol = std::ffi::CString::new(PLUGIN_SYMBOL).unwrap();
+        let entry = libc::dlsym(handle, symbol.as_ptr());
+        if entry.is_null() {
+            libc::dlclose(handle);
+            return Err(format!("{PLUGIN_SYMBOL} is not found in {path}").into());
+        }
+
+        let entry: ComponentPluginEntry = std::mem::transmute(entry);
+        let plugin = entry();
+
+        if plugin.abi_version != PLUGIN_ABI_VERSION {
+            libc::dlclose(handle);
+            let msg = format!(
+                "the ABI version of {path} is {}, but {PLUGIN_ABI_VERSION} is required",
+                plugin.abi_version
+            );
+            return Err(msg.into());
+        }
+
+        if !std::ptr::eq(plugin.instance, &SAFE_DRIVE_INSTANCE) {
+            libc::dlclose(handle);
+            let msg = format!("{path} must link the same shared library of safe_drive");
+            return Err(msg.into());
+        }
+
+        (plugin.register)(&mut self.registry);
+
+        self.libraries.push((path.to_string(), handle as usize));
+
+        Ok(())
+    }
+
+    /// Load a component, and return its unique ID.
+    /// If `plugin_name` is not registered, the library of `package_name` is loaded.
+    pub fn load(
+        &mut self,
+        package_name: &str,
+        plugin_name: &str,
+        options: LoadOptions,
+    ) -> Result<u64, DynError> {
+        let factory = if let Some(factory) = self.registry.get(plugin_name) {
+            factory
+        } else {
+            self.load_package(package_name)?;
+            self.registry
+                .get(plugin_name)
+                .ok_or_else(|| format!("no such plugin: {plugin_name}"))?
+        };
+
+        let before = self.selector.entity_keys();
+
+        let context = self.node.context.clone();
+        let mut args = ComponentArgs {
+            context: &context,
+            selector: &mut self.selector,
+            options: &options,
+        };
+
+        let result = factory(&mut args);
+        let entities = self.selector.entity_keys().difference(&before);
+
+        let component = match result {
+            Ok(component) => component,
+            Err(e) => {
+                self.selector.remove_entities(&entities);
+                return Err(e);
+            }
+        };
+
+        let id = self.next_id;
+        self.next_id += 1;
+        self.components.insert(
+            id,
+            LoadedComponent {
+                component,
+                entities,
+            },
+        );
+
+        Ok(id)
+    }
+
+    /// Unload a component.
+    /// Entities added to the selector by the factory are removed.
+    pub fn unload(&mut self, id: u64) -> Result<(), DynError> {
+        let loaded = self
+            .components
+            .remove(&id)
+            .ok_or_else(|| format!("no such component: unique_id = {id}"))?;
+        self.selector.remove_entities(&loaded.entities);
+        Ok(())
+    }
+
+    /// Get the fully qualified names and the unique IDs of loaded components.
+    pub fn list(&self) -> Vec<(String, u64)> {
+        self.components
+            .iter()
+            .map(|(id, c)| {
+                let name = c
+                    .component
+                    .get_node()
+                    .get_fully_qualified_name()
+                    .unwrap_or_default();
+                (name, *id)
+            })
+            .collect()
+    }
+
+    /// Wait events of the container and the components, and invoke callbacks.
+    pub fn spin_once(&mut self) -> Result<(), DynError> {
+        self.selector.wait()?;
+        self.handle_requests()
+    }
+
+    /// Same as `spin_once` but this returns `Ok(false)` after `t` duration; timeout.
+    pub fn spin_once_timeout(&mut self, t: Duration) -> Result<bool, DynError> {
+        let is_fired = self.selector.wait_timeout(t)?;
+        self.handle_requests()?;
+        Ok(is_fired)
+    }
+
+    /// Call `spin_once` repeatedly until the context is shut down.
+    pub fn spin(&mut self) -> Result<(), DynError> {
+        while self.node.context.ok() {
+            if let Err(e) = self.spin_once() {
+                // `spin_once` fails if the context is shut down while waiting
+                if self.node.context.ok() {
+                    return Err(e);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_requests(&mut self) -> Result<(), DynError> {
+        if self.is_requested.replace(false) {
+            self.handle_load()?;
+            self.handle_unload()?;
+            self.handle_list()?;
+        }
+        Ok(())
+    }
+
+    /// Load the library of a package from the candidates in order,
+    /// and report all failures if no candidate can be loaded.
+    #[cfg(not(target_os = "windows"))]
+    fn load_package(&mut self, package_name: &str) -> Result<(), DynError> {
+        let file_name = format!("lib{package_name}.so");
+
+        let mut paths: Vec<_> = std::env::var("AMENT_PREFIX_PATH")
+            .unwrap_or_default()
+            .split(':')
+            .filter(|prefix| !prefix.is_empty())
+            .map(|prefix| format!("{prefix}/lib/{file_name}"))
+            .filter(|path| std::path::Path::new(path).exists())
+            .collect();
+
+        // search by the dynamic linker
+        paths.push(file_name);
+
+        let mut errors = Vec::new();
+        for path in paths {
+            match unsafe { self.load_library(&path) } {
+                Ok(()) => return Ok(()),
+                Err(e) => errors.push(e.to_string()),
+            }
+        }
+
+        Err(errors.join("; ").into())
+    }
+
+    #[cfg(target_os = "windows")]
+    fn load_package(&mut self, package_name: &str) -> Result<(), DynError> {
+        Err(format!("loading {package_name} dynamically is not supported").into())
+    }
+
+    fn handle_load(&mut self) -> Result<(), DynError> {
+        let Some(server) = self.srv_load.take() else {
+            return Ok(());
+        };
+
+        let (server, result) = take_request(server, |request| {
+            let mut response = LoadNodeResponse::new().unwrap();
+            let options = LoadOptions::from_request(request);
+            let package_name = request.package_name.get_string();
+            let plugin_name = request.plugin_name.get_string();
+
+            match self.load(&package_name, &plugin_name, options) {
+                Ok(id) => {
+                    let node = self.components[&id].component.get_node();
+                    response.success = true;
+                    response.unique_id = id;
+                    response
+                        .full_node_name
+                        .assign(&node.get_fully_qualified_name().unwrap_or_default());
+                }
+                Err(e) => {
+                    let msg = format!("failed to load {plugin_name}: {e}");
+                    log_error(&msg);
+                    response.error_message.assign(&msg);
+                }
+            }
+
+            response
+        });
+
+        self.srv_load = Some(server);
+        result
+    }
+
+    fn handle_unload(&mut self) -> Result<(), DynError> {
+        let Some(server) = self.srv_unload.take() else {
+            return Ok(());
+        };
+
+        let (server, result) = take_request(server, |request| {
+            let mut response = UnloadNodeResponse::new().unwrap();
+
+            match self.unload(request.unique_id) {
+                Ok(()) => response.success = true,
+                Err(e) => {
+                    response.error_message.assign(&e.to_string());
+                }
+            }
+
+            response
+        });
+
+        self.srv_unload = Some(server);
+        result
+    }
+
+    fn handle_list(&mut self) -> Result<(), DynError> {
+        let Some(server) = self.srv_list.take() else {
+            return Ok(());
+        };
+
+        let (server, result) = take_request(server, |_| {
+            let mut response = ListNodesResponse::new().unwrap();
+            let list = self.list();
+
+            if let (Some(mut names), Some(mut ids)) =
+                (RosStringSeq::new(list.len()), U64Seq::new(list.len()))
+            {
+                names
+                    .iter_mut()
+                    .zip(list.iter())
+                    .for_each(|(dst, (name, _))| {
+                        dst.assign(name);
+                    });
+                ids.as_slice_mut()
+                    .iter_mut()
+                    .zip(list.iter())
+                    .for_each(|(dst, (_, id))| *dst = *id);
+                response.full_node_names = names;
+                response.unique_ids = ids;
+            }
+
+            response
+        });
+
+        self.srv_list = Some(server);
+        result
+    }
+}
+
+impl Drop for ComponentContainer {
+    fn drop(&mut self) {
+        // drop components before the selector, whose callbacks may refer them
+        let ids: Vec<_> = self.components.keys().copied().collect();
+        for id in ids {
+            let _ = self.unload(id);
+        }
+    }
+}
+
+/// Take a request, and send the response created by `f`.
+/// The server is returned on every path, so that it can be restored.
+fn take_request<T: ServiceMsg>(
+    server: Server<T>,
+    f: impl FnOnce(&<T as ServiceMsg>::Request) -> <T as ServiceMsg>::Response,
+) -> (Server<T>, Result<(), DynError>) {
+    let data = server.data.clone();
+    match server.try_recv() {
+        RecvResult::Ok((sender, request, _)) => {
+            let response = f(&request);
+            (send_response(sender.send(&response)), Ok(()))
+        }
+        RecvResult::RetryLater(server) => (server, Ok(())),
+        RecvResult::Err(e) => (Server::from_data(data), Err(e)),
+    }
+}
+
+fn send_response<T, E: std::fmt::Display>(
+    result: Result<Server<T>, (ServerSend<T>, E)>,
+) -> Server<T>
+where
+    T: ServiceMsg,
+{
+    match result {
+        Ok(server) => server,
+        Err((sender, e)) => {
+            log_error(&format!("failed to send a response: {e}"));
+            sender.give_up()
+        }
+    }
+}
+
+fn log_error(msg: &str) {
+    let logger = Logger::new("safe_drive");
+    pr_error_in!(logger, "{msg}");
+}
+
+#[cfg(not(target_os = "windows"))]
+fn dlerror() -> String {
+    let err = unsafe { libc::dlerror() };
+    if err.is_null() {
+        "unknown error".to_string()
+    } else {
+        unsafe { std::ffi::CStr::from_ptr(err) }
+            .to_string_lossy()
+            .into_owned()
+    }
+}
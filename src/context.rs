@@ -16,19 +16,35 @@
 //!
 //! // Create a selector.
 //! let selector = ctx.create_selector().unwrap();
+//!
+//! // Shut down the context explicitly.
+//! ctx.shutdown("finished").unwrap();
+//! assert!(!ctx.ok());
 //! ```
 
 use crate::{
     error::*,
     get_allocator,
+    lifecycle::LifecycleNode,
     node::{Node, NodeOptions},
     rcl,
-    selector::{async_selector::SELECTOR, Selector},
+    selector::{
+        async_selector::{AsyncSelector, SELECTOR},
+        guard_condition::{GuardCondition, RCLGuardCondition},
+        Selector,
+    },
     signal_handler,
+    topic::intra_process::IntraProcessManager,
 };
 use once_cell::sync::Lazy;
 use parking_lot::Mutex;
-use std::{env, ffi::CString, sync::Arc};
+use std::{
+    env,
+    error::Error,
+    ffi::CString,
+    fmt::Display,
+    sync::{Arc, Weak},
+};
 
 static CONTEXT: Lazy<Mutex<Option<Arc<Context>>>> = Lazy::new(|| Mutex::new(None));
 
@@ -40,13 +56,54 @@ static CARGS: Lazy<Vec<usize>> = Lazy::new(|| {
         .collect()
 });
 
+/// Hook which is invoked with the reason when a context is shut down.
+pub type ShutdownHook = Box<dyn FnOnce(&str) + Send + 'static>;
+
+/// ID of a shutdown hook, which is returned by `Context::add_on_shutdown`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ShutdownHookId(u64);
+
+/// Error returned by `Selector::wait` and asynchronous receivers
+/// when their context has been shut down.
+#[derive(Debug, Clone)]
+pub struct Shutdown {
+    /// Reason given to `Context::shutdown`.
+    pub reason: String,
+}
+
+impl Display for Shutdown {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Shutdown: {}", self.reason)
+    }
+}
+
+impl Error for Shutdown {}
+
+#[derive(Default)]
+struct ShutdownState {
+    reason: Option<String>,
+    hooks: Vec<(ShutdownHookId, ShutdownHook)>,
+    next_id: u64,
+
+    /// Guard conditions of selectors, which are triggered to wake up the selectors.
+    selectors: Vec<Weak<RCLGuardCondition>>,
+}
+
 /// Context of ROS2.
 pub struct Context {
     context: rcl::rcl_context_t,
+    pub(crate) intra_process: IntraProcessManager,
+    shutdown: Mutex<ShutdownState>,
+
+    /// Arguments used to initialize the context.
+    arguments: Vec<String>,
 }
 
 impl Context {
     /// Create a new context.
+    /// This is equivalent to `ContextBuilder::new().build()`,
+    /// so command line arguments of the process are parsed,
+    /// and the context is shared in the process.
     ///
     /// # Example
     ///
@@ -57,42 +114,12 @@ impl Context {
     /// let ctx = Context::new().unwrap();
     /// ```
     pub fn new() -> Result<Arc<Self>, DynError> {
-        signal_handler::init();
-
-        {
-            let guard = CONTEXT.lock();
-            if let Some(ctx) = guard.as_ref() {
-                return Ok(ctx.clone());
-            }
-        }
-
-        // allocate context
-        let mut context = rcl::MTSafeFn::rcl_get_zero_initialized_context();
-
-        let options = InitOptions::new()?;
-
-        {
-            let guard = rcl::MT_UNSAFE_FN.lock();
-
-            // initialize context
-            guard.rcl_init(
-                CARGS.len() as i32,
-                CARGS.as_ptr() as *const *const i8,
-                options.as_ptr(),
-                &mut context,
-            )?;
-
-            guard.rcl_logging_configure(&context.global_arguments, &crate::get_allocator())?;
-            guard.rcl_logging_fini()?;
-        }
-
-        let context = Arc::new(Context { context });
-        {
-            let mut guard = CONTEXT.lock();
-            *guard = Some(context.clone());
-        }
+        ContextBuilder::new().build()
+    }
 
-        Ok(context)
+    /// Get the domain ID of the context.
+    pub fn get_domain_id(&self) -> RCLResult<usize> {
+        rcl::MTSafeFn::rcl_context_get_domain_id(unsafe { self.as_ptr_mut() })
     }
 
     /// Create a new node of ROS2.
@@ -120,6 +147,8 @@ impl Context {
     /// - `RCLError::NodeInvalidName` if the name is invalid, or
     /// - `RCLError::NodeInvalidNamespace` if the namespace_ is invalid, or
     /// - `RCLError::Error` if an unspecified error occurs.
+    ///
+    /// Use `safe_drive::names` to get the reason why the name is invalid.
     pub fn create_node(
         self: &Arc<Self>,
         name: &str,
@@ -130,6 +159,37 @@ impl Context {
         Node::new(a, name, namespace, options)
     }
 
+    /// Create a new lifecycle node, which is managed by the standard state machine of ROS2.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::{context::Context, lifecycle::State};
+    ///
+    /// // Create a context.
+    /// let ctx = Context::new().unwrap();
+    ///
+    /// // Create a lifecycle node.
+    /// let node = ctx
+    ///     .create_lifecycle_node("context_rs_lifecycle", None, Default::default())
+    ///     .unwrap();
+    ///
+    /// assert_eq!(node.get_current_state(), State::Unconfigured);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Return errors of `create_node`, or errors occurred when creating
+    /// the services and the publisher of the lifecycle node.
+    pub fn create_lifecycle_node(
+        self: &Arc<Self>,
+        name: &str,
+        namespace: Option<&str>,
+        options: NodeOptions,
+    ) -> Result<LifecycleNode, DynError> {
+        LifecycleNode::new(self, name, namespace, options)
+    }
+
     /// Create a new selector.
     /// The selector is used to wait event and invoke callback for single threaded execution.
     ///
@@ -157,6 +217,102 @@ impl Context {
         Selector::new(self.clone())
     }
 
+    /// Return `true` if the context has not been shut down.
+    pub fn ok(&self) -> bool {
+        rcl::MTSafeFn::rcl_context_is_valid(self.as_ptr())
+    }
+
+    /// Shut down the context.
+    ///
+    /// Registered shutdown hooks are invoked with `reason` in reverse order of registration,
+    /// and then `Selector::wait` and asynchronous receivers of the context
+    /// return `Shutdown` error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::context::ContextBuilder;
+    ///
+    /// let ctx = ContextBuilder::new().independent().build().unwrap();
+    ///
+    /// ctx.add_on_shutdown(Box::new(|reason| println!("shutdown: {reason}")));
+    ///
+    /// ctx.shutdown("finished").unwrap();
+    /// assert!(!ctx.ok());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::AlreadyShutdown` if the context has been already shut down, or
+    /// - `RCLError::InvalidArgument` if any arguments are invalid, or
+    /// - `RCLError::Error` if an unspecified error occurs.
+    pub fn shutdown(&self, reason: &str) -> RCLResult<()> {
+        self.shutdown_and_wake(reason)?;
+
+        // the thread of the asynchronous selector terminates by itself
+        SELECTOR.lock().remove_context(self);
+
+        Ok(())
+    }
+
+    /// Register a hook invoked when the context is shut down.
+    /// Hooks are invoked in reverse order of registration.
+    pub fn add_on_shutdown(&self, hook: ShutdownHook) -> ShutdownHookId {
+        let mut guard = self.shutdown.lock();
+        let id = ShutdownHookId(guard.next_id);
+        guard.next_id += 1;
+        guard.hooks.push((id, hook));
+        id
+    }
+
+    /// Remove a hook registered by `add_on_shutdown`.
+    /// Return `false` if the hook does not exist.
+    pub fn remove_on_shutdown(&self, id: ShutdownHookId) -> bool {
+        let mut guard = self.shutdown.lock();
+        let len = guard.hooks.len();
+        guard.hooks.retain(|(hook_id, _)| *hook_id != id);
+        len != guard.hooks.len()
+    }
+
+    /// Return `Shutdown` error if the context has been shut down.
+    pub(crate) fn check_ok(&self) -> Result<(), Shutdown> {
+        if self.ok() {
+            Ok(())
+        } else {
+            let reason = self.shutdown.lock().reason.clone().unwrap_or_default();
+            Err(Shutdown { reason })
+        }
+    }
+
+    /// Register the guard condition of a selector to wake it up when shutting down.
+    pub(crate) fn register_selector(&self, cond: &GuardCondition) {
+        let mut guard = self.shutdown.lock();
+        guard.selectors.retain(|c| c.strong_count() > 0);
+        guard.selectors.push(Arc::downgrade(&cond.cond));
+    }
+
+    fn shutdown_and_wake(&self, reason: &str) -> RCLResult<()> {
+        let (hooks, selectors) = {
+            let mut guard = self.shutdown.lock();
+            rcl::MTSafeFn::rcl_shutdown(unsafe { self.as_ptr_mut() })?;
+            guard.reason = Some(reason.to_string());
+            (
+                std::mem::take(&mut guard.hooks),
+                std::mem::take(&mut guard.selectors),
+            )
+        };
+
+        for (_, hook) in hooks.into_iter().rev() {
+            hook(reason);
+        }
+
+        for cond in selectors.iter().filter_map(|c| c.upgrade()) {
+            let _ = GuardCondition { cond }.trigger();
+        }
+
+        Ok(())
+    }
+
     pub(crate) fn as_ptr(&self) -> *const rcl::rcl_context_t {
         &self.context as *const _
     }
@@ -168,7 +324,9 @@ impl Context {
 
 impl Drop for Context {
     fn drop(&mut self) {
-        rcl::MTSafeFn::rcl_shutdown(&mut self.context).unwrap();
+        if self.ok() {
+            self.shutdown_and_wake("context was dropped").unwrap();
+        }
         {
             let guard = rcl::MT_UNSAFE_FN.lock();
             guard.rcl_context_fini(&mut self.context).unwrap();
@@ -176,6 +334,184 @@ impl Drop for Context {
     }
 }
 
+/// Builder of contexts.
+///
+/// By default, a context is created by the command line arguments of the process,
+/// and it is shared in the process.
+/// A context can be created by explicit arguments and domain ID,
+/// and independent contexts can be created by `ContextBuilder::independent`.
+///
+/// # Example
+///
+/// ```
+/// use safe_drive::context::ContextBuilder;
+///
+/// // Create independent contexts of different domains.
+/// let ctx1 = ContextBuilder::new()
+///     .arguments(["bridge", "--ros-args", "-r", "__node:=bridge1"])
+///     .domain_id(1)
+///     .independent()
+///     .build()
+///     .unwrap();
+///
+/// let ctx2 = ContextBuilder::new()
+///     .domain_id(2)
+///     .independent()
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(ctx1.get_domain_id().unwrap(), 1);
+/// assert_eq!(ctx2.get_domain_id().unwrap(), 2);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ContextBuilder {
+    arguments: Option<Vec<String>>,
+    domain_id: Option<usize>,
+    independent: bool,
+}
+
+impl ContextBuilder {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set arguments instead of the command line arguments of the process.
+    /// Like `std::env::args()`, the first argument is the program name,
+    /// and ROS specific arguments must be enclosed by `--ros-args` and `--`.
+    pub fn arguments<I, S>(mut self, arguments: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.arguments = Some(arguments.into_iter().map(|arg| arg.into()).collect());
+        self
+    }
+
+    /// Set the domain ID.
+    /// If this is not set, `ROS_DOMAIN_ID` environment variable is used.
+    pub fn domain_id(mut self, domain_id: usize) -> Self {
+        self.domain_id = Some(domain_id);
+        self
+    }
+
+    /// Create a context which is not shared in the process.
+    ///
+    /// By default, the first context is shared in the process,
+    /// and `build()` returns the shared context if it exists.
+    pub fn independent(mut self) -> Self {
+        self.independent = true;
+        self
+    }
+
+    /// Create a context.
+    ///
+    /// If `independent()` is not called and the shared context has been already created,
+    /// the shared context is returned.
+    /// If the shared context has been shut down, it is replaced by a new context.
+    /// In that case, it fails if the domain ID or the arguments are specified
+    /// and they differ from those of the shared context.
+    ///
+    /// # Errors
+    ///
+    /// - an error if the options differ from those of the shared context, or
+    /// - `RCLError::AlreadyInit` if the context has already be initialized, or
+    /// - `RCLError::InvalidArgument` if any arguments are invalid, or
+    /// - `RCLError::BadAlloc` if allocating memory failed, or
+    /// - `RCLError::InvalidRosArgs` if the arguments are invalid, or
+    /// - `RCLError::Error` if an unspecified error occurs.
+    pub fn build(self) -> Result<Arc<Context>, DynError> {
+        signal_handler::init();
+
+        if self.independent {
+            return Ok(Arc::new(self.init()?));
+        }
+
+        let mut guard = CONTEXT.lock();
+        if let Some(ctx) = guard.as_ref().filter(|ctx| ctx.ok()) {
+            self.check_shared(ctx)?;
+            return Ok(ctx.clone());
+        }
+
+        let context = Arc::new(self.init()?);
+        *guard = Some(context.clone());
+
+        Ok(context)
+    }
+
+    /// Check whether the shared context satisfies the specified options.
+    fn check_shared(&self, ctx: &Context) -> Result<(), DynError> {
+        if let Some(domain_id) = self.domain_id {
+            let shared = ctx.get_domain_id()?;
+            if domain_id != shared {
+                let msg = format!(
+                    "the domain ID {domain_id} differs from {shared} of the shared context, use independent()"
+                );
+                return Err(msg.into());
+            }
+        }
+
+        if let Some(arguments) = &self.arguments {
+            if *arguments != ctx.arguments {
+                let msg = format!(
+                    "the arguments {arguments:?} differ from {:?} of the shared context, use independent()",
+                    ctx.arguments
+                );
+                return Err(msg.into());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn init(&self) -> Result<Context, DynError> {
+        // allocate context
+        let mut context = rcl::MTSafeFn::rcl_get_zero_initialized_context();
+
+        let mut options = InitOptions::new()?;
+
+        let guard = rcl::MT_UNSAFE_FN.lock();
+
+        if let Some(domain_id) = self.domain_id {
+            guard.rcl_init_options_set_domain_id(options.as_ptr_mut(), domain_id)?;
+        }
+
+        // initialize context
+        if let Some(arguments) = &self.arguments {
+            let args = arguments
+                .iter()
+                .map(|arg| CString::new(arg.as_str()))
+                .collect::<Result<Vec<_>, _>>()?;
+            let argv: Vec<_> = args.iter().map(|arg| arg.as_ptr()).collect();
+            guard.rcl_init(
+                argv.len() as i32,
+                argv.as_ptr(),
+                options.as_ptr(),
+                &mut context,
+            )?;
+        } else {
+            guard.rcl_init(
+                CARGS.len() as i32,
+                CARGS.as_ptr() as *const *const i8,
+                options.as_ptr(),
+                &mut context,
+            )?;
+        }
+
+        guard.rcl_logging_configure(&context.global_arguments, &crate::get_allocator())?;
+        guard.rcl_logging_fini()?;
+
+        Ok(Context {
+            context,
+            intra_process: IntraProcessManager::new(),
+            shutdown: Default::default(),
+            arguments: self
+                .arguments
+                .clone()
+                .unwrap_or_else(|| env::args().collect()),
+        })
+    }
+}
+
 /// Options for the initialization of the context.
 pub(crate) struct InitOptions {
     options: rcl::rcl_init_options_t,
@@ -218,7 +554,7 @@ unsafe impl Send for Context {}
 #[no_mangle]
 pub(crate) extern "C" fn remove_context() {
     {
-        let _ = SELECTOR.lock().halt();
+        let _ = AsyncSelector::halt();
     }
     signal_handler::halt();
 
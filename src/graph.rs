@@ -0,0 +1,458 @@
+//! Introspection of the ROS graph.
+//!
+//! Nodes, topics, and services in the ROS graph can be queried by methods of `Node`.
+//! All results are copied into owned Rust types.
+//!
+//! # Example
+//!
+//! ```
+//! use safe_drive::{context::Context, logger::Logger, pr_info};
+//!
+//! let ctx = Context::new().unwrap();
+//! let node = ctx.create_node("graph_rs", None, Default::default()).unwrap();
+//! let logger = Logger::new("graph_rs");
+//!
+//! // List nodes.
+//! for (name, namespace) in node.get_node_names_and_namespaces().unwrap() {
+//!     pr_info!(logger, "node: name = {name}, namespace = {namespace}");
+//! }
+//!
+//! // List topics and their types.
+//! for (topic, types) in node.get_topic_names_and_types().unwrap() {
+//!     pr_info!(logger, "topic: {topic}, types = {:?}", types);
+//! }
+//!
+//! // Show publishers of a topic.
+//! for info in node.get_publishers_info_by_topic("/rosout").unwrap() {
+//!     pr_info!(logger, "publisher of /rosout: {}/{}", info.node_namespace, info.node_name);
+//! }
+//! ```
+
+use crate::{
+    error::{DynError, RCLError, RCLResult},
+    get_allocator, is_halt,
+    node::Node,
+    qos::Profile,
+    rcl,
+    selector::{
+        async_selector::{self, SELECTOR},
+        guard_condition::GuardCondition,
+        CallbackResult,
+    },
+    signal_handler::Signaled,
+};
+use std::{
+    collections::BTreeMap,
+    ffi::{CStr, CString},
+    future::Future,
+    os::raw::c_char,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::{self, Poll},
+};
+
+/// Names and types of topics or services.
+/// Each key is a name, and each value is a list of types of the name.
+pub type NamesAndTypes = BTreeMap<String, Vec<String>>;
+
+/// Kinds of endpoints of topics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EndpointType {
+    Publisher,
+    Subscription,
+}
+
+/// Information of an endpoint of a topic.
+#[derive(Debug, Clone)]
+pub struct TopicEndpointInfo {
+    /// Name of the node of the endpoint.
+    pub node_name: String,
+
+    /// Namespace of the node of the endpoint.
+    pub node_namespace: String,
+
+    /// Type name of the topic.
+    pub topic_type: String,
+
+    /// Whether the endpoint is a publisher or a subscription.
+    pub endpoint_type: EndpointType,
+
+    /// Global unique identifier of the endpoint.
+    pub endpoint_gid: Vec<u8>,
+
+    /// QoS profile of the endpoint.
+    pub qos_profile: Profile,
+}
+
+pub(crate) fn get_node_names_and_namespaces(node: &Node) -> RCLResult<Vec<(String, String)>> {
+    let mut names = rcl::MTSafeFn::rcutils_get_zero_initialized_string_array();
+    let mut namespaces = rcl::MTSafeFn::rcutils_get_zero_initialized_string_array();
+
+    let guard = rcl::MT_UNSAFE_FN.lock();
+    guard.rcl_get_node_names(node.as_ptr(), get_allocator(), &mut names, &mut namespaces)?;
+
+    let result = to_strings(&names)
+        .into_iter()
+        .zip(to_strings(&namespaces))
+        .collect();
+
+    guard.rcutils_string_array_fini(&mut names)?;
+    guard.rcutils_string_array_fini(&mut namespaces)?;
+
+    Ok(result)
+}
+
+pub(crate) fn get_topic_names_and_types(node: &Node) -> RCLResult<NamesAndTypes> {
+    let guard = rcl::MT_UNSAFE_FN.lock();
+    get_names_and_types(&guard, |allocator, names_and_types| {
+        guard.rcl_get_topic_names_and_types(node.as_ptr(), allocator, false, names_and_types)
+    })
+}
+
+pub(crate) fn get_service_names_and_types(node: &Node) -> RCLResult<NamesAndTypes> {
+    let guard = rcl::MT_UNSAFE_FN.lock();
+    get_names_and_types(&guard, |allocator, names_and_types| {
+        guard.rcl_get_service_names_and_types(node.as_ptr(), allocator, names_and_types)
+    })
+}
+
+/// Kinds of queries of names and types by a node.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum ByNode {
+    Publisher,
+    Subscriber,
+    Service,
+    Client,
+}
+
+pub(crate) fn get_names_and_types_by_node(
+    node: &Node,
+    kind: ByNode,
+    node_name: &str,
+    node_namespace: &str,
+) -> RCLResult<NamesAndTypes> {
+    let node_name = to_cstring(node_name)?;
+    let node_namespace = to_cstring(node_namespace)?;
+
+    let guard = rcl::MT_UNSAFE_FN.lock();
+    get_names_and_types(&guard, |allocator, names_and_types| {
+        let (name, namespace) = (node_name.as_ptr(), node_namespace.as_ptr());
+        match kind {
+            ByNode::Publisher => guard.rcl_get_publisher_names_and_types_by_node(
+                node.as_ptr(),
+                allocator,
+                false,
+                name,
+                namespace,
+                names_and_types,
+            ),
+            ByNode::Subscriber => guard.rcl_get_subscriber_names_and_types_by_node(
+                node.as_ptr(),
+                allocator,
+                false,
+                name,
+                namespace,
+                names_and_types,
+            ),
+            ByNode::Service => guard.rcl_get_service_names_and_types_by_node(
+                node.as_ptr(),
+                allocator,
+                name,
+                namespace,
+                names_and_types,
+            ),
+            ByNode::Client => guard.rcl_get_client_names_and_types_by_node(
+                node.as_ptr(),
+                allocator,
+                name,
+                namespace,
+                names_and_types,
+            ),
+        }
+    })
+}
+
+pub(crate) fn get_endpoints_info_by_topic(
+    node: &Node,
+    endpoint_type: EndpointType,
+    topic_name: &str,
+) -> RCLResult<Vec<TopicEndpointInfo>> {
+    let guard = rcl::MT_UNSAFE_FN.lock();
+    let topic_name = guard.rcl_node_resolve_name(node.as_ptr(), topic_name, false, false)?;
+    let topic_name = to_cstring(&topic_name)?;
+
+    let mut allocator = get_allocator();
+    let mut info_array = rcl::MTSafeFn::rmw_get_zero_initialized_topic_endpoint_info_array();
+
+    match endpoint_type {
+        EndpointType::Publisher => guard.rcl_get_publishers_info_by_topic(
+            node.as_ptr(),
+            &mut allocator,
+            topic_name.as_ptr(),
+            false,
+            &mut info_array,
+        )?,
+        EndpointType::Subscription => guard.rcl_get_subscriptions_info_by_topic(
+            node.as_ptr(),
+            &mut allocator,
+            topic_name.as_ptr(),
+            false,
+            &mut info_array,
+        )?,
+    }
+
+    let infos = if info_array.info_array.is_null() {
+        &[]
+    } else {
+        unsafe { std::slice::from_raw_parts(info_array.info_array, info_array.size as _) }
+    };
+
+    let result = infos
+        .iter()
+        .map(|info| TopicEndpointInfo {
+            node_name: to_string(info.node_name),
+            node_namespace: to_string(info.node_namespace),
+            topic_type: to_string(info.topic_type),
+            endpoint_type,
+            endpoint_gid: info.endpoint_gid.to_vec(),
+            qos_profile: (&info.qos_profile).into(),
+        })
+        .collect();
+
+    guard.rmw_topic_endpoint_info_array_fini(&mut info_array, &mut allocator)?;
+
+    Ok(result)
+}
+
+pub(crate) fn count_endpoints(
+    node: &Node,
+    endpoint_type: EndpointType,
+    topic_name: &str,
+) -> RCLResult<usize> {
+    let guard = rcl::MT_UNSAFE_FN.lock();
+    let topic_name = guard.rcl_node_resolve_name(node.as_ptr(), topic_name, false, false)?;
+    let topic_name = to_cstring(&topic_name)?;
+
+    match endpoint_type {
+        EndpointType::Publisher => guard.rcl_count_publishers(node.as_ptr(), topic_name.as_ptr()),
+        EndpointType::Subscription => {
+            guard.rcl_count_subscribers(node.as_ptr(), topic_name.as_ptr())
+        }
+    }
+}
+
+/// Call `f` to get names and types, and convert them into `NamesAndTypes`.
+fn get_names_and_types<F>(guard: &rcl::MTUnsafeFn, f: F) -> RCLResult<NamesAndTypes>
+where
+    F: FnOnce(*mut rcl::rcl_allocator_t, *mut rcl::rcl_names_and_types_t) -> RCLResult<()>,
+{
+    let mut allocator = get_allocator();
+    let mut names_and_types = rcl::MTSafeFn::rmw_get_zero_initialized_names_and_types();
+
+    f(&mut allocator, &mut names_and_types)?;
+
+    let names = to_strings(&names_and_types.names);
+    let types = if names_and_types.types.is_null() {
+        &[]
+    } else {
+        unsafe { std::slice::from_raw_parts(names_and_types.types, names.len()) }
+    };
+
+    let result = names
+        .into_iter()
+        .zip(types.iter().map(to_strings))
+        .collect();
+
+    guard.rcl_names_and_types_fini(&mut names_and_types)?;
+
+    Ok(result)
+}
+
+fn to_strings(array: &rcl::rcutils_string_array_t) -> Vec<String> {
+    if array.data.is_null() {
+        return Vec::new();
+    }
+
+    let data = unsafe { std::slice::from_raw_parts(array.data, array.size as _) };
+    data.iter().map(|s| to_string(*s)).collect()
+}
+
+fn to_string(s: *const c_char) -> String {
+    if s.is_null() {
+        String::new()
+    } else {
+        unsafe { CStr::from_ptr(s) }.to_string_lossy().into_owned()
+    }
+}
+
+fn to_cstring(s: &str) -> RCLResult<CString> {
+    CString::new(s).map_err(|_| RCLError::InvalidArgument)
+}
+
+/// Watcher of changes of the ROS graph.
+///
+/// A watcher is notified by the graph guard condition of a node,
+/// which is triggered when nodes, publishers, subscriptions, services, or clients
+/// appear or disappear in the ROS graph.
+/// A watcher can be created by `Node::create_graph_watcher`,
+/// and several watchers of a node can be used at the same time.
+///
+/// Use `GraphWatcher::changed` or `GraphWatcher::wait_for` in async functions,
+/// or `Selector::add_graph_watcher` to invoke a callback function.
+///
+/// # Example
+///
+/// ```
+/// use safe_drive::{error::DynError, node::Node};
+/// use std::sync::Arc;
+///
+/// async fn wait_for_talker(node: Arc<Node>) -> Result<(), DynError> {
+///     let mut watcher = node.create_graph_watcher()?;
+///
+///     // Wait until a publisher of "/chatter" appears.
+///     watcher.wait_for_publisher("/chatter").await?;
+///
+///     // Wait until the node "/talker" goes away.
+///     watcher.wait_for_node_gone("/talker").await
+/// }
+/// ```
+pub struct GraphWatcher {
+    node: Arc<Node>,
+    pub(crate) cond: GuardCondition,
+}
+
+impl GraphWatcher {
+    pub(crate) fn new(node: Arc<Node>) -> Result<Self, DynError> {
+        let cond = GuardCondition::graph(node.clone())?;
+        Ok(GraphWatcher { node, cond })
+    }
+
+    /// Get the node watched by this watcher.
+    pub fn get_node(&self) -> &Arc<Node> {
+        &self.node
+    }
+
+    /// Wait until the ROS graph changes.
+    ///
+    /// A change may be reported even if the change was made
+    /// before calling this function, so check the graph again after waiting.
+    pub async fn changed(&mut self) -> Result<(), DynError> {
+        AsyncGraphChange {
+            watcher: self,
+            changed: Arc::new(AtomicBool::new(false)),
+            is_waiting: false,
+        }
+        .await
+    }
+
+    /// Wait until `f` returns `true`.
+    /// `f` is called immediately, and is called again whenever the ROS graph changes.
+    pub async fn wait_for<F>(&mut self, mut f: F) -> Result<(), DynError>
+    where
+        F: FnMut(&Node) -> Result<bool, DynError>,
+    {
+        loop {
+            if f(&self.node)? {
+                return Ok(());
+            }
+            self.changed().await?;
+        }
+    }
+
+    /// Wait until a publisher of `topic_name` appears.
+    pub async fn wait_for_publisher(&mut self, topic_name: &str) -> Result<(), DynError> {
+        self.wait_for(|node| Ok(node.count_publishers(topic_name)? > 0))
+            .await
+    }
+
+    /// Wait until all publishers of `topic_name` go away.
+    pub async fn wait_for_publisher_gone(&mut self, topic_name: &str) -> Result<(), DynError> {
+        self.wait_for(|node| Ok(node.count_publishers(topic_name)? == 0))
+            .await
+    }
+
+    /// Wait until a subscriber of `topic_name` appears.
+    pub async fn wait_for_subscriber(&mut self, topic_name: &str) -> Result<(), DynError> {
+        self.wait_for(|node| Ok(node.count_subscribers(topic_name)? > 0))
+            .await
+    }
+
+    /// Wait until all subscribers of `topic_name` go away.
+    pub async fn wait_for_subscriber_gone(&mut self, topic_name: &str) -> Result<(), DynError> {
+        self.wait_for(|node| Ok(node.count_subscribers(topic_name)? == 0))
+            .await
+    }
+
+    /// Wait until a node whose fully qualified name is `node_fqn` appears.
+    pub async fn wait_for_node(&mut self, node_fqn: &str) -> Result<(), DynError> {
+        self.wait_for(|node| Ok(node.get_node_names()?.iter().any(|n| n == node_fqn)))
+            .await
+    }
+
+    /// Wait until a node whose fully qualified name is `node_fqn` goes away.
+    pub async fn wait_for_node_gone(&mut self, node_fqn: &str) -> Result<(), DynError> {
+        self.wait_for(|node| Ok(node.get_node_names()?.iter().all(|n| n != node_fqn)))
+            .await
+    }
+}
+
+/// Future which is ready when the ROS graph changes.
+struct AsyncGraphChange<'a> {
+    watcher: &'a mut GraphWatcher,
+    changed: Arc<AtomicBool>,
+    is_waiting: bool,
+}
+
+impl<'a> Future for AsyncGraphChange<'a> {
+    type Output = Result<(), DynError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Self::Output> {
+        if is_halt() {
+            return Poll::Ready(Err(Signaled.into()));
+        }
+        self.watcher.node.context.check_ok()?;
+
+        let this = self.get_mut();
+
+        if this.changed.load(Ordering::Acquire) {
+            this.is_waiting = false;
+            return Poll::Ready(Ok(()));
+        }
+
+        let changed = this.changed.clone();
+        let mut waker = Some(cx.waker().clone());
+
+        let mut guard = SELECTOR.lock();
+        guard.send_command(
+            &this.watcher.node.context,
+            async_selector::Command::ConditionVar(
+                this.watcher.cond.clone(),
+                Box::new(move || {
+                    changed.store(true, Ordering::Release);
+                    if let Some(w) = waker.take() {
+                        w.wake();
+                    }
+                    CallbackResult::Ok
+                }),
+            ),
+        )?;
+
+        this.is_waiting = true;
+        Poll::Pending
+    }
+}
+
+impl<'a> Drop for AsyncGraphChange<'a> {
+    fn drop(&mut self) {
+        if self.is_waiting {
+            let mut guard = SELECTOR.lock();
+            let _ = guard.send_command(
+                &self.watcher.node.context,
+                async_selector::Command::RemoveConditionVar(self.watcher.cond.clone()),
+            );
+        }
+    }
+}
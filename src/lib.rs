@@ -223,11 +223,15 @@ pub mod allocator;
 
 pub mod action;
 pub mod clock;
+pub mod component;
 pub mod context;
 pub mod error;
+pub mod graph;
 pub mod helper;
+pub mod lifecycle;
 pub mod logger;
 pub mod msg;
+pub mod names;
 pub mod node;
 pub mod parameter;
 pub mod publisher_loaned_message;
@@ -236,11 +240,12 @@ pub mod rcl;
 pub mod selector;
 pub mod service;
 pub mod subscriber_loaned_message;
+pub mod time;
+pub mod time_source;
 pub mod topic;
 
 mod delta_list;
 mod signal_handler;
-mod time;
 
 type PhantomUnsync = PhantomData<Cell<()>>;
 type PhantomUnsend = PhantomData<MutexGuard<'static, ()>>;
@@ -0,0 +1,896 @@
+//! Lifecycle node, which is managed by the standard state machine of ROS2.
+//!
+//! A lifecycle node starts in the unconfigured state,
+//! and its state is changed by transitions requested by
+//! the `change_state` service or by methods of `LifecycleNode`.
+//! The state machine is as follows.
+//!
+//! ```text
+//!                 configure           activate
+//! unconfigured ------------> inactive ----------> active
+//!              <------------          <----------
+//!                 cleanup             deactivate
+//!
+//! unconfigured, inactive, active --(shutdown)--> finalized
+//! ```
+//!
+//! During a transition, the node is in a transition state,
+//! and a user hook such as `on_configure` is invoked.
+//! The hook decides the goal state by returning `CallbackReturn`.
+//! If the hook returns `CallbackReturn::Error`, the node goes to the error processing state,
+//! and `on_error` decides whether the node goes to the unconfigured or the finalized state.
+//!
+//! A lifecycle node serves the following services, and publishes transitions to `<node name>/transition_event`.
+//!
+//! - `<node name>/change_state`
+//! - `<node name>/get_state`
+//! - `<node name>/get_available_states`
+//! - `<node name>/get_available_transitions`
+//!
+//! # Example
+//!
+//! ```
+//! use safe_drive::{
+//!     context::Context,
+//!     lifecycle::{CallbackReturn, State},
+//!     msg::common_interfaces::std_msgs,
+//! };
+//!
+//! let ctx = Context::new().unwrap();
+//! let node = ctx
+//!     .create_lifecycle_node("lifecycle_rs", None, Default::default())
+//!     .unwrap();
+//!
+//! // Register a hook.
+//! node.set_on_configure(Box::new(|_previous| CallbackReturn::Success));
+//!
+//! // Messages are sent only when the node is active.
+//! let publisher = node
+//!     .create_publisher::<std_msgs::msg::UInt32>("lifecycle_rs_topic", None)
+//!     .unwrap();
+//!
+//! assert_eq!(node.configure().unwrap(), State::Inactive);
+//! assert_eq!(node.activate().unwrap(), State::Active);
+//!
+//! let msg = std_msgs::msg::UInt32::new().unwrap();
+//! publisher.send(&msg).unwrap();
+//! ```
+
+use crate::{
+    context::Context,
+    error::{DynError, RCLResult},
+    logger::{pr_error_in, pr_warn_in, Logger},
+    msg::{
+        interfaces::lifecycle_msgs::{
+            self,
+            msg::{StateSeq, TransitionDescriptionSeq, TransitionEvent},
+            srv::{
+                ChangeState, ChangeStateResponse, GetAvailableStates, GetAvailableStatesResponse,
+                GetAvailableTransitions, GetAvailableTransitionsResponse, GetState,
+                GetStateResponse,
+            },
+        },
+        TypeSupport,
+    },
+    node::{Node, NodeOptions},
+    qos::Profile,
+    selector::{guard_condition::GuardCondition, CallbackResult, Selector},
+    topic::publisher::Publisher,
+};
+use parking_lot::Mutex;
+use std::{
+    cell::Cell,
+    fmt::Display,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread::JoinHandle,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// States of the state machine.
+/// The first four states are primary states, and the others are transition states.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum State {
+    Unconfigured,
+    Inactive,
+    Active,
+    Finalized,
+    Configuring,
+    CleaningUp,
+    ShuttingDown,
+    Activating,
+    Deactivating,
+    ErrorProcessing,
+}
+
+impl State {
+    const ALL: [State; 10] = [
+        State::Unconfigured,
+        State::Inactive,
+        State::Active,
+        State::Finalized,
+        State::Configuring,
+        State::CleaningUp,
+        State::ShuttingDown,
+        State::Activating,
+        State::Deactivating,
+        State::ErrorProcessing,
+    ];
+
+    /// ID defined by `lifecycle_msgs/msg/State`.
+    pub fn id(&self) -> u8 {
+        use lifecycle_msgs::msg::*;
+        match self {
+            Self::Unconfigured => PRIMARY_STATE_UNCONFIGURED,
+            Self::Inactive => PRIMARY_STATE_INACTIVE,
+            Self::Active => PRIMARY_STATE_ACTIVE,
+            Self::Finalized => PRIMARY_STATE_FINALIZED,
+            Self::Configuring => TRANSITION_STATE_CONFIGURING,
+            Self::CleaningUp => TRANSITION_STATE_CLEANINGUP,
+            Self::ShuttingDown => TRANSITION_STATE_SHUTTINGDOWN,
+            Self::Activating => TRANSITION_STATE_ACTIVATING,
+            Self::Deactivating => TRANSITION_STATE_DEACTIVATING,
+            Self::ErrorProcessing => TRANSITION_STATE_ERRORPROCESSING,
+        }
+    }
+
+    /// Label used in messages.
+    pub fn label(&self) -> &'static str {
+        match self {
+            State::Unconfigured => "unconfigured",
+            State::Inactive => "inactive",
+            State::Active => "active",
+            State::Finalized => "finalized",
+            State::Configuring => "configuring",
+            State::CleaningUp => "cleaningup",
+            State::ShuttingDown => "shuttingdown",
+            State::Activating => "activating",
+            State::Deactivating => "deactivating",
+            State::ErrorProcessing => "errorprocessing",
+        }
+    }
+
+    /// Return `true` if this is a primary state.
+    pub fn is_primary(&self) -> bool {
+        matches!(
+            self,
+            State::Unconfigured | State::Inactive | State::Active | State::Finalized
+        )
+    }
+
+    fn to_msg(self) -> lifecycle_msgs::msg::State {
+        let mut msg = lifecycle_msgs::msg::State::new().unwrap();
+        msg.id = self.id();
+        msg.label.assign(self.label());
+        msg
+    }
+}
+
+impl Display for State {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+/// Transitions which can be requested.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transition {
+    Configure,
+    Cleanup,
+    Activate,
+    Deactivate,
+    UnconfiguredShutdown,
+    InactiveShutdown,
+    ActiveShutdown,
+}
+
+impl Transition {
+    const ALL: [Transition; 7] = [
+        Transition::Configure,
+        Transition::Cleanup,
+        Transition::Activate,
+        Transition::Deactivate,
+        Transition::UnconfiguredShutdown,
+        Transition::InactiveShutdown,
+        Transition::ActiveShutdown,
+    ];
+
+    /// ID defined by `lifecycle_msgs/msg/Transition`.
+    pub fn id(&self) -> u8 {
+        use lifecycle_msgs::msg::*;
+        match self {
+            Self::Configure => TRANSITION_CONFIGURE,
+            Self::Cleanup => TRANSITION_CLEANUP,
+            Self::Activate => TRANSITION_ACTIVATE,
+            Self::Deactivate => TRANSITION_DEACTIVATE,
+            Self::UnconfiguredShutdown => TRANSITION_UNCONFIGURED_SHUTDOWN,
+            Self::InactiveShutdown => TRANSITION_INACTIVE_SHUTDOWN,
+            Self::ActiveShutdown => TRANSITION_ACTIVE_SHUTDOWN,
+        }
+    }
+
+    /// Label used in messages.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Transition::Configure => "configure",
+            Transition::Cleanup => "cleanup",
+            Transition::Activate => "activate",
+            Transition::Deactivate => "deactivate",
+            Transition::UnconfiguredShutdown
+            | Transition::InactiveShutdown
+            | Transition::ActiveShutdown => "shutdown",
+        }
+    }
+
+    /// The primary state from which this transition starts.
+    pub fn start_state(&self) -> State {
+        match self {
+            Transition::Configure | Transition::UnconfiguredShutdown => State::Unconfigured,
+            Transition::Cleanup | Transition::Activate | Transition::InactiveShutdown => {
+                State::Inactive
+            }
+            Transition::Deactivate | Transition::ActiveShutdown => State::Active,
+        }
+    }
+
+    /// The transition state in which a user hook is invoked.
+    pub fn goal_state(&self) -> State {
+        match self {
+            Transition::Configure => State::Configuring,
+            Transition::Cleanup => State::CleaningUp,
+            Transition::Activate => State::Activating,
+            Transition::Deactivate => State::Deactivating,
+            Transition::UnconfiguredShutdown
+            | Transition::InactiveShutdown
+            | Transition::ActiveShutdown => State::ShuttingDown,
+        }
+    }
+
+    /// The primary state reached when the hook of this transition succeeds.
+    pub fn success_state(&self) -> State {
+        result_transition(self.goal_state(), CallbackReturn::Success).2
+    }
+
+    /// Find a transition from `state` by ID,
+    /// or by label if `id` is `TRANSITION_CREATE` (zero).
+    fn find(state: State, id: u8, label: &str) -> Option<Self> {
+        Transition::ALL
+            .into_iter()
+            .filter(|t| t.start_state() == state)
+            .find(|t| {
+                if id == lifecycle_msgs::msg::TRANSITION_CREATE {
+                    t.label() == label
+                } else {
+                    t.id() == id
+                }
+            })
+    }
+}
+
+impl Display for Transition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+/// Return value of user hooks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CallbackReturn {
+    /// Go to the next primary state.
+    Success,
+
+    /// Go back to the previous primary state.
+    Failure,
+
+    /// Go to the error processing state.
+    Error,
+}
+
+/// User hook invoked with the previous primary state.
+pub type LifecycleCallback = Box<dyn FnMut(State) -> CallbackReturn + Send + 'static>;
+
+#[derive(Default)]
+struct Callbacks {
+    on_configure: Option<LifecycleCallback>,
+    on_cleanup: Option<LifecycleCallback>,
+    on_activate: Option<LifecycleCallback>,
+    on_deactivate: Option<LifecycleCallback>,
+    on_shutdown: Option<LifecycleCallback>,
+    on_error: Option<LifecycleCallback>,
+}
+
+impl Callbacks {
+    /// The hook of a transition state.
+    fn get_mut(&mut self, transition_state: State) -> &mut Option<LifecycleCallback> {
+        match transition_state {
+            State::Configuring => &mut self.on_configure,
+            State::CleaningUp => &mut self.on_cleanup,
+            State::Activating => &mut self.on_activate,
+            State::Deactivating => &mut self.on_deactivate,
+            State::ShuttingDown => &mut self.on_shutdown,
+            State::ErrorProcessing => &mut self.on_error,
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// Return the ID, label and goal state of the transition from a transition state.
+fn result_transition(transition_state: State, ret: CallbackReturn) -> (u8, &'static str, State) {
+    use lifecycle_msgs::msg::{
+        TRANSITION_ON_ACTIVATE_ERROR, TRANSITION_ON_ACTIVATE_FAILURE,
+        TRANSITION_ON_ACTIVATE_SUCCESS, TRANSITION_ON_CLEANUP_ERROR, TRANSITION_ON_CLEANUP_FAILURE,
+        TRANSITION_ON_CLEANUP_SUCCESS, TRANSITION_ON_CONFIGURE_ERROR,
+        TRANSITION_ON_CONFIGURE_FAILURE, TRANSITION_ON_CONFIGURE_SUCCESS,
+        TRANSITION_ON_DEACTIVATE_ERROR, TRANSITION_ON_DEACTIVATE_FAILURE,
+        TRANSITION_ON_DEACTIVATE_SUCCESS, TRANSITION_ON_ERROR_ERROR, TRANSITION_ON_ERROR_FAILURE,
+        TRANSITION_ON_ERROR_SUCCESS, TRANSITION_ON_SHUTDOWN_ERROR, TRANSITION_ON_SHUTDOWN_FAILURE,
+        TRANSITION_ON_SHUTDOWN_SUCCESS,
+    };
+
+    let (id, goal) = match (transition_state, ret) {
+        (State::Configuring, CallbackReturn::Success) => {
+            (TRANSITION_ON_CONFIGURE_SUCCESS, State::Inactive)
+        }
+        (State::Configuring, CallbackReturn::Failure) => {
+            (TRANSITION_ON_CONFIGURE_FAILURE, State::Unconfigured)
+        }
+        (State::Configuring, CallbackReturn::Error) => {
+            (TRANSITION_ON_CONFIGURE_ERROR, State::ErrorProcessing)
+        }
+        (State::CleaningUp, CallbackReturn::Success) => {
+            (TRANSITION_ON_CLEANUP_SUCCESS, State::Unconfigured)
+        }
+        (State::CleaningUp, CallbackReturn::Failure) => {
+            (TRANSITION_ON_CLEANUP_FAILURE, State::Inactive)
+        }
+        (State::CleaningUp, CallbackReturn::Error) => {
+            (TRANSITION_ON_CLEANUP_ERROR, State::ErrorProcessing)
+        }
+        (State::Activating, CallbackReturn::Success) => {
+            (TRANSITION_ON_ACTIVATE_SUCCESS, State::Active)
+        }
+        (State::Activating, CallbackReturn::Failure) => {
+            (TRANSITION_ON_ACTIVATE_FAILURE, State::Inactive)
+        }
+        (State::Activating, CallbackReturn::Error) => {
+            (TRANSITION_ON_ACTIVATE_ERROR, State::ErrorProcessing)
+        }
+        (State::Deactivating, CallbackReturn::Success) => {
+            (TRANSITION_ON_DEACTIVATE_SUCCESS, State::Inactive)
+        }
+        (State::Deactivating, CallbackReturn::Failure) => {
+            (TRANSITION_ON_DEACTIVATE_FAILURE, State::Active)
+        }
+        (State::Deactivating, CallbackReturn::Error) => {
+            (TRANSITION_ON_DEACTIVATE_ERROR, State::ErrorProcessing)
+        }
+        (State::ShuttingDown, CallbackReturn::Success) => {
+            (TRANSITION_ON_SHUTDOWN_SUCCESS, State::Finalized)
+        }
+        (State::ShuttingDown, CallbackReturn::Failure) => {
+            (TRANSITION_ON_SHUTDOWN_FAILURE, State::Finalized)
+        }
+        (State::ShuttingDown, CallbackReturn::Error) => {
+            (TRANSITION_ON_SHUTDOWN_ERROR, State::ErrorProcessing)
+        }
+        (State::ErrorProcessing, CallbackReturn::Success) => {
+            (TRANSITION_ON_ERROR_SUCCESS, State::Unconfigured)
+        }
+        (State::ErrorProcessing, CallbackReturn::Failure) => {
+            (TRANSITION_ON_ERROR_FAILURE, State::Finalized)
+        }
+        (State::ErrorProcessing, CallbackReturn::Error) => {
+            (TRANSITION_ON_ERROR_ERROR, State::Finalized)
+        }
+        _ => unreachable!(),
+    };
+
+    let label = match ret {
+        CallbackReturn::Success => "transition_success",
+        CallbackReturn::Failure => "transition_failure",
+        CallbackReturn::Error => "transition_error",
+    };
+
+    (id, label, goal)
+}
+
+/// State machine shared by a lifecycle node and its service thread.
+struct StateMachine {
+    /// Transition states are not the start states of any transitions,
+    /// so transitions are serialized by checking and setting the state at once.
+    state: Mutex<State>,
+
+    /// Not locked while a hook is running, so hooks can replace hooks.
+    callbacks: Mutex<Callbacks>,
+
+    /// `true` if the state is active, which is shared with lifecycle publishers.
+    is_active: Arc<AtomicBool>,
+
+    publisher: Publisher<TransitionEvent>,
+}
+
+impl StateMachine {
+    fn trigger(&self, transition: Transition) -> Result<State, DynError> {
+        let transition_state = transition.goal_state();
+
+        let start = {
+            let mut state = self.state.lock();
+            let start = *state;
+            if transition.start_state() != start {
+                let msg =
+                    format!("invalid transition: {transition} cannot be triggered in {start}");
+                return Err(msg.into());
+            }
+
+            // Other transitions fail until this transition finishes.
+            *state = transition_state;
+            start
+        };
+        self.set_state(start, transition_state, transition.id(), transition.label());
+
+        let ret = self.call(transition_state, start);
+        let (id, label, goal) = result_transition(transition_state, ret);
+        self.set_state(transition_state, goal, id, label);
+
+        if goal != State::ErrorProcessing {
+            return Ok(goal);
+        }
+
+        let ret = self.call(State::ErrorProcessing, start);
+        let (id, label, goal) = result_transition(State::ErrorProcessing, ret);
+        self.set_state(State::ErrorProcessing, goal, id, label);
+
+        Ok(goal)
+    }
+
+    /// Invoke the hook of a transition state.
+    /// If no hook is registered, the transition succeeds.
+    ///
+    /// The hook is taken out while it is running,
+    /// and it is put back unless another hook is set in the meantime.
+    fn call(&self, transition_state: State, previous: State) -> CallbackReturn {
+        let Some(mut callback) = self.callbacks.lock().get_mut(transition_state).take() else {
+            return CallbackReturn::Success;
+        };
+
+        let ret = callback(previous);
+
+        let mut callbacks = self.callbacks.lock();
+        let slot = callbacks.get_mut(transition_state);
+        if slot.is_none() {
+            *slot = Some(callback);
+        }
+
+        ret
+    }
+
+    fn set_state(&self, start: State, goal: State, id: u8, label: &str) {
+        *self.state.lock() = goal;
+        self.is_active
+            .store(goal == State::Active, Ordering::Release);
+
+        let mut event = TransitionEvent::new().unwrap();
+        event.timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0);
+        event.transition.id = id;
+        event.transition.label.assign(label);
+        event.start_state = start.to_msg();
+        event.goal_state = goal.to_msg();
+
+        if let Err(e) = self.publisher.send(&event) {
+            let logger = Logger::new("safe_drive");
+            pr_error_in!(logger, "failed to publish a transition event: {e}");
+        }
+    }
+
+    fn get_available_transitions(&self) -> Vec<Transition> {
+        let state = *self.state.lock();
+        Transition::ALL
+            .into_iter()
+            .filter(|t| t.start_state() == state)
+            .collect()
+    }
+}
+
+/// Lifecycle node.
+/// Use `Context::create_lifecycle_node` to create a lifecycle node.
+///
+/// User hooks are invoked in the thread of the services or in the thread calling
+/// `trigger_transition`.
+/// Hooks can set hooks, but transitions triggered while a transition is in progress fail.
+///
+/// # Example
+///
+/// ```
+/// use safe_drive::{
+///     context::Context,
+///     lifecycle::{CallbackReturn, State},
+/// };
+///
+/// let ctx = Context::new().unwrap();
+/// let node = ctx
+///     .create_lifecycle_node("lifecycle_node_rs", None, Default::default())
+///     .unwrap();
+///
+/// node.set_on_activate(Box::new(|_previous| CallbackReturn::Failure));
+///
+/// assert_eq!(node.configure().unwrap(), State::Inactive);
+///
+/// // on_activate fails, so the node goes back to inactive.
+/// assert_eq!(node.activate().unwrap(), State::Inactive);
+/// ```
+pub struct LifecycleNode {
+    node: Arc<Node>,
+    machine: Arc<StateMachine>,
+    handler: Option<JoinHandle<Result<(), DynError>>>,
+    cond_halt: GuardCondition,
+}
+
+impl LifecycleNode {
+    pub(crate) fn new(
+        context: &Arc<Context>,
+        name: &str,
+        namespace: Option<&str>,
+        options: NodeOptions,
+    ) -> Result<Self, DynError> {
+        let node = context.create_node(name, namespace, options)?;
+
+        // the name may be remapped, so it is taken from the node as the services do
+        let publisher = node.create_publisher::<TransitionEvent>(
+            &format!("{}/transition_event", node.get_name()?),
+            Some(Profile::default()),
+        )?;
+
+        let machine = Arc::new(StateMachine {
+            state: Mutex::new(State::Unconfigured),
+            callbacks: Default::default(),
+            is_active: Arc::new(AtomicBool::new(false)),
+            publisher,
+        });
+
+        let cond_halt = GuardCondition::new(context.clone())?;
+
+        let n = node.clone();
+        let m = machine.clone();
+        let cond_halt_cloned = cond_halt.clone();
+        let handler = std::thread::spawn(move || lifecycle_server(n, m, cond_halt_cloned));
+
+        Ok(LifecycleNode {
+            node,
+            machine,
+            handler: Some(handler),
+            cond_halt,
+        })
+    }
+
+    /// Get the underlying node.
+    pub fn get_node(&self) -> &Arc<Node> {
+        &self.node
+    }
+
+    /// Get the current state.
+    pub fn get_current_state(&self) -> State {
+        *self.machine.state.lock()
+    }
+
+    /// Get all states of the state machine.
+    pub fn get_available_states(&self) -> Vec<State> {
+        State::ALL.to_vec()
+    }
+
+    /// Get transitions which can be triggered in the current state.
+    pub fn get_available_transitions(&self) -> Vec<Transition> {
+        self.machine.get_available_transitions()
+    }
+
+    /// Trigger a transition, and return the new primary state.
+    ///
+    /// # Errors
+    ///
+    /// Return an error if `transition` cannot be triggered in the current state.
+    pub fn trigger_transition(&self, transition: Transition) -> Result<State, DynError> {
+        self.machine.trigger(transition)
+    }
+
+    /// Trigger `Transition::Configure`.
+    pub fn configure(&self) -> Result<State, DynError> {
+        self.trigger_transition(Transition::Configure)
+    }
+
+    /// Trigger `Transition::Cleanup`.
+    pub fn cleanup(&self) -> Result<State, DynError> {
+        self.trigger_transition(Transition::Cleanup)
+    }
+
+    /// Trigger `Transition::Activate`.
+    pub fn activate(&self) -> Result<State, DynError> {
+        self.trigger_transition(Transition::Activate)
+    }
+
+    /// Trigger `Transition::Deactivate`.
+    pub fn deactivate(&self) -> Result<State, DynError> {
+        self.trigger_transition(Transition::Deactivate)
+    }
+
+    /// Trigger the shutdown transition of the current state.
+    pub fn shutdown(&self) -> Result<State, DynError> {
+        let transition = match self.get_current_state() {
+            State::Unconfigured => Transition::UnconfiguredShutdown,
+            State::Inactive => Transition::InactiveShutdown,
+            State::Active => Transition::ActiveShutdown,
+            state => {
+                return Err(
+                    format!("invalid transition: shutdown cannot be triggered in {state}").into(),
+                )
+            }
+        };
+        self.trigger_transition(transition)
+    }
+
+    /// Set the hook invoked in the configuring state.
+    pub fn set_on_configure(&self, callback: LifecycleCallback) {
+        self.machine.callbacks.lock().on_configure = Some(callback);
+    }
+
+    /// Set the hook invoked in the cleaning up state.
+    pub fn set_on_cleanup(&self, callback: LifecycleCallback) {
+        self.machine.callbacks.lock().on_cleanup = Some(callback);
+    }
+
+    /// Set the hook invoked in the activating state.
+    pub fn set_on_activate(&self, callback: LifecycleCallback) {
+        self.machine.callbacks.lock().on_activate = Some(callback);
+    }
+
+    /// Set the hook invoked in the deactivating state.
+    pub fn set_on_deactivate(&self, callback: LifecycleCallback) {
+        self.machine.callbacks.lock().on_deactivate = Some(callback);
+    }
+
+    /// Set the hook invoked in the shutting down state.
+    pub fn set_on_shutdown(&self, callback: LifecycleCallback) {
+        self.machine.callbacks.lock().on_shutdown = Some(callback);
+    }
+
+    /// Set the hook invoked in the error processing state.
+    /// If this returns `CallbackReturn::Success`, the node goes to the unconfigured state,
+    /// otherwise the finalized state.
+    pub fn set_on_error(&self, callback: LifecycleCallback) {
+        self.machine.callbacks.lock().on_error = Some(callback);
+    }
+
+    /// Create a lifecycle publisher, which sends messages only when the node is active.
+    /// If `qos` is specified `None`, the default profile is used.
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::AlreadyInit` if the publisher is already initialized, or
+    /// - `RCLError::NodeInvalid` if the node is invalid, or
+    /// - `RCLError::InvalidArgument` if any arguments are invalid, or
+    /// - `RCLError::BadAlloc` if allocating memory fails, or
+    /// - `RCLError::TopicNameInvalid` if the given topic name is invalid, or
+    /// - `RCLError::Error` if an unspecified error occurs.
+    pub fn create_publisher<T: TypeSupport>(
+        &self,
+        topic_name: &str,
+        qos: Option<Profile>,
+    ) -> RCLResult<LifecyclePublisher<T>> {
+        let publisher = self.node.create_publisher(topic_name, qos)?;
+        Ok(LifecyclePublisher {
+            publisher,
+            is_active: self.machine.is_active.clone(),
+            should_warn: AtomicBool::new(true),
+        })
+    }
+}
+
+impl Drop for LifecycleNode {
+    fn drop(&mut self) {
+        if self.cond_halt.trigger().is_ok() {
+            if let Some(handler) = self.handler.take() {
+                let _ = handler.join();
+            }
+        }
+    }
+}
+
+/// Publisher of a lifecycle node.
+/// Messages are dropped while the node is not active.
+pub struct LifecyclePublisher<T> {
+    publisher: Publisher<T>,
+    is_active: Arc<AtomicBool>,
+
+    /// A warning is printed once until the node is activated.
+    should_warn: AtomicBool,
+}
+
+impl<T: TypeSupport> LifecyclePublisher<T> {
+    /// Return `true` if the node is active.
+    pub fn is_activated(&self) -> bool {
+        self.is_active.load(Ordering::Acquire)
+    }
+
+    pub fn get_topic_name(&self) -> &str {
+        self.publisher.get_topic_name()
+    }
+
+    /// Send a message if the node is active, otherwise the message is dropped.
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::InvalidArgument` if any arguments are invalid, or
+    /// - `RCLError::PublisherInvalid` if the publisher is invalid, or
+    /// - `RCLError::Error` if an unspecified error occurs.
+    pub fn send(&self, msg: &T) -> Result<(), DynError> {
+        if self.is_activated() {
+            self.should_warn.store(true, Ordering::Relaxed);
+            self.publisher.send(msg)
+        } else {
+            if self.should_warn.swap(false, Ordering::Relaxed) {
+                let logger = Logger::new("safe_drive");
+                pr_warn_in!(
+                    logger,
+                    "the message to {} is dropped because the lifecycle node is not active",
+                    self.get_topic_name()
+                );
+            }
+            Ok(())
+        }
+    }
+}
+
+fn lifecycle_server(
+    node: Arc<Node>,
+    machine: Arc<StateMachine>,
+    cond_halt: GuardCondition,
+) -> Result<(), DynError> {
+    if let Ok(mut selector) = node.context.create_selector() {
+        add_srv_change_state(&node, &mut selector, machine.clone())?;
+        add_srv_get_state(&node, &mut selector, machine.clone())?;
+        add_srv_get_available_states(&node, &mut selector)?;
+        add_srv_get_available_transitions(&node, &mut selector, machine)?;
+
+        let is_halt = Rc::new(Cell::new(false));
+        let is_halt_cloned = is_halt.clone();
+
+        selector.add_guard_condition(
+            &cond_halt,
+            Some(Box::new(move || {
+                is_halt_cloned.set(true);
+                CallbackResult::Remove
+            })),
+            false,
+        );
+
+        while !is_halt.get() {
+            selector.wait()?;
+        }
+    } else {
+        let logger = Logger::new("safe_drive");
+        pr_error_in!(logger, "failed to start services of a lifecycle node");
+    }
+
+    Ok(())
+}
+
+fn add_srv_change_state(
+    node: &Arc<Node>,
+    selector: &mut Selector,
+    machine: Arc<StateMachine>,
+) -> RCLResult<()> {
+    let name = node.get_name()?;
+    let srv = node
+        .create_server::<ChangeState>(&format!("{name}/change_state"), Some(Profile::default()))?;
+
+    selector.add_server(
+        srv,
+        Box::new(move |req, _| {
+            let mut response = ChangeStateResponse::new().unwrap();
+
+            let state = *machine.state.lock();
+            let label = req.transition.label.get_string();
+            if let Some(transition) = Transition::find(state, req.transition.id, &label) {
+                // a failed hook also results in a primary state
+                response.success = matches!(
+                    machine.trigger(transition),
+                    Ok(goal) if goal == transition.success_state()
+                );
+            } else {
+                let logger = Logger::new("safe_drive");
+                pr_warn_in!(
+                    logger,
+                    "invalid transition: id = {}, label = {label}, state = {state}",
+                    req.transition.id
+                );
+            }
+
+            response
+        }),
+    );
+
+    Ok(())
+}
+
+fn add_srv_get_state(
+    node: &Arc<Node>,
+    selector: &mut Selector,
+    machine: Arc<StateMachine>,
+) -> RCLResult<()> {
+    let name = node.get_name()?;
+    let srv =
+        node.create_server::<GetState>(&format!("{name}/get_state"), Some(Profile::default()))?;
+
+    selector.add_server(
+        srv,
+        Box::new(move |_, _| {
+            let mut response = GetStateResponse::new().unwrap();
+            response.current_state = machine.state.lock().to_msg();
+            response
+        }),
+    );
+
+    Ok(())
+}
+
+fn add_srv_get_available_states(node: &Arc<Node>, selector: &mut Selector) -> RCLResult<()> {
+    let name = node.get_name()?;
+    let srv = node.create_server::<GetAvailableStates>(
+        &format!("{name}/get_available_states"),
+        Some(Profile::default()),
+    )?;
+
+    selector.add_server(
+        srv,
+        Box::new(move |_, _| {
+            let mut response = GetAvailableStatesResponse::new().unwrap();
+            if let Some(mut seq) = StateSeq::new(State::ALL.len()) {
+                seq.iter_mut()
+                    .zip(State::ALL.iter())
+                    .for_each(|(dst, src)| *dst = src.to_msg());
+                response.available_states = seq;
+            }
+            response
+        }),
+    );
+
+    Ok(())
+}
+
+fn add_srv_get_available_transitions(
+    node: &Arc<Node>,
+    selector: &mut Selector,
+    machine: Arc<StateMachine>,
+) -> RCLResult<()> {
+    let name = node.get_name()?;
+    let srv = node.create_server::<GetAvailableTransitions>(
+        &format!("{name}/get_available_transitions"),
+        Some(Profile::default()),
+    )?;
+
+    selector.add_server(
+        srv,
+        Box::new(move |_, _| {
+            let mut response = GetAvailableTransitionsResponse::new().unwrap();
+            let transitions = machine.get_available_transitions();
+            if let Some(mut seq) = TransitionDescriptionSeq::new(transitions.len()) {
+                seq.iter_mut()
+                    .zip(transitions.iter())
+                    .for_each(|(dst, src)| {
+                        dst.transition.id = src.id();
+                        dst.transition.label.assign(src.label());
+                        dst.start_state = src.start_state().to_msg();
+                        dst.goal_state = src.goal_state().to_msg();
+                    });
+                response.available_transitions = seq;
+            }
+            response
+        }),
+    );
+
+    Ok(())
+}
@@ -112,6 +112,14 @@ macro_rules! pr_warn {
     }}
 }
 
+macro_rules! pr_warn_in {
+    ($logger:expr, $($arg:tt)*) => {{
+        let res = std::format!($($arg)*);
+        let _ = $logger.write_warn(&res, crate::function!(), std::file!(), std::line!() as u64);
+    }}
+}
+pub(crate) use pr_warn_in;
+
 /// Print error.
 #[macro_export]
 macro_rules! pr_error {
@@ -486,6 +486,7 @@ pub mod builtin_interfaces {
 
     /// ROS2 provides the `Time` structure to represent a time,
     /// but **DO NOT USE THIS** because of the **year-2038 problem**.
+    /// Use `safe_drive::time::Time` instead, and convert it when needed.
     pub type UnsafeTime = builtin_interfaces__msg__Time;
 
     impl TypeSupport for UnsafeTime {
@@ -0,0 +1,232 @@
+//! Expansion and validation of names of nodes, namespaces, topics and services.
+//!
+//! ROS2 names must follow the rules described in
+//! [Topic and Service name mapping to DDS](https://design.ros2.org/articles/topic_and_service_names.html).
+//! Functions of this module report the reason and the index of the invalid character
+//! when a name violates the rules.
+//!
+//! # Example
+//!
+//! ```
+//! use safe_drive::names;
+//!
+//! // `~` is expanded to the fully qualified name of the node.
+//! let name = names::expand_topic_name("~/topic", "node", "/ns", false).unwrap();
+//! assert_eq!(name, "/ns/node/topic");
+//!
+//! // A topic name must not contain spaces.
+//! let err = names::expand_topic_name("my topic", "node", "/ns", false).unwrap_err();
+//! assert_eq!(err.invalid_index, Some(2));
+//!
+//! assert!(names::validate_node_name("my_node").is_ok());
+//! assert!(names::validate_namespace("/my_ns").is_ok());
+//! assert!(names::validate_full_topic_name("relative/topic").is_err());
+//! ```
+
+use crate::{
+    error::{RCLError, RCLResult},
+    logger::{pr_error_in, Logger},
+    rcl,
+};
+use std::{
+    error::Error,
+    ffi::{CStr, CString},
+    fmt::Display,
+};
+
+/// Kind of a name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NameKind {
+    NodeName,
+    Namespace,
+    TopicName,
+    ServiceName,
+}
+
+impl NameKind {
+    fn label(&self) -> &'static str {
+        match self {
+            Self::NodeName => "node name",
+            Self::Namespace => "namespace",
+            Self::TopicName => "topic name",
+            Self::ServiceName => "service name",
+        }
+    }
+
+    fn topic_or_service(is_service: bool) -> Self {
+        if is_service {
+            Self::ServiceName
+        } else {
+            Self::TopicName
+        }
+    }
+}
+
+/// Error of an invalid name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NameError {
+    /// Kind of the invalid name.
+    pub kind: NameKind,
+
+    /// The invalid name.
+    pub name: String,
+
+    /// Why the name is invalid.
+    pub reason: String,
+
+    /// Index of the invalid character of `name`, if it is identified.
+    pub invalid_index: Option<usize>,
+}
+
+impl NameError {
+    fn new(kind: NameKind, name: &str, reason: String, invalid_index: Option<usize>) -> Self {
+        NameError {
+            kind,
+            name: name.to_string(),
+            reason,
+            invalid_index,
+        }
+    }
+}
+
+impl Display for NameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid {} \"{}\": {}",
+            self.kind.label(),
+            self.name,
+            self.reason
+        )?;
+        if let Some(index) = self.invalid_index {
+            write!(f, " (at index {index})")?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for NameError {}
+
+impl NameError {
+    /// Log the reason and the index, and convert this to the error code of `rcl`.
+    /// This is used by functions returning `RCLResult`, whose errors lose them.
+    pub(crate) fn log(self) -> RCLError {
+        let logger = Logger::new("safe_drive");
+        pr_error_in!(logger, "{self}");
+        self.into()
+    }
+}
+
+/// `NameError` is converted to the error code of `rcl` for its kind,
+/// which is returned by functions such as `Node::create_publisher`.
+/// These functions log the reason and the index before converting.
+impl From<NameError> for RCLError {
+    fn from(e: NameError) -> Self {
+        match e.kind {
+            NameKind::NodeName => RCLError::NodeInvalidName,
+            NameKind::Namespace => RCLError::NodeInvalidNamespace,
+            NameKind::TopicName => RCLError::TopicNameInvalid,
+            NameKind::ServiceName => RCLError::ServiceNameInvalid,
+        }
+    }
+}
+
+/// Validate a fully qualified topic or service name such as `/ns/topic`.
+///
+/// # Errors
+///
+/// Returns `NameError` if the name is invalid.
+pub fn validate_full_topic_name(name: &str) -> Result<(), NameError> {
+    validate(
+        NameKind::TopicName,
+        name,
+        rcl::MTSafeFn::rmw_validate_full_topic_name,
+    )
+}
+
+/// Validate a node name such as `my_node`.
+///
+/// # Errors
+///
+/// Returns `NameError` if the name is invalid.
+pub fn validate_node_name(name: &str) -> Result<(), NameError> {
+    validate(
+        NameKind::NodeName,
+        name,
+        rcl::MTSafeFn::rmw_validate_node_name,
+    )
+}
+
+/// Validate an absolute namespace such as `/my_ns`.
+///
+/// # Errors
+///
+/// Returns `NameError` if the namespace is invalid.
+pub fn validate_namespace(namespace: &str) -> Result<(), NameError> {
+    validate(
+        NameKind::Namespace,
+        namespace,
+        rcl::MTSafeFn::rmw_validate_namespace,
+    )
+}
+
+/// Expand a topic or service name to the fully qualified name,
+/// and validate it.
+/// `~` and substitutions such as `{node}` and `{ns}` are expanded by
+/// `node_name` and `node_namespace`.
+///
+/// Remap rules are not applied.
+/// Use `Node::resolve_topic_name` to apply them.
+///
+/// # Errors
+///
+/// Returns `NameError` if the name is invalid before or after the expansion.
+pub fn expand_topic_name(
+    name: &str,
+    node_name: &str,
+    node_namespace: &str,
+    is_service: bool,
+) -> Result<String, NameError> {
+    let kind = NameKind::topic_or_service(is_service);
+
+    validate(kind, name, rcl::MTSafeFn::rcl_validate_topic_name)?;
+
+    let name_c = to_cstring(kind, name)?;
+    let node_name_c = to_cstring(NameKind::NodeName, node_name)?;
+    let node_namespace_c = to_cstring(NameKind::Namespace, node_namespace)?;
+
+    let expanded = rcl::MTSafeFn::rcl_expand_topic_name(&name_c, &node_name_c, &node_namespace_c)
+        .map_err(|e| NameError::new(kind, name, e.to_string(), None))?;
+
+    validate(kind, &expanded, rcl::MTSafeFn::rmw_validate_full_topic_name)?;
+
+    Ok(expanded)
+}
+
+/// Convert a name to `CString`.
+///
+/// # Errors
+///
+/// Returns `NameError` if the name contains a null character.
+pub(crate) fn to_cstring(kind: NameKind, name: &str) -> Result<CString, NameError> {
+    CString::new(name).map_err(|e| {
+        NameError::new(
+            kind,
+            name,
+            "must not contain a null character".to_string(),
+            Some(e.nul_position()),
+        )
+    })
+}
+
+/// Return the reason and the index of the invalid character if invalid.
+type ValidateFn = fn(&CStr) -> RCLResult<Option<(String, usize)>>;
+
+fn validate(kind: NameKind, name: &str, f: ValidateFn) -> Result<(), NameError> {
+    let name_c = to_cstring(kind, name)?;
+    match f(&name_c) {
+        Ok(None) => Ok(()),
+        Ok(Some((reason, index))) => Err(NameError::new(kind, name, reason, Some(index))),
+        Err(e) => Err(NameError::new(kind, name, e.to_string(), None)),
+    }
+}
@@ -18,17 +18,36 @@
 use libc::atexit;
 
 use crate::{
+    action::{self, client::ClientQosOption, server::ServerQosOption},
+    clock::Clock,
     context::{remove_context, Context},
-    error::{DynError, RCLResult},
+    error::{DynError, RCLError, RCLResult},
+    get_allocator,
+    graph::{self, ByNode, EndpointType, GraphWatcher, NamesAndTypes, TopicEndpointInfo},
     helper::InitOnce,
-    msg::{ServiceMsg, TypeSupport},
-    parameter::ParameterServer,
-    qos, rcl,
+    msg::{ActionMsg, ServiceMsg, TypeSupport},
+    names::{self, NameError, NameKind},
+    parameter::{
+        client::ParameterClient, event::ParameterEventHandler, ParameterServer,
+        ParameterServerOptions, Parameters, Value,
+    },
+    qos::{
+        self,
+        overriding::{apply_overrides, EntityKind, QosOverridingOptions},
+    },
+    rcl,
     service::{client::Client, server::Server},
+    time_source::TimeSource,
+    topic::intra_process::IntraProcessSubscriber,
     topic::publisher::Publisher,
     topic::subscriber::Subscriber,
 };
-use std::{ffi::CString, sync::Arc};
+use parking_lot::{Mutex, RwLock};
+use std::{
+    collections::BTreeMap,
+    ffi::CString,
+    sync::{Arc, Weak},
+};
 
 static SET_ATEXIT: InitOnce = InitOnce::new();
 
@@ -36,6 +55,12 @@ static SET_ATEXIT: InitOnce = InitOnce::new();
 pub struct Node {
     node: rcl::rcl_node_t,
     init_param_server: InitOnce,
+    init_time_source: InitOnce,
+    clock: Arc<Mutex<Clock>>,
+
+    /// Parameters of the parameter server of this node.
+    params: Mutex<Weak<RwLock<Parameters>>>,
+
     pub(crate) context: Arc<Context>,
 }
 
@@ -48,8 +73,18 @@ impl Node {
     ) -> RCLResult<Arc<Self>> {
         let mut node = rcl::MTSafeFn::rcl_get_zero_initialized_node();
 
-        let name_c = CString::new(name).unwrap();
-        let namespace_c = CString::new(namespace.unwrap_or_default()).unwrap();
+        names::validate_node_name(name).map_err(NameError::log)?;
+        let namespace = namespace.unwrap_or_default();
+        match namespace {
+            "" => (),
+            ns if ns.starts_with('/') => names::validate_namespace(ns).map_err(NameError::log)?,
+            ns => names::validate_namespace(&format!("/{ns}")).map_err(NameError::log)?,
+        }
+
+        let options = options.to_rcl()?;
+        let name_c = names::to_cstring(NameKind::NodeName, name).map_err(NameError::log)?;
+        let namespace_c =
+            names::to_cstring(NameKind::Namespace, namespace).map_err(NameError::log)?;
 
         {
             let guard = rcl::MT_UNSAFE_FN.lock();
@@ -70,6 +105,9 @@ impl Node {
         Ok(Arc::new(Node {
             node,
             init_param_server: InitOnce::new(),
+            init_time_source: InitOnce::new(),
+            clock: Arc::new(Mutex::new(Clock::new()?)),
+            params: Mutex::new(Weak::new()),
             context,
         }))
     }
@@ -94,13 +132,141 @@ impl Node {
         rcl::MTSafeFn::rcl_node_get_namespace(&self.node)
     }
 
+    /// Resolve a topic or service name to the fully qualified name.
+    /// `~` and substitutions are expanded, and then remap rules of
+    /// the arguments of the context and `NodeOptions` are applied.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::context::Context;
+    ///
+    /// let ctx = Context::new().unwrap();
+    /// let node = ctx
+    ///     .create_node("resolve_topic_name_rs", Some("ns"), Default::default())
+    ///     .unwrap();
+    ///
+    /// let name = node.resolve_topic_name("~/topic", false).unwrap();
+    /// assert_eq!(name, "/ns/resolve_topic_name_rs/topic");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `NameError` with the reason and the index of the invalid character
+    /// if the name is invalid.
+    pub fn resolve_topic_name(&self, name: &str, is_service: bool) -> Result<String, NameError> {
+        self.expand_topic_name(name, is_service)?;
+
+        let resolved = {
+            let guard = rcl::MT_UNSAFE_FN.lock();
+            guard
+                .rcl_node_resolve_name(&self.node, name, is_service, false)
+                .map_err(|e| to_name_error(name, is_service, e))?
+        };
+        names::validate_full_topic_name(&resolved)?;
+
+        Ok(resolved)
+    }
+
+    /// Expand and validate a topic or service name without applying remap rules,
+    /// which are applied by `rcl` when creating publishers, subscribers and so on.
+    pub(crate) fn expand_topic_name(
+        &self,
+        name: &str,
+        is_service: bool,
+    ) -> Result<String, NameError> {
+        let node_name = self
+            .get_name()
+            .map_err(|e| to_name_error(name, is_service, e))?;
+        let node_namespace = self
+            .get_namespace()
+            .map_err(|e| to_name_error(name, is_service, e))?;
+        names::expand_topic_name(name, &node_name, &node_namespace, is_service)
+    }
+
+    /// Get parameter overrides of this node specified by
+    /// the global arguments of the context and the arguments of `NodeOptions`.
+    /// The latter take precedence.
+    pub(crate) fn parameter_overrides(&self) -> RCLResult<BTreeMap<String, Value>> {
+        let fqn = self.get_fully_qualified_name()?;
+        let options = unsafe { &*rcl::MTSafeFn::rcl_node_get_options(&self.node)? };
+
+        let mut guard = rcl::MT_UNSAFE_FN.lock();
+
+        let mut params = if options.use_global_arguments {
+            let arguments = unsafe { &(*self.context.as_ptr()).global_arguments };
+            guard.parameter_map(&fqn, arguments)?
+        } else {
+            BTreeMap::new()
+        };
+
+        if !options.arguments.impl_.is_null() {
+            params.extend(guard.parameter_map(&fqn, &options.arguments)?);
+        }
+
+        Ok(params)
+    }
+
+    /// Get the parameters of the parameter server of this node if it exists.
+    pub(crate) fn get_parameters(&self) -> Option<Arc<RwLock<Parameters>>> {
+        self.params.lock().upgrade()
+    }
+
+    pub(crate) fn set_parameters(&self, params: &Arc<RwLock<Parameters>>) {
+        *self.params.lock() = Arc::downgrade(params);
+    }
+
     pub fn create_parameter_server(self: &Arc<Self>) -> Result<ParameterServer, DynError> {
+        self.create_parameter_server_with_options(Default::default())
+    }
+
+    /// Create a parameter server with options.
+    /// See `ParameterServerOptions`.
+    pub fn create_parameter_server_with_options(
+        self: &Arc<Self>,
+        options: ParameterServerOptions,
+    ) -> Result<ParameterServer, DynError> {
         self.init_param_server.init(
-            || ParameterServer::new(self.clone()),
+            || ParameterServer::new(self.clone(), options.clone()),
             Err("a parameter server has been already created".into()),
         )
     }
 
+    /// Get the clock of this node, whose ROS time is overridden by `TimeSource`
+    /// if `use_sim_time` is true.
+    pub fn get_clock(&self) -> Arc<Mutex<Clock>> {
+        self.clock.clone()
+    }
+
+    /// Create a time source driving the clock of this node.
+    /// Only one time source can be created for each node.
+    /// See `safe_drive::time_source`.
+    pub fn create_time_source(self: &Arc<Self>) -> Result<TimeSource, DynError> {
+        self.init_time_source.init(
+            || TimeSource::new(self.clone()),
+            Err("a time source has been already created".into()),
+        )
+    }
+
+    /// Create a client of parameters of a remote node.
+    /// `remote_node_name` is the name of the remote node such as `/ns/node`,
+    /// and relative names are expanded by the namespace of this node.
+    /// See `safe_drive::parameter::client`.
+    pub fn create_parameter_client(
+        self: &Arc<Self>,
+        remote_node_name: &str,
+    ) -> Result<ParameterClient, DynError> {
+        ParameterClient::new(self.clone(), remote_node_name)
+    }
+
+    /// Create a handler of `/parameter_events`.
+    /// See `safe_drive::parameter::event`.
+    pub fn create_parameter_event_handler(
+        self: &Arc<Self>,
+    ) -> Result<ParameterEventHandler, DynError> {
+        ParameterEventHandler::new(self.clone())
+    }
+
     /// Create a publisher.
     /// If `qos` is specified `None`,
     /// the default profile is used.
@@ -122,6 +288,8 @@ impl Node {
         topic_name: &str,
         qos: Option<qos::Profile>,
     ) -> RCLResult<Publisher<T>> {
+        self.expand_topic_name(topic_name, false)
+            .map_err(NameError::log)?;
         Publisher::new(self.clone(), topic_name, qos)
     }
 
@@ -148,9 +316,91 @@ impl Node {
         topic_name: &str,
         qos: Option<qos::Profile>,
     ) -> RCLResult<Publisher<T>> {
+        self.expand_topic_name(topic_name, false)
+            .map_err(NameError::log)?;
         Publisher::new_disable_loaned_message(self.clone(), topic_name, qos)
     }
 
+    /// Create a publisher for intra-process communication.
+    /// If `qos` is specified `None`,
+    /// the default profile is used.
+    ///
+    /// `T` is the type of messages the created publisher send.
+    ///
+    /// Messages sent by `Publisher::send_owned` or `Publisher::send_shared` are
+    /// delivered to subscribers created by `create_subscriber_intra_process`
+    /// in the same context without serialization.
+    /// See `safe_drive::topic::intra_process`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::{msg::common_interfaces::std_msgs, node::Node, topic::publisher::Publisher};
+    /// use std::sync::Arc;
+    ///
+    /// fn create_publisher_intra_process(node: Arc<Node>) -> Publisher<std_msgs::msg::Bool> {
+    ///     node.create_publisher_intra_process("topic_name", None).unwrap()
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::InvalidArgument` if the durability of `qos` is `TransientLocal`.
+    pub fn create_publisher_intra_process<T: TypeSupport + 'static>(
+        self: &Arc<Self>,
+        topic_name: &str,
+        qos: Option<qos::Profile>,
+    ) -> RCLResult<Publisher<T>> {
+        self.expand_topic_name(topic_name, false)
+            .map_err(NameError::log)?;
+        Publisher::new_intra_process(self.clone(), topic_name, qos)
+    }
+
+    /// Create a publisher whose QoS profile can be overridden by parameters.
+    /// If `qos` is specified `None`,
+    /// the default profile is used before overriding.
+    ///
+    /// `T` is the type of messages the created publisher send.
+    ///
+    /// Policies specified by `options` are overridden by parameters of
+    /// `qos_overrides.<topic>.publisher.<policy>`.
+    /// See `safe_drive::qos::overriding`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::{
+    ///     msg::common_interfaces::std_msgs, node::Node, qos::overriding::QosOverridingOptions,
+    ///     topic::publisher::Publisher,
+    /// };
+    /// use std::sync::Arc;
+    ///
+    /// fn create_publisher_with_qos_overriding(node: Arc<Node>) -> Publisher<std_msgs::msg::Bool> {
+    ///     node.create_publisher_with_qos_overriding("topic_name", None, QosOverridingOptions::default())
+    ///         .unwrap()
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a parameter has an invalid type or value.
+    pub fn create_publisher_with_qos_overriding<T: TypeSupport>(
+        self: &Arc<Self>,
+        topic_name: &str,
+        qos: Option<qos::Profile>,
+        options: QosOverridingOptions,
+    ) -> Result<Publisher<T>, DynError> {
+        let resolved = self.resolve_topic_name(topic_name, false)?;
+        let qos = apply_overrides(
+            self,
+            &resolved,
+            EntityKind::Publisher,
+            qos.unwrap_or_default(),
+            &options,
+        )?;
+        Ok(Publisher::new(self.clone(), topic_name, Some(qos))?)
+    }
+
     /// Create a subscriber.
     /// If `qos` is specified `None`,
     /// the default profile is used.
@@ -172,6 +422,8 @@ impl Node {
         topic_name: &str,
         qos: Option<qos::Profile>,
     ) -> RCLResult<Subscriber<T>> {
+        self.expand_topic_name(topic_name, false)
+            .map_err(NameError::log)?;
         Subscriber::new(self.clone(), topic_name, qos)
     }
 
@@ -196,9 +448,96 @@ impl Node {
         topic_name: &str,
         qos: Option<qos::Profile>,
     ) -> RCLResult<Subscriber<T>> {
+        self.expand_topic_name(topic_name, false)
+            .map_err(NameError::log)?;
         Subscriber::new_disable_loaned_message(self.clone(), topic_name, qos)
     }
 
+    /// Create a subscriber for intra-process communication.
+    /// If `qos` is specified `None`,
+    /// the default profile is used.
+    ///
+    /// `T` is the type of messages the created subscriber receive.
+    ///
+    /// The created subscriber receives messages from publishers created by
+    /// `create_publisher_intra_process` in the same context without serialization,
+    /// and it ignores messages from the other publishers in the same context.
+    /// See `safe_drive::topic::intra_process`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::{
+    ///     msg::common_interfaces::std_msgs, node::Node,
+    ///     topic::intra_process::IntraProcessSubscriber,
+    /// };
+    /// use std::sync::Arc;
+    ///
+    /// fn create_subscriber_intra_process(
+    ///     node: Arc<Node>,
+    /// ) -> IntraProcessSubscriber<std_msgs::msg::Bool> {
+    ///     node.create_subscriber_intra_process("topic_name", None).unwrap()
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::InvalidArgument` if the durability of `qos` is `TransientLocal`.
+    pub fn create_subscriber_intra_process<T: TypeSupport + Send + Sync + 'static>(
+        self: &Arc<Self>,
+        topic_name: &str,
+        qos: Option<qos::Profile>,
+    ) -> RCLResult<IntraProcessSubscriber<T>> {
+        self.expand_topic_name(topic_name, false)
+            .map_err(NameError::log)?;
+        IntraProcessSubscriber::new(self.clone(), topic_name, qos)
+    }
+
+    /// Create a subscriber whose QoS profile can be overridden by parameters.
+    /// If `qos` is specified `None`,
+    /// the default profile is used before overriding.
+    ///
+    /// `T` is the type of messages the created subscriber receive.
+    ///
+    /// Policies specified by `options` are overridden by parameters of
+    /// `qos_overrides.<topic>.subscription.<policy>`.
+    /// See `safe_drive::qos::overriding`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::{
+    ///     msg::common_interfaces::std_msgs, node::Node, qos::overriding::QosOverridingOptions,
+    ///     topic::subscriber::Subscriber,
+    /// };
+    /// use std::sync::Arc;
+    ///
+    /// fn create_subscriber_with_qos_overriding(node: Arc<Node>) -> Subscriber<std_msgs::msg::Bool> {
+    ///     node.create_subscriber_with_qos_overriding("topic_name", None, QosOverridingOptions::default())
+    ///         .unwrap()
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a parameter has an invalid type or value.
+    pub fn create_subscriber_with_qos_overriding<T: TypeSupport>(
+        self: &Arc<Self>,
+        topic_name: &str,
+        qos: Option<qos::Profile>,
+        options: QosOverridingOptions,
+    ) -> Result<Subscriber<T>, DynError> {
+        let resolved = self.resolve_topic_name(topic_name, false)?;
+        let qos = apply_overrides(
+            self,
+            &resolved,
+            EntityKind::Subscription,
+            qos.unwrap_or_default(),
+            &options,
+        )?;
+        Ok(Subscriber::new(self.clone(), topic_name, Some(qos))?)
+    }
+
     /// Create a server.
     /// If `qos` is specified `None`,
     /// the default profile is used.
@@ -220,6 +559,8 @@ impl Node {
         service_name: &str,
         qos: Option<qos::Profile>,
     ) -> RCLResult<Server<T>> {
+        self.expand_topic_name(service_name, true)
+            .map_err(NameError::log)?;
         Server::new(self.clone(), service_name, qos)
     }
 
@@ -244,8 +585,331 @@ impl Node {
         service_name: &str,
         qos: Option<qos::Profile>,
     ) -> RCLResult<Client<T>> {
+        self.expand_topic_name(service_name, true)
+            .map_err(NameError::log)?;
         Client::new(self.clone(), service_name, qos)
     }
+
+    /// Create a watcher of changes of the ROS graph.
+    /// See `safe_drive::graph::GraphWatcher`.
+    pub fn create_graph_watcher(self: &Arc<Self>) -> Result<GraphWatcher, DynError> {
+        GraphWatcher::new(self.clone())
+    }
+
+    /// Get names and namespaces of nodes in the ROS graph.
+    /// See `safe_drive::graph`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::node::Node;
+    /// use std::sync::Arc;
+    ///
+    /// fn node_exists(node: Arc<Node>, name: &str, namespace: &str) -> bool {
+    ///     node.get_node_names_and_namespaces()
+    ///         .unwrap()
+    ///         .iter()
+    ///         .any(|(n, ns)| n == name && ns == namespace)
+    /// }
+    /// ```
+    pub fn get_node_names_and_namespaces(&self) -> RCLResult<Vec<(String, String)>> {
+        graph::get_node_names_and_namespaces(self)
+    }
+
+    /// Get fully qualified names of nodes in the ROS graph.
+    pub fn get_node_names(&self) -> RCLResult<Vec<String>> {
+        let names = self.get_node_names_and_namespaces()?;
+        Ok(names
+            .into_iter()
+            .map(|(name, namespace)| {
+                if namespace.ends_with('/') {
+                    format!("{namespace}{name}")
+                } else {
+                    format!("{namespace}/{name}")
+                }
+            })
+            .collect())
+    }
+
+    /// Get names and types of topics in the ROS graph.
+    pub fn get_topic_names_and_types(&self) -> RCLResult<NamesAndTypes> {
+        graph::get_topic_names_and_types(self)
+    }
+
+    /// Get names and types of services in the ROS graph.
+    pub fn get_service_names_and_types(&self) -> RCLResult<NamesAndTypes> {
+        graph::get_service_names_and_types(self)
+    }
+
+    /// Get names and types of topics published by a node.
+    pub fn get_publisher_names_and_types_by_node(
+        &self,
+        node_name: &str,
+        node_namespace: &str,
+    ) -> RCLResult<NamesAndTypes> {
+        graph::get_names_and_types_by_node(self, ByNode::Publisher, node_name, node_namespace)
+    }
+
+    /// Get names and types of topics subscribed by a node.
+    pub fn get_subscriber_names_and_types_by_node(
+        &self,
+        node_name: &str,
+        node_namespace: &str,
+    ) -> RCLResult<NamesAndTypes> {
+        graph::get_names_and_types_by_node(self, ByNode::Subscriber, node_name, node_namespace)
+    }
+
+    /// Get names and types of services served by a node.
+    pub fn get_service_names_and_types_by_node(
+        &self,
+        node_name: &str,
+        node_namespace: &str,
+    ) -> RCLResult<NamesAndTypes> {
+        graph::get_names_and_types_by_node(self, ByNode::Service, node_name, node_namespace)
+    }
+
+    /// Get names and types of services used by clients of a node.
+    pub fn get_client_names_and_types_by_node(
+        &self,
+        node_name: &str,
+        node_namespace: &str,
+    ) -> RCLResult<NamesAndTypes> {
+        graph::get_names_and_types_by_node(self, ByNode::Client, node_name, node_namespace)
+    }
+
+    /// Get information of publishers of a topic, including QoS profiles and GIDs.
+    /// `topic_name` is expanded and remapped by the rules of this node.
+    pub fn get_publishers_info_by_topic(
+        &self,
+        topic_name: &str,
+    ) -> RCLResult<Vec<TopicEndpointInfo>> {
+        graph::get_endpoints_info_by_topic(self, EndpointType::Publisher, topic_name)
+    }
+
+    /// Get information of subscriptions of a topic, including QoS profiles and GIDs.
+    /// `topic_name` is expanded and remapped by the rules of this node.
+    pub fn get_subscriptions_info_by_topic(
+        &self,
+        topic_name: &str,
+    ) -> RCLResult<Vec<TopicEndpointInfo>> {
+        graph::get_endpoints_info_by_topic(self, EndpointType::Subscription, topic_name)
+    }
+
+    /// Count publishers of a topic.
+    /// `topic_name` is expanded and remapped by the rules of this node.
+    pub fn count_publishers(&self, topic_name: &str) -> RCLResult<usize> {
+        graph::count_endpoints(self, EndpointType::Publisher, topic_name)
+    }
+
+    /// Count subscribers of a topic.
+    /// `topic_name` is expanded and remapped by the rules of this node.
+    pub fn count_subscribers(&self, topic_name: &str) -> RCLResult<usize> {
+        graph::count_endpoints(self, EndpointType::Subscription, topic_name)
+    }
+
+    /// Create a sub-node, which extends the namespace of this node by `sub_namespace`.
+    /// See `SubNode`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NameError` if `sub_namespace` is absolute or
+    /// the extended namespace is invalid.
+    pub fn create_sub_node(self: &Arc<Self>, sub_namespace: &str) -> Result<SubNode, NameError> {
+        SubNode::new(self.clone(), "", sub_namespace)
+    }
+}
+
+/// Sub-node, which shares the node of ROS2 but extends its namespace.
+///
+/// Relative names of topics, services and actions created by a sub-node
+/// are prefixed by the sub-namespace.
+/// Absolute names and names beginning with `~` are not changed.
+/// Because the underlying node is shared,
+/// sub-nodes do not increase discovery traffic.
+///
+/// # Example
+///
+/// ```
+/// use safe_drive::{context::Context, msg::common_interfaces::std_msgs};
+///
+/// let ctx = Context::new().unwrap();
+/// let node = ctx
+///     .create_node("sub_node_rs", Some("robot"), Default::default())
+///     .unwrap();
+///
+/// let camera = node.create_sub_node("camera_left").unwrap();
+/// assert_eq!(camera.get_effective_namespace(), "/robot/camera_left");
+///
+/// // The topic name becomes "/robot/camera_left/image".
+/// let publisher = camera
+///     .create_publisher::<std_msgs::msg::UInt32>("image", None)
+///     .unwrap();
+/// ```
+#[derive(Clone)]
+pub struct SubNode {
+    node: Arc<Node>,
+    sub_namespace: String,
+}
+
+impl SubNode {
+    fn new(node: Arc<Node>, parent: &str, sub_namespace: &str) -> Result<Self, NameError> {
+        let invalid = |reason: &str| NameError {
+            kind: NameKind::Namespace,
+            name: sub_namespace.to_string(),
+            reason: reason.to_string(),
+            invalid_index: Some(0),
+        };
+
+        if sub_namespace.is_empty() {
+            return Err(invalid("sub-namespace must not be empty"));
+        }
+        if sub_namespace.starts_with('/') {
+            return Err(invalid("sub-namespace must be relative"));
+        }
+
+        let sub_namespace = if parent.is_empty() {
+            sub_namespace.to_string()
+        } else {
+            format!("{parent}/{sub_namespace}")
+        };
+
+        let sub_node = SubNode {
+            node,
+            sub_namespace,
+        };
+        names::validate_namespace(&sub_node.get_effective_namespace())?;
+
+        Ok(sub_node)
+    }
+
+    /// Get the underlying node.
+    pub fn get_node(&self) -> &Arc<Node> {
+        &self.node
+    }
+
+    /// Get the sub-namespace, which is relative to the namespace of the node.
+    pub fn get_sub_namespace(&self) -> &str {
+        &self.sub_namespace
+    }
+
+    /// Get the namespace of the node extended by the sub-namespace.
+    pub fn get_effective_namespace(&self) -> String {
+        match self.node.get_namespace() {
+            Ok(ns) if ns != "/" => format!("{ns}/{}", self.sub_namespace),
+            _ => format!("/{}", self.sub_namespace),
+        }
+    }
+
+    /// Prefix `name` by the sub-namespace if `name` is relative.
+    pub fn extend_name(&self, name: &str) -> String {
+        if name.starts_with('/') || name.starts_with('~') {
+            name.to_string()
+        } else {
+            format!("{}/{name}", self.sub_namespace)
+        }
+    }
+
+    /// Create a sub-node of this sub-node.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NameError` if `sub_namespace` is absolute or
+    /// the extended namespace is invalid.
+    pub fn create_sub_node(&self, sub_namespace: &str) -> Result<SubNode, NameError> {
+        SubNode::new(self.node.clone(), &self.sub_namespace, sub_namespace)
+    }
+
+    /// Same as `Node::create_publisher` but the name is extended by the sub-namespace.
+    pub fn create_publisher<T: TypeSupport>(
+        &self,
+        topic_name: &str,
+        qos: Option<qos::Profile>,
+    ) -> RCLResult<Publisher<T>> {
+        self.node
+            .create_publisher(&self.extend_name(topic_name), qos)
+    }
+
+    /// Same as `Node::create_publisher_intra_process` but the name is extended by the sub-namespace.
+    pub fn create_publisher_intra_process<T: TypeSupport + 'static>(
+        &self,
+        topic_name: &str,
+        qos: Option<qos::Profile>,
+    ) -> RCLResult<Publisher<T>> {
+        self.node
+            .create_publisher_intra_process(&self.extend_name(topic_name), qos)
+    }
+
+    /// Same as `Node::create_subscriber` but the name is extended by the sub-namespace.
+    pub fn create_subscriber<T: TypeSupport>(
+        &self,
+        topic_name: &str,
+        qos: Option<qos::Profile>,
+    ) -> RCLResult<Subscriber<T>> {
+        self.node
+            .create_subscriber(&self.extend_name(topic_name), qos)
+    }
+
+    /// Same as `Node::create_subscriber_intra_process` but the name is extended by the sub-namespace.
+    pub fn create_subscriber_intra_process<T: TypeSupport + Send + Sync + 'static>(
+        &self,
+        topic_name: &str,
+        qos: Option<qos::Profile>,
+    ) -> RCLResult<IntraProcessSubscriber<T>> {
+        self.node
+            .create_subscriber_intra_process(&self.extend_name(topic_name), qos)
+    }
+
+    /// Same as `Node::create_server` but the name is extended by the sub-namespace.
+    pub fn create_server<T: ServiceMsg>(
+        &self,
+        service_name: &str,
+        qos: Option<qos::Profile>,
+    ) -> RCLResult<Server<T>> {
+        self.node
+            .create_server(&self.extend_name(service_name), qos)
+    }
+
+    /// Same as `Node::create_client` but the name is extended by the sub-namespace.
+    pub fn create_client<T: ServiceMsg>(
+        &self,
+        service_name: &str,
+        qos: Option<qos::Profile>,
+    ) -> RCLResult<Client<T>> {
+        self.node
+            .create_client(&self.extend_name(service_name), qos)
+    }
+
+    /// Create a server of an action whose name is extended by the sub-namespace.
+    /// See `safe_drive::action::server::Server::new`.
+    pub fn create_action_server<T: ActionMsg>(
+        &self,
+        action_name: &str,
+        qos: Option<ServerQosOption>,
+    ) -> Result<action::server::Server<T>, DynError> {
+        let action_name = self.extend_name(action_name);
+        self.node.expand_topic_name(&action_name, false)?;
+        Ok(action::server::Server::new(
+            self.node.clone(),
+            &action_name,
+            qos,
+        )?)
+    }
+
+    /// Create a client of an action whose name is extended by the sub-namespace.
+    /// See `safe_drive::action::client::Client::new`.
+    pub fn create_action_client<T: ActionMsg>(
+        &self,
+        action_name: &str,
+        qos: Option<ClientQosOption>,
+    ) -> Result<action::client::Client<T>, DynError> {
+        let action_name = self.extend_name(action_name);
+        self.node.expand_topic_name(&action_name, false)?;
+        Ok(action::client::Client::new(
+            self.node.clone(),
+            &action_name,
+            qos,
+        )?)
+    }
 }
 
 impl Drop for Node {
@@ -256,30 +920,165 @@ impl Drop for Node {
 }
 
 /// Options for nodes.
+///
+/// # Example
+///
+/// ```
+/// use safe_drive::{context::Context, node::NodeOptions};
+///
+/// let ctx = Context::new().unwrap();
+///
+/// // Remap "chatter" to "chatter_remapped", and set a parameter.
+/// let options = NodeOptions::new()
+///     .arguments([
+///         "--ros-args",
+///         "-r",
+///         "chatter:=chatter_remapped",
+///         "-p",
+///         "param:=10",
+///     ])
+///     .use_global_arguments(false)
+///     .enable_rosout(false);
+///
+/// let node = ctx
+///     .create_node("node_options_rs", None, options)
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone)]
 pub struct NodeOptions {
-    options: rcl::rcl_node_options_t,
+    arguments: Vec<String>,
+    use_global_arguments: bool,
+    enable_rosout: bool,
+    rosout_qos: qos::Profile,
 }
 
 impl Default for NodeOptions {
+    /// Default options.
+    /// - Arguments: empty,
+    /// - Use global arguments: true,
+    /// - Enable rosout: true,
+    /// - Rosout QoS: `Profile::rosout()`
     fn default() -> Self {
-        let options = rcl::MTSafeFn::rcl_node_get_default_options();
-        NodeOptions { options }
+        NodeOptions {
+            arguments: Vec::new(),
+            use_global_arguments: true,
+            enable_rosout: true,
+            rosout_qos: qos::Profile::rosout(),
+        }
     }
 }
 
 impl NodeOptions {
     /// Create options to create a node
     pub fn new() -> Self {
-        // TODO: allow setting options
         Default::default()
     }
 
-    pub(crate) fn as_ptr(&self) -> *const rcl::rcl_node_options_t {
+    /// Set command line arguments which apply only to the node.
+    /// ROS specific arguments must be enclosed by `--ros-args` and `--`,
+    /// such as remapping rules (`-r`), parameters (`-p`), and parameter files (`--params-file`).
+    ///
+    /// Arguments are parsed when the node is created,
+    /// and an error is returned if they are invalid.
+    pub fn arguments<I, S>(mut self, arguments: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.arguments = arguments.into_iter().map(|arg| arg.into()).collect();
+        self
+    }
+
+    /// If false, only the arguments of the options are used,
+    /// otherwise global arguments of the context are used also.
+    pub fn use_global_arguments(mut self, use_global_arguments: bool) -> Self {
+        self.use_global_arguments = use_global_arguments;
+        self
+    }
+
+    /// Enable or disable publishing logs to `/rosout`.
+    pub fn enable_rosout(mut self, enable_rosout: bool) -> Self {
+        self.enable_rosout = enable_rosout;
+        self
+    }
+
+    /// Set the QoS profile of `/rosout`.
+    pub fn rosout_qos(mut self, rosout_qos: qos::Profile) -> Self {
+        self.rosout_qos = rosout_qos;
+        self
+    }
+
+    pub fn get_arguments(&self) -> &[String] {
+        &self.arguments
+    }
+
+    pub fn get_use_global_arguments(&self) -> bool {
+        self.use_global_arguments
+    }
+
+    pub fn get_enable_rosout(&self) -> bool {
+        self.enable_rosout
+    }
+
+    pub fn get_rosout_qos(&self) -> &qos::Profile {
+        &self.rosout_qos
+    }
+
+    /// Convert into `rcl_node_options_t` by parsing the arguments.
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::InvalidRosArgs` if the arguments contain unknown ROS specific arguments, or
+    /// - errors of `rcl_parse_arguments`, such as `RCLError::InvalidRemapRule` and `RCLError::InvalidParamRule`.
+    fn to_rcl(&self) -> RCLResult<RCLNodeOptions> {
+        let mut options = RCLNodeOptions {
+            options: rcl::MTSafeFn::rcl_node_get_default_options(),
+        };
+
+        options.options.use_global_arguments = self.use_global_arguments;
+        options.options.enable_rosout = self.enable_rosout;
+        options.options.rosout_qos = (&self.rosout_qos).into();
+
+        if !self.arguments.is_empty() {
+            let args = self
+                .arguments
+                .iter()
+                .map(|arg| CString::new(arg.as_str()))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| RCLError::InvalidArgument)?;
+            let argv: Vec<_> = args.iter().map(|arg| arg.as_ptr()).collect();
+
+            {
+                let guard = rcl::MT_UNSAFE_FN.lock();
+                guard.rcl_parse_arguments(
+                    argv.len() as _,
+                    argv.as_ptr(),
+                    get_allocator(),
+                    &mut options.options.arguments,
+                )?;
+            }
+
+            if rcl::MTSafeFn::rcl_arguments_get_count_unparsed_ros(&options.options.arguments) > 0 {
+                return Err(RCLError::InvalidRosArgs);
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+/// `rcl_node_options_t` which is finalized when dropped.
+struct RCLNodeOptions {
+    options: rcl::rcl_node_options_t,
+}
+
+impl RCLNodeOptions {
+    fn as_ptr(&self) -> *const rcl::rcl_node_options_t {
         &self.options
     }
 }
 
-impl Drop for NodeOptions {
+impl Drop for RCLNodeOptions {
     fn drop(&mut self) {
         let guard = rcl::MT_UNSAFE_FN.lock();
         let _ = guard.rcl_node_options_fini(&mut self.options);
@@ -288,3 +1087,16 @@ impl Drop for NodeOptions {
 
 unsafe impl Sync for Node {}
 unsafe impl Send for Node {}
+
+fn to_name_error(name: &str, is_service: bool, e: RCLError) -> NameError {
+    NameError {
+        kind: if is_service {
+            NameKind::ServiceName
+        } else {
+            NameKind::TopicName
+        },
+        name: name.to_string(),
+        reason: e.to_string(),
+        invalid_index: None,
+    }
+}
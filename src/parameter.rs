@@ -160,6 +160,11 @@
 //! // async_std::task::block_on(run_wait(param_server)); // Spawn an asynchronous task.
 //! ```
 
+pub mod client;
+pub mod event;
+pub mod typed;
+pub mod yaml;
+
 use crate::{
     error::{DynError, RCLResult},
     helper::Contains,
@@ -169,13 +174,14 @@ use crate::{
         interfaces::rcl_interfaces::{
             self,
             msg::{
-                ParameterDescriptor, ParameterDescriptorSeq, ParameterValue, ParameterValueSeq,
-                SetParametersResultSeq,
+                ParameterDescriptor, ParameterDescriptorSeq, ParameterEvent, ParameterSeq,
+                ParameterValue, ParameterValueSeq, SetParametersResultSeq,
             },
             srv::{
                 DescribeParameters, DescribeParametersResponse, GetParameterTypes,
                 GetParameterTypesResponse, GetParameters, GetParametersResponse, ListParameters,
-                ListParametersResponse, SetParameters, SetParametersResponse,
+                ListParametersResponse, SetParameters, SetParametersAtomically,
+                SetParametersAtomicallyResponse, SetParametersResponse,
             },
         },
         BoolSeq, F64Seq, I64Seq, RosString, RosStringSeq, U8Seq,
@@ -189,6 +195,7 @@ use crate::{
         CallbackResult, Selector,
     },
     signal_handler::Signaled,
+    topic::publisher::Publisher,
 };
 use num_traits::Zero;
 use parking_lot::RwLock;
@@ -202,6 +209,7 @@ use std::{
     slice::from_raw_parts,
     sync::Arc,
     task::Poll,
+    time::SystemTime,
 };
 
 /// Parameter server.
@@ -267,6 +275,79 @@ pub struct ParameterServer {
     node: Arc<Node>,
 }
 
+/// Options of `ParameterServer`.
+///
+/// # Example
+///
+/// ```
+/// use safe_drive::{context::Context, parameter::ParameterServerOptions};
+///
+/// let ctx = Context::new().unwrap();
+/// let node = ctx.create_node("param_server_options_rs", None, Default::default()).unwrap();
+///
+/// // Remote nodes can create and delete parameters,
+/// // and parameter overrides are used only when parameters are declared.
+/// let options = ParameterServerOptions::new()
+///     .allow_undeclared_parameters(true)
+///     .automatically_declare_parameters_from_overrides(false);
+///
+/// let param_server = node.create_parameter_server_with_options(options).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct ParameterServerOptions {
+    allow_undeclared_parameters: bool,
+    automatically_declare_parameters_from_overrides: bool,
+}
+
+impl Default for ParameterServerOptions {
+    /// Default options.
+    /// - Allow undeclared parameters: false,
+    /// - Automatically declare parameters from overrides: true
+    ///
+    /// Unlike rclcpp, parameters are automatically declared from overrides by default
+    /// for compatibility with former versions.
+    fn default() -> Self {
+        ParameterServerOptions {
+            allow_undeclared_parameters: false,
+            automatically_declare_parameters_from_overrides: true,
+        }
+    }
+}
+
+impl ParameterServerOptions {
+    /// Create options to create a parameter server.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// If true, remote nodes can set undeclared parameters,
+    /// which are declared as dynamically typed parameters,
+    /// and they can undeclare dynamically typed parameters by setting `PARAMETER_NOT_SET`.
+    pub fn allow_undeclared_parameters(mut self, allow_undeclared_parameters: bool) -> Self {
+        self.allow_undeclared_parameters = allow_undeclared_parameters;
+        self
+    }
+
+    /// If true, all parameter overrides given by arguments are declared when creating a server.
+    /// Otherwise, they are used as initial values when parameters are declared.
+    pub fn automatically_declare_parameters_from_overrides(
+        mut self,
+        automatically_declare_parameters_from_overrides: bool,
+    ) -> Self {
+        self.automatically_declare_parameters_from_overrides =
+            automatically_declare_parameters_from_overrides;
+        self
+    }
+
+    pub fn get_allow_undeclared_parameters(&self) -> bool {
+        self.allow_undeclared_parameters
+    }
+
+    pub fn get_automatically_declare_parameters_from_overrides(&self) -> bool {
+        self.automatically_declare_parameters_from_overrides
+    }
+}
+
 /// Describe a range of integer.
 ///
 /// # Example
@@ -278,6 +359,16 @@ pub struct ParameterServer {
 /// assert!(range.contains(-2));
 /// assert!(range.contains(10));
 /// assert!(!range.contains(9));
+///
+/// // The bounds are always valid even if they are not on the step.
+/// let range = IntegerRange { min: 0, max: 10, step: 4 };
+/// assert!(range.contains(8));
+/// assert!(range.contains(10));
+/// assert!(!range.contains(9));
+///
+/// // 0 means no step.
+/// let range = IntegerRange { min: 0, max: 10, step: 0 };
+/// assert!(range.contains(7));
 /// ```
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct IntegerRange {
@@ -289,12 +380,30 @@ pub struct IntegerRange {
 impl Contains for IntegerRange {
     type T = i64;
     fn contains(&self, val: i64) -> bool {
-        let range = self.min..=self.max;
-        if range.contains(&val) {
-            let diff = val - self.min;
-            (diff % self.step as i64) == 0
-        } else {
-            false
+        // Same as rclcpp, the bounds are valid regardless of the step.
+        if val == self.min || val == self.max {
+            return true;
+        }
+
+        if !(self.min..=self.max).contains(&val) {
+            return false;
+        }
+
+        if self.step == 0 {
+            return true;
+        }
+
+        let diff = val as i128 - self.min as i128;
+        diff % self.step as i128 == 0
+    }
+}
+
+impl From<&rcl_interfaces::msg::IntegerRange> for IntegerRange {
+    fn from(range: &rcl_interfaces::msg::IntegerRange) -> Self {
+        IntegerRange {
+            min: range.from_value,
+            max: range.to_value,
+            step: range.step as usize,
         }
     }
 }
@@ -320,6 +429,11 @@ impl From<&IntegerRange> for rcl_interfaces::msg::IntegerRange {
 /// assert!(range.contains(-2.0));
 /// assert!(range.contains(10.0));
 /// assert!(!range.contains(9.0));
+///
+/// // Rounding errors are tolerated.
+/// let range = FloatingPointRange { min: 0.0, max: 1.0, step: 0.1 };
+/// assert!(range.contains(0.3));
+/// assert!(!range.contains(0.35));
 /// ```
 #[derive(Debug, PartialEq, PartialOrd)]
 pub struct FloatingPointRange {
@@ -328,6 +442,16 @@ pub struct FloatingPointRange {
     pub step: f64,
 }
 
+impl From<&rcl_interfaces::msg::FloatingPointRange> for FloatingPointRange {
+    fn from(range: &rcl_interfaces::msg::FloatingPointRange) -> Self {
+        FloatingPointRange {
+            min: range.from_value,
+            max: range.to_value,
+            step: range.step,
+        }
+    }
+}
+
 impl From<&FloatingPointRange> for rcl_interfaces::msg::FloatingPointRange {
     fn from(range: &FloatingPointRange) -> Self {
         rcl_interfaces::msg::FloatingPointRange {
@@ -341,20 +465,30 @@ impl From<&FloatingPointRange> for rcl_interfaces::msg::FloatingPointRange {
 impl Contains for FloatingPointRange {
     type T = f64;
     fn contains(&self, val: f64) -> bool {
-        let range = self.min..=self.max;
-        if range.contains(&val) {
-            if self.step.is_zero() {
-                return true;
-            }
+        // Same as rclcpp, the bounds are valid regardless of the step.
+        if are_doubles_equal(val, self.min) || are_doubles_equal(val, self.max) {
+            return true;
+        }
 
-            let diff = val - self.min;
-            (diff % self.step).is_zero()
-        } else {
-            false
+        if !(self.min..=self.max).contains(&val) {
+            return false;
         }
+
+        if self.step.is_zero() {
+            return true;
+        }
+
+        let n = ((val - self.min) / self.step).round();
+        are_doubles_equal(val, self.min + n * self.step)
     }
 }
 
+// copied from https://github.com/ros2/rclcpp/blob/rolling/rclcpp/src/rclcpp/node_interfaces/node_parameters.cpp
+fn are_doubles_equal(x: f64, y: f64) -> bool {
+    const ULP: f64 = 100.0;
+    (x - y).abs() <= f64::EPSILON * (x + y).abs() * ULP
+}
+
 #[derive(Debug)]
 pub struct Descriptor {
     pub description: String,
@@ -365,6 +499,144 @@ pub struct Descriptor {
     pub integer_range: Option<IntegerRange>,
 }
 
+impl From<&ParameterDescriptor> for Descriptor {
+    fn from(descriptor: &ParameterDescriptor) -> Self {
+        Descriptor {
+            description: descriptor.description.get_string(),
+            additional_constraints: descriptor.additional_constraints.get_string(),
+            read_only: descriptor.read_only,
+            dynamic_typing: descriptor.dynamic_typing,
+            floating_point_range: descriptor
+                .floating_point_range
+                .iter()
+                .next()
+                .map(|range| range.into()),
+            integer_range: descriptor
+                .integer_range
+                .iter()
+                .next()
+                .map(|range| range.into()),
+        }
+    }
+}
+
+impl Descriptor {
+    /// Create a descriptor of a statically typed and writable parameter without ranges.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::parameter::Descriptor;
+    ///
+    /// let descriptor = Descriptor::new("max speed [m/s]")
+    ///     .floating_point_range(0.0, 10.0, 0.0)
+    ///     .read_only();
+    /// assert!(descriptor.read_only);
+    /// ```
+    pub fn new(description: impl Into<String>) -> Self {
+        Descriptor {
+            description: description.into(),
+            additional_constraints: String::new(),
+            read_only: false,
+            dynamic_typing: false,
+            floating_point_range: None,
+            integer_range: None,
+        }
+    }
+
+    pub fn read_only(mut self) -> Self {
+        self.read_only = true;
+        self
+    }
+
+    pub fn additional_constraints(mut self, constraints: impl Into<String>) -> Self {
+        self.additional_constraints = constraints.into();
+        self
+    }
+
+    pub fn integer_range(mut self, min: i64, max: i64, step: usize) -> Self {
+        self.integer_range = Some(IntegerRange { min, max, step });
+        self
+    }
+
+    pub fn floating_point_range(mut self, min: f64, max: f64, step: f64) -> Self {
+        self.floating_point_range = Some(FloatingPointRange { min, max, step });
+        self
+    }
+}
+
+impl Descriptor {
+    /// Check whether a value is in the range.
+    /// Ranges are applied to each element of arrays.
+    fn check_range(&self, value: &Value) -> bool {
+        match (value, &self.integer_range) {
+            (Value::I64(x), Some(range)) => return range.contains(*x),
+            (Value::VecI64(arr), Some(range)) => return arr.iter().all(|x| range.contains(*x)),
+            _ => (),
+        }
+
+        match (value, &self.floating_point_range) {
+            (Value::F64(x), Some(range)) => range.contains(*x),
+            (Value::VecF64(arr), Some(range)) => arr.iter().all(|x| range.contains(*x)),
+            _ => true,
+        }
+    }
+
+    /// Check whether this descriptor is consistent with a parameter `name` whose value is `value`.
+    fn validate(&self, name: &str, value: &Value) -> Result<(), String> {
+        if self.integer_range.is_some() && self.floating_point_range.is_some() {
+            return Err(format!(
+                "{} cannot have both an integer range and a floating point range.",
+                name
+            ));
+        }
+
+        if let Some(range) = &self.integer_range {
+            if range.min > range.max {
+                return Err(format!("{}: the integer range is empty.", name));
+            }
+
+            if !self.dynamic_typing && !matches!(value, Value::I64(_) | Value::VecI64(_)) {
+                return Err(format!(
+                    "{}({}) is not an integer (array) type.",
+                    name,
+                    value.type_name()
+                ));
+            }
+        }
+
+        if let Some(range) = &self.floating_point_range {
+            if range.min.is_nan() || range.max.is_nan() || range.min > range.max {
+                return Err(format!("{}: the floating point range is empty.", name));
+            }
+
+            if range.step.is_nan() || range.step < 0.0 {
+                return Err(format!("{}: the step must not be negative.", name));
+            }
+
+            if !self.dynamic_typing && !matches!(value, Value::F64(_) | Value::VecF64(_)) {
+                return Err(format!(
+                    "{}({}) is not a floating point (array) type.",
+                    name,
+                    value.type_name()
+                ));
+            }
+        }
+
+        if !self.check_range(value) {
+            return Err(format!("{} is exceeding the range", name));
+        }
+
+        Ok(())
+    }
+}
+
+impl From<&str> for Descriptor {
+    fn from(description: &str) -> Self {
+        Descriptor::new(description)
+    }
+}
+
 /// Parameters.
 ///
 /// # Example
@@ -382,10 +654,74 @@ pub struct Descriptor {
 ///     params.set_parameter(name, value, false /* read_only */, Some("description".to_string()))
 /// }
 /// ```
-#[derive(Debug)]
 pub struct Parameters {
     params: BTreeMap<String, Parameter>,
     updated: BTreeSet<String>,
+    events: Option<EventPublisher>,
+    on_set_callbacks: Vec<(OnSetParametersCallbackId, OnSetParametersCallback)>,
+    next_callback_id: u64,
+    bindings: Vec<Box<dyn typed::Binding>>,
+    overrides: BTreeMap<String, Value>,
+    allow_undeclared: bool,
+}
+
+impl std::fmt::Debug for Parameters {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Parameters")
+            .field("params", &self.params)
+            .field("updated", &self.updated)
+            .finish()
+    }
+}
+
+/// Result of validation by `OnSetParametersCallback`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetParametersResult {
+    pub successful: bool,
+
+    /// Why the parameters were rejected.
+    pub reason: String,
+}
+
+impl SetParametersResult {
+    /// Accept the parameters.
+    pub fn success() -> Self {
+        SetParametersResult {
+            successful: true,
+            reason: String::new(),
+        }
+    }
+
+    /// Reject the parameters with the reason.
+    pub fn failure(reason: impl Into<String>) -> Self {
+        SetParametersResult {
+            successful: false,
+            reason: reason.into(),
+        }
+    }
+}
+
+/// Callback to validate parameters before they are set.
+/// See `Parameters::add_on_set_parameters_callback`.
+pub type OnSetParametersCallback =
+    Box<dyn FnMut(&Parameters, &[(String, Value)]) -> SetParametersResult + Send + Sync>;
+
+/// ID of `OnSetParametersCallback`, which is used to remove it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct OnSetParametersCallbackId(u64);
+
+/// Parameters changed by remote nodes.
+#[derive(Default)]
+struct Changes {
+    new: Vec<String>,
+    changed: Vec<String>,
+    deleted: Vec<(String, Value)>,
+}
+
+/// Publisher of `/parameter_events`.
+struct EventPublisher {
+    publisher: Publisher<ParameterEvent>,
+    node_name: String,
 }
 
 impl Parameters {
@@ -393,6 +729,232 @@ impl Parameters {
         Self {
             params: BTreeMap::new(),
             updated: BTreeSet::new(),
+            events: None,
+            on_set_callbacks: Vec::new(),
+            next_callback_id: 0,
+            bindings: Vec::new(),
+            overrides: BTreeMap::new(),
+            allow_undeclared: false,
+        }
+    }
+
+    /// Add a callback to validate parameters before they are set.
+    /// The callback takes the parameters and the proposed names and values,
+    /// and the update is rejected if the callback returns a failure.
+    /// The reason of the failure is returned to the caller,
+    /// including remote nodes calling the `set_parameters` service.
+    ///
+    /// Callbacks are invoked in the order of addition until one of them fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::{
+    ///     context::Context,
+    ///     parameter::{SetParametersResult, Value},
+    /// };
+    ///
+    /// let ctx = Context::new().unwrap();
+    /// let node = ctx.create_node("on_set_param_rs", None, Default::default()).unwrap();
+    /// let param_server = node.create_parameter_server().unwrap();
+    ///
+    /// let mut params = param_server.params.write();
+    /// params.add_on_set_parameters_callback(Box::new(|_params, proposed| {
+    ///     for (name, value) in proposed {
+    ///         if let ("max_speed", Value::F64(x)) = (name.as_str(), value) {
+    ///             if *x > 10.0 {
+    ///                 return SetParametersResult::failure("max_speed must be <= 10.0");
+    ///             }
+    ///         }
+    ///     }
+    ///     SetParametersResult::success()
+    /// }));
+    ///
+    /// assert!(params.set_parameter("max_speed".to_string(), Value::F64(5.0), false, None).is_ok());
+    /// assert!(params.set_parameter("max_speed".to_string(), Value::F64(20.0), false, None).is_err());
+    /// ```
+    pub fn add_on_set_parameters_callback(
+        &mut self,
+        callback: OnSetParametersCallback,
+    ) -> OnSetParametersCallbackId {
+        let id = OnSetParametersCallbackId(self.next_callback_id);
+        self.next_callback_id += 1;
+        self.on_set_callbacks.push((id, callback));
+        id
+    }
+
+    /// Remove a callback added by `add_on_set_parameters_callback`.
+    /// Return `false` if there is no such callback.
+    pub fn remove_on_set_parameters_callback(&mut self, id: OnSetParametersCallbackId) -> bool {
+        let len = self.on_set_callbacks.len();
+        self.on_set_callbacks.retain(|(i, _)| *i != id);
+        len != self.on_set_callbacks.len()
+    }
+
+    /// Invoke callbacks added by `add_on_set_parameters_callback`.
+    fn call_on_set_callbacks(&mut self, proposed: &[(String, Value)]) -> Result<(), String> {
+        // Values must be convertible to the types of bound fields.
+        for binding in self.bindings.iter() {
+            binding.check(proposed)?;
+        }
+
+        // Take the callbacks out to pass `self` to them.
+        let mut callbacks = std::mem::take(&mut self.on_set_callbacks);
+
+        let mut result = Ok(());
+        for (_, callback) in callbacks.iter_mut() {
+            let r = callback(self, proposed);
+            if !r.successful {
+                result = Err(r.reason);
+                break;
+            }
+        }
+
+        self.on_set_callbacks = callbacks;
+        result
+    }
+
+    /// Set a value of a declared parameter, and update bound fields.
+    fn apply(&mut self, name: &str, value: Value) {
+        if let Some(param) = self.params.get_mut(name) {
+            for binding in self.bindings.iter() {
+                binding.update(name, &value);
+            }
+            param.value = value;
+        }
+    }
+
+    /// Check whether the proposed values can be set by a remote node.
+    /// Undeclared parameters are accepted if they are allowed,
+    /// and `Value::NotSet` means undeclaring a parameter.
+    fn check_remote_set(&self, proposed: &[(String, Value)]) -> Result<(), String> {
+        for (name, value) in proposed {
+            let Some(param) = self.params.get(name) else {
+                if self.allow_undeclared {
+                    continue;
+                }
+                return Err(format!("no such parameter: name = {}", name));
+            };
+
+            if param.descriptor.read_only {
+                return Err(format!("{} is read only", name));
+            }
+
+            if *value == Value::NotSet && !param.descriptor.dynamic_typing {
+                return Err(format!("{} is a statically typed value", name));
+            }
+
+            if !param.check_range(value) {
+                return Err(format!("{} is not in the range", name));
+            }
+
+            if !param.descriptor.dynamic_typing && !param.value.type_check(value) {
+                return Err(format!(
+                    "failed type checking: dst = {}, src = {}",
+                    param.value.type_name(),
+                    value.type_name()
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Set a value proposed by a remote node, which has been checked by `check_remote_set`.
+    /// Undeclared parameters are declared as dynamically typed parameters,
+    /// and parameters are undeclared by `Value::NotSet`.
+    fn apply_remote(&mut self, name: String, value: Value, changes: &mut Changes) {
+        if !self.params.contains_key(&name) {
+            if value != Value::NotSet {
+                let param = Parameter::new(value, false, true, name.clone());
+                self.params.insert(name.clone(), param);
+                changes.deleted.retain(|(n, _)| *n != name);
+                changes.new.push(name);
+            }
+        } else if value == Value::NotSet {
+            if let Some(param) = self.params.remove(&name) {
+                changes.new.retain(|n| *n != name);
+                changes.changed.retain(|n| *n != name);
+                changes.deleted.push((name, param.value));
+            }
+        } else {
+            self.apply(&name, value);
+            if !changes.new.contains(&name) && !changes.changed.contains(&name) {
+                changes.changed.push(name);
+            }
+        }
+    }
+
+    /// Publish changes made by `apply_remote`, and mark new or changed parameters as updated.
+    /// Return `true` if there are new or changed parameters.
+    fn commit_changes(&mut self, changes: Changes) -> bool {
+        if changes.new.is_empty() && changes.changed.is_empty() && changes.deleted.is_empty() {
+            return false;
+        }
+
+        let new: Vec<&str> = changes.new.iter().map(|k| k.as_str()).collect();
+        let changed: Vec<&str> = changes.changed.iter().map(|k| k.as_str()).collect();
+        let deleted: Vec<(&str, &Value)> = changes
+            .deleted
+            .iter()
+            .map(|(k, v)| (k.as_str(), v))
+            .collect();
+        self.publish_event(&new, &changed, &deleted);
+
+        for (name, _) in changes.deleted.iter() {
+            self.updated.remove(name);
+        }
+
+        let is_updated = !changes.new.is_empty() || !changes.changed.is_empty();
+        self.updated.extend(changes.new);
+        self.updated.extend(changes.changed);
+        is_updated
+    }
+
+    /// Publish an event of parameters to `/parameter_events`.
+    /// `new` and `changed` are names of parameters,
+    /// and `deleted` are names and values of removed parameters.
+    fn publish_event(&self, new: &[&str], changed: &[&str], deleted: &[(&str, &Value)]) {
+        let Some(events) = &self.events else {
+            return;
+        };
+
+        let to_seq = |params: Vec<(&str, &Value)>| {
+            let mut seq = ParameterSeq::new(params.len())?;
+            for (dst, (name, value)) in seq.iter_mut().zip(params) {
+                dst.name.assign(name);
+                dst.value = value.into();
+            }
+            Some(seq)
+        };
+
+        let lookup = |names: &[&str]| {
+            names
+                .iter()
+                .filter_map(|name| self.params.get_key_value(*name))
+                .map(|(name, param)| (name.as_str(), &param.value))
+                .collect::<Vec<_>>()
+        };
+
+        let logger = Logger::new("safe_drive");
+        let (Some(mut event), Some(new), Some(changed), Some(deleted)) = (
+            ParameterEvent::new(),
+            to_seq(lookup(new)),
+            to_seq(lookup(changed)),
+            to_seq(deleted.to_vec()),
+        ) else {
+            pr_fatal_in!(logger, "{}:{}: failed allocation", file!(), line!());
+            return;
+        };
+
+        event.stamp = SystemTime::now().into();
+        event.node.assign(&events.node_name);
+        event.new_parameters = new;
+        event.changed_parameters = changed;
+        event.deleted_parameters = deleted;
+
+        if let Err(e) = events.publisher.send(&event) {
+            pr_error_in!(logger, "failed to publish a parameter event: {e}");
         }
     }
 
@@ -404,18 +966,105 @@ impl Parameters {
         self.params.get(name)
     }
 
-    pub fn add_parameter(&mut self, name: String, parameter: Parameter) -> Result<(), DynError> {
+    /// Remove a declared parameter, and publish it as a deleted parameter to `/parameter_events`.
+    ///
+    /// # Errors
+    ///
+    /// Same as rclcpp, read only or statically typed parameters cannot be undeclared.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::{context::Context, parameter::Value};
+    ///
+    /// let ctx = Context::new().unwrap();
+    /// let node = ctx.create_node("undeclare_param_rs", None, Default::default()).unwrap();
+    /// let param_server = node.create_parameter_server().unwrap();
+    ///
+    /// let mut params = param_server.params.write();
+    /// params
+    ///     .set_dynamically_typed_parameter("my_param".to_string(), Value::I64(1), false, None)
+    ///     .unwrap();
+    ///
+    /// params.undeclare_parameter("my_param").unwrap();
+    /// assert!(params.get_parameter("my_param").is_none());
+    /// ```
+    pub fn undeclare_parameter(&mut self, name: &str) -> Result<(), DynError> {
+        let Some(param) = self.params.get(name) else {
+            let msg = format!("no such parameter: name = {}", name);
+            return Err(msg.into());
+        };
+
+        if param.descriptor.read_only {
+            let msg = format!("{} is read only", name);
+            return Err(msg.into());
+        }
+
+        if !param.descriptor.dynamic_typing {
+            let msg = format!("{} is a statically typed value", name);
+            return Err(msg.into());
+        }
+
+        if let Some(param) = self.params.remove(name) {
+            self.updated.remove(name);
+            self.publish_event(&[], &[], &[(name, &param.value)]);
+        }
+
+        Ok(())
+    }
+
+    /// Get an initial value of a parameter being declared.
+    /// If a value is given by parameter overrides, it is used instead of `value`.
+    fn initial_value(
+        &self,
+        name: &str,
+        value: Value,
+        dynamic_typing: bool,
+    ) -> Result<Value, String> {
+        match self.overrides.get(name) {
+            Some(overridden) if dynamic_typing || value.type_check(overridden) => {
+                Ok(overridden.clone())
+            }
+            Some(overridden) => Err(format!(
+                "failed type checking: dst = {}, src = {}",
+                value.type_name(),
+                overridden.type_name()
+            )),
+            None => Ok(value),
+        }
+    }
+
+    pub fn add_parameter(
+        &mut self,
+        name: String,
+        mut parameter: Parameter,
+    ) -> Result<(), DynError> {
         if let Some(_) = self.params.get_mut(&name) {
             let msg: String = format!("{} is already declared", name);
             Err(msg.into())
         } else {
-            if parameter.check_range(&parameter.value) {
-                self.params.insert(name, parameter);
-                Ok(())
-            } else {
-                let msg = format!("{} is exceeding the range", name);
-                return Err(msg.into());
-            }
+            parameter.value =
+                self.initial_value(&name, parameter.value, parameter.descriptor.dynamic_typing)?;
+
+            parameter.descriptor.validate(&name, &parameter.value)?;
+
+            self.call_on_set_callbacks(&[(name.clone(), parameter.value.clone())])?;
+            self.params.insert(name.clone(), parameter);
+            self.publish_event(&[&name], &[], &[]);
+            Ok(())
+        }
+    }
+
+    /// Declare a read-only parameter which reports a value used internally,
+    /// such as an overridden QoS policy.
+    /// If the parameter has been already declared, it becomes read-only.
+    pub(crate) fn declare_read_only(&mut self, name: String, value: Value) {
+        if let Some(param) = self.params.get_mut(&name) {
+            param.descriptor.read_only = true;
+        } else {
+            let param = Parameter::new(value, true, false, name.clone());
+            self.params.insert(name.clone(), param);
+            self.publish_event(&[&name], &[], &[]);
         }
     }
 
@@ -445,7 +1094,9 @@ impl Parameters {
             }
 
             if param.value.type_check(&value) {
-                param.value = value;
+                self.call_on_set_callbacks(&[(name.clone(), value.clone())])?;
+                self.apply(&name, value);
+                self.publish_event(&[], &[&name], &[]);
                 Ok(())
             } else {
                 let msg = format!(
@@ -456,13 +1107,16 @@ impl Parameters {
                 Err(msg.into())
             }
         } else {
+            let value = self.initial_value(&name, value, false)?;
             let param = Parameter::new(
                 value,
                 read_only,
                 false,
                 description.unwrap_or_else(|| name.clone()),
             );
-            self.params.insert(name, param);
+            self.call_on_set_callbacks(&[(name.clone(), param.value.clone())])?;
+            self.params.insert(name.clone(), param);
+            self.publish_event(&[&name], &[], &[]);
             Ok(())
         }
     }
@@ -490,19 +1144,32 @@ impl Parameters {
                 return Err(msg.into());
             }
 
-            param.value = value;
+            self.call_on_set_callbacks(&[(name.clone(), value.clone())])?;
+            self.apply(&name, value);
+            self.publish_event(&[], &[&name], &[]);
         } else {
+            let value = self.initial_value(&name, value, true)?;
             let param = Parameter::new(
                 value,
                 read_only,
                 true,
                 description.unwrap_or_else(|| name.clone()),
             );
-            self.params.insert(name, param);
+            self.call_on_set_callbacks(&[(name.clone(), param.value.clone())])?;
+            self.params.insert(name.clone(), param);
+            self.publish_event(&[&name], &[], &[]);
         }
         Ok(())
     }
 
+    /// Set a floating point range to a parameter.
+    /// The range is applied to each element if the value is an array.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the parameter has an integer range,
+    /// if the parameter is not a floating point (array) type,
+    /// or if the current value is not in the range.
     pub fn set_floating_point_range(
         &mut self,
         name: &str,
@@ -513,36 +1180,26 @@ impl Parameters {
         let range = FloatingPointRange { min, max, step };
 
         if let Some(param) = self.params.get_mut(name) {
-            if !param.check_range(&param.value) {
-                let msg = format!("{:?} is not in the range.", param.value);
+            let old = param.descriptor.floating_point_range.replace(range);
+            if let Err(msg) = param.descriptor.validate(name, &param.value) {
+                param.descriptor.floating_point_range = old;
                 return Err(msg.into());
             }
-
-            if param.descriptor.dynamic_typing {
-                param.descriptor.floating_point_range = Some(range);
-                Ok(())
-            } else {
-                match &param.value {
-                    Value::F64(_) | Value::VecF64(_) => {
-                        param.descriptor.floating_point_range = Some(range);
-                        Ok(())
-                    }
-                    _ => {
-                        let msg = format!(
-                            "{}({}) is not a floating point (array) type.",
-                            name,
-                            param.value.type_name()
-                        );
-                        Err(msg.into())
-                    }
-                }
-            }
+            Ok(())
         } else {
             let msg = format!("no such parameter: name = {}", name);
             Err(msg.into())
         }
     }
 
+    /// Set an integer range to a parameter.
+    /// The range is applied to each element if the value is an array.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the parameter has a floating point range,
+    /// if the parameter is not an integer (array) type,
+    /// or if the current value is not in the range.
     pub fn set_integer_range(
         &mut self,
         name: &str,
@@ -553,30 +1210,12 @@ impl Parameters {
         let range = IntegerRange { min, max, step };
 
         if let Some(param) = self.params.get_mut(name) {
-            if !param.check_range(&param.value) {
-                let msg = format!("{:?} is not in the range.", param.value);
+            let old = param.descriptor.integer_range.replace(range);
+            if let Err(msg) = param.descriptor.validate(name, &param.value) {
+                param.descriptor.integer_range = old;
                 return Err(msg.into());
             }
-
-            if param.descriptor.dynamic_typing {
-                param.descriptor.integer_range = Some(range);
-                Ok(())
-            } else {
-                match &param.value {
-                    Value::I64(_) | Value::VecI64(_) => {
-                        param.descriptor.integer_range = Some(range);
-                        Ok(())
-                    }
-                    _ => {
-                        let msg = format!(
-                            "{}({}) is not an integer (array) type.",
-                            name,
-                            param.value.type_name()
-                        );
-                        Err(msg.into())
-                    }
-                }
-            }
+            Ok(())
         } else {
             let msg = format!("no such parameter: name = {}", name);
             Err(msg.into())
@@ -606,21 +1245,11 @@ impl Parameter {
     }
 
     fn check_range(&self, value: &Value) -> bool {
-        match (value, &self.descriptor.integer_range) {
-            (Value::I64(x), Some(range)) => return range.contains(*x),
-            (Value::VecI64(arr), Some(range)) => return arr.iter().all(|x| range.contains(*x)),
-            _ => (),
-        }
-
-        match (value, &self.descriptor.floating_point_range) {
-            (Value::F64(x), Some(range)) => range.contains(*x),
-            (Value::VecF64(arr), Some(range)) => return arr.iter().all(|x| range.contains(*x)),
-            _ => true,
-        }
+        self.descriptor.check_range(value)
     }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
 pub enum Value {
     NotSet,
     Bool(bool),
@@ -650,7 +1279,7 @@ impl Value {
         )
     }
 
-    fn type_name(&self) -> &str {
+    pub(crate) fn type_name(&self) -> &str {
         match self {
             Value::Bool(_) => "Bool",
             Value::I64(_) => "I64",
@@ -666,6 +1295,121 @@ impl Value {
     }
 }
 
+/// Type of a parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParameterType {
+    NotSet,
+    Bool,
+    I64,
+    F64,
+    String,
+    VecU8,
+    VecBool,
+    VecI64,
+    VecF64,
+    VecString,
+}
+
+impl From<u8> for ParameterType {
+    fn from(type_: u8) -> Self {
+        match type_ {
+            1 => ParameterType::Bool,
+            2 => ParameterType::I64,
+            3 => ParameterType::F64,
+            4 => ParameterType::String,
+            5 => ParameterType::VecU8,
+            6 => ParameterType::VecBool,
+            7 => ParameterType::VecI64,
+            8 => ParameterType::VecF64,
+            9 => ParameterType::VecString,
+            _ => ParameterType::NotSet,
+        }
+    }
+}
+
+impl From<&Value> for ParameterType {
+    fn from(value: &Value) -> Self {
+        match value {
+            Value::NotSet => ParameterType::NotSet,
+            Value::Bool(_) => ParameterType::Bool,
+            Value::I64(_) => ParameterType::I64,
+            Value::F64(_) => ParameterType::F64,
+            Value::String(_) => ParameterType::String,
+            Value::VecU8(_) => ParameterType::VecU8,
+            Value::VecBool(_) => ParameterType::VecBool,
+            Value::VecI64(_) => ParameterType::VecI64,
+            Value::VecF64(_) => ParameterType::VecF64,
+            Value::VecString(_) => ParameterType::VecString,
+        }
+    }
+}
+
+/// Error of conversion from `Value` to a Rust type.
+///
+/// # Example
+///
+/// ```
+/// use safe_drive::parameter::{ParameterType, Value};
+///
+/// let x: f64 = (&Value::F64(1.5)).try_into().unwrap();
+/// assert_eq!(x, 1.5);
+///
+/// let err = f64::try_from(&Value::I64(1)).unwrap_err();
+/// assert_eq!(err.expected, ParameterType::F64);
+/// assert_eq!(err.actual, ParameterType::I64);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValueTypeError {
+    pub expected: ParameterType,
+    pub actual: ParameterType,
+}
+
+impl Display for ValueTypeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "failed type checking: expected = {:?}, actual = {:?}",
+            self.expected, self.actual
+        )
+    }
+}
+
+impl std::error::Error for ValueTypeError {}
+
+macro_rules! impl_value_conversion {
+    ($ty:ty, $variant:ident) => {
+        impl TryFrom<&Value> for $ty {
+            type Error = ValueTypeError;
+
+            fn try_from(value: &Value) -> Result<Self, Self::Error> {
+                match value {
+                    Value::$variant(x) => Ok(x.to_owned()),
+                    _ => Err(ValueTypeError {
+                        expected: ParameterType::$variant,
+                        actual: value.into(),
+                    }),
+                }
+            }
+        }
+
+        impl From<$ty> for Value {
+            fn from(x: $ty) -> Self {
+                Value::$variant(x)
+            }
+        }
+    };
+}
+
+impl_value_conversion!(bool, Bool);
+impl_value_conversion!(i64, I64);
+impl_value_conversion!(f64, F64);
+impl_value_conversion!(String, String);
+impl_value_conversion!(Vec<u8>, VecU8);
+impl_value_conversion!(Vec<bool>, VecBool);
+impl_value_conversion!(Vec<i64>, VecI64);
+impl_value_conversion!(Vec<f64>, VecF64);
+impl_value_conversion!(Vec<String>, VecString);
+
 impl Display for Value {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -857,18 +1601,24 @@ impl From<&rcl_variant_t> for Value {
 }
 
 impl ParameterServer {
-    pub(crate) fn new(node: Arc<Node>) -> Result<Self, DynError> {
-        let params_value = {
-            let mut guard = crate::rcl::MT_UNSAFE_FN.lock();
-            let fqn = node.get_fully_qualified_name()?;
-            let arguments = unsafe { &mut (*node.context.as_ptr_mut()).global_arguments };
-            guard.parameter_map(fqn.as_str(), arguments)?
-        };
+    pub(crate) fn new(node: Arc<Node>, options: ParameterServerOptions) -> Result<Self, DynError> {
+        let params_value = node.parameter_overrides()?;
         let mut params = Parameters::new();
-        for (k, v) in params_value.into_iter() {
-            let _ = params.set_parameter(k, v, false, None);
+        params.allow_undeclared = options.allow_undeclared_parameters;
+        params.events = Some(EventPublisher {
+            publisher: node
+                .create_publisher("/parameter_events", Some(Profile::parameter_events()))?,
+            node_name: node.get_fully_qualified_name()?,
+        });
+        if options.automatically_declare_parameters_from_overrides {
+            for (k, v) in params_value.into_iter() {
+                let _ = params.set_parameter(k, v, false, None);
+            }
+        } else {
+            params.overrides = params_value;
         }
         let params = Arc::new(RwLock::new(params));
+        node.set_parameters(&params);
         let ps = params.clone();
         let n = node.clone();
 
@@ -916,20 +1666,8 @@ fn param_server(
 ) -> Result<(), DynError> {
     if let Ok(mut selector) = node.context.create_selector() {
         add_srv_list(&node, &mut selector, params.clone())?;
-        add_srv_set(
-            &node,
-            &mut selector,
-            params.clone(),
-            "set_parameters",
-            cond_callback.clone(),
-        )?;
-        add_srv_set(
-            &node,
-            &mut selector,
-            params.clone(),
-            "set_parameters_atomically",
-            cond_callback,
-        )?;
+        add_srv_set(&node, &mut selector, params.clone(), cond_callback.clone())?;
+        add_srv_set_atomically(&node, &mut selector, params.clone(), cond_callback)?;
         add_srv_get(&node, &mut selector, params.clone())?;
         add_srv_get_types(&node, &mut selector, params.clone())?;
         add_srv_describe(&node, &mut selector, params)?;
@@ -961,12 +1699,11 @@ fn add_srv_set(
     node: &Arc<Node>,
     selector: &mut Selector,
     params: Arc<RwLock<Parameters>>,
-    service_name: &str,
     cond_callback: GuardCondition,
 ) -> RCLResult<()> {
     let name = node.get_name()?;
     let srv_set = node.create_server::<SetParameters>(
-        &format!("{name}/{service_name}"),
+        &format!("{name}/set_parameters"),
         Some(Profile::default()),
     )?;
 
@@ -982,50 +1719,36 @@ fn add_srv_set(
 
             let slice = results.as_slice_mut();
 
-            let mut updated = 0;
-            {
+            let is_updated = {
                 let mut guard = params.write();
+                let mut changes = Changes::default();
                 for (i, param) in req.parameters.iter().enumerate() {
                     let key = param.name.to_string();
                     let val: Value = (&param.value).into();
 
-                    if let Some(original) = guard.params.get_mut(&key) {
-                        if original.descriptor.read_only {
-                            let reason = format!("{} is read only", key);
-                            slice[i].reason.assign(&reason);
-                            continue;
-                        }
-
-                        if !original.check_range(&val) {
-                            let reason = format!("{} is not in the range", key);
-                            slice[i].reason.assign(&reason);
-                            slice[i].successful = false;
-                            continue;
-                        }
+                    // Each parameter is validated and set individually.
+                    let proposed = [(key, val)];
+                    let result = guard
+                        .check_remote_set(&proposed)
+                        .and_then(|_| guard.call_on_set_callbacks(&proposed));
 
-                        if original.descriptor.dynamic_typing || original.value.type_check(&val) {
-                            original.value = val;
+                    match result {
+                        Ok(()) => {
+                            let [(key, val)] = proposed;
+                            guard.apply_remote(key, val, &mut changes);
                             slice[i].successful = true;
-                            updated += 1;
-                            guard.updated.insert(key);
-                        } else {
-                            let reason = format!(
-                                "failed type checking: dst = {}, src = {}",
-                                original.value.type_name(),
-                                val.type_name()
-                            );
+                        }
+                        Err(reason) => {
                             slice[i].reason.assign(&reason);
                             slice[i].successful = false;
                         }
-                    } else {
-                        let reason = format!("no such parameter: name = {}", key);
-                        slice[i].reason.assign(&reason);
-                        slice[i].successful = false;
                     }
                 }
-            }
 
-            if updated > 0 && cond_callback.trigger().is_err() {
+                guard.commit_changes(changes)
+            };
+
+            if is_updated && cond_callback.trigger().is_err() {
                 let logger = Logger::new("safe_drive");
                 pr_fatal_in!(
                     logger,
@@ -1045,6 +1768,70 @@ fn add_srv_set(
     Ok(())
 }
 
+fn add_srv_set_atomically(
+    node: &Arc<Node>,
+    selector: &mut Selector,
+    params: Arc<RwLock<Parameters>>,
+    cond_callback: GuardCondition,
+) -> Result<(), DynError> {
+    let name = node.get_name()?;
+    let srv_set = node.create_server::<SetParametersAtomically>(
+        &format!("{name}/set_parameters_atomically"),
+        Some(Profile::default()),
+    )?;
+
+    selector.add_server(
+        srv_set,
+        Box::new(move |req, _| {
+            let mut response = SetParametersAtomicallyResponse::new().unwrap();
+
+            let proposed: Vec<(String, Value)> = req
+                .parameters
+                .iter()
+                .map(|param| (param.name.to_string(), (&param.value).into()))
+                .collect();
+
+            let is_updated = {
+                let mut guard = params.write();
+
+                // All parameters are validated before any of them is set.
+                let result = guard
+                    .check_remote_set(&proposed)
+                    .and_then(|_| guard.call_on_set_callbacks(&proposed));
+
+                if let Err(reason) = result {
+                    response.result.reason.assign(&reason);
+                    response.result.successful = false;
+                    return response;
+                }
+
+                let mut changes = Changes::default();
+                for (key, val) in proposed {
+                    guard.apply_remote(key, val, &mut changes);
+                }
+
+                guard.commit_changes(changes)
+            };
+
+            response.result.successful = true;
+
+            if is_updated && cond_callback.trigger().is_err() {
+                let logger = Logger::new("safe_drive");
+                pr_fatal_in!(
+                    logger,
+                    "{}:{}: failed to trigger a condition variable",
+                    file!(),
+                    line!()
+                );
+            }
+
+            response
+        }),
+    );
+
+    Ok(())
+}
+
 fn add_srv_get(
     node: &Arc<Node>,
     selector: &mut Selector,
@@ -1065,6 +1852,8 @@ fn add_srv_get(
                 let key = name.to_string();
                 if let Some(param) = gurad.params.get(&key) {
                     result.push(&param.value);
+                } else {
+                    result.push(&Value::NotSet);
                 }
             }
 
@@ -1327,6 +2116,7 @@ impl<'a> Future for AsyncWait<'a> {
         if is_halt() {
             return Poll::Ready(Err(Signaled.into()));
         }
+        self.param_server.node.context.check_ok()?;
 
         match self.state {
             WaitState::Init => {
@@ -0,0 +1,362 @@
+//! Client to get, set, list and describe parameters of a remote node.
+//!
+//! Methods without suffix are asynchronous,
+//! and methods with `_timeout` suffix block until a response arrives or timeout.
+//!
+//! # Example
+//!
+//! ```
+//! use safe_drive::{context::Context, parameter::Value};
+//! use std::time::Duration;
+//!
+//! let ctx = Context::new().unwrap();
+//! let node = ctx
+//!     .create_node("param_client_rs", None, Default::default())
+//!     .unwrap();
+//!
+//! // Create a client of parameters of "/param_server_rs".
+//! let mut client = node.create_parameter_client("/param_server_rs").unwrap();
+//!
+//! async fn run_client(mut client: safe_drive::parameter::client::ParameterClient) {
+//!     let values = client.get_parameters(&["my_flag"]).await.unwrap();
+//!     println!("my_flag = {}", values[0]);
+//!
+//!     let results = client
+//!         .set_parameters(&[("my_flag".to_string(), Value::Bool(true))])
+//!         .await
+//!         .unwrap();
+//!     if let Err(reason) = &results[0] {
+//!         println!("failed to set my_flag: {reason}");
+//!     }
+//! }
+//!
+//! // async_std::task::block_on(run_client(client)); // Spawn an asynchronous task.
+//!
+//! // Synchronous version.
+//! let dur = Duration::from_millis(10);
+//! // The server does not exist, so this times out.
+//! assert!(client.list_parameters_timeout(&[], 0, dur).is_err());
+//! ```
+
+use super::{Descriptor, ParameterType, Value};
+use crate::{
+    error::DynError,
+    msg::{
+        interfaces::rcl_interfaces::{
+            msg::{ParameterSeq, SetParametersResult},
+            srv::{
+                DescribeParameters, DescribeParametersRequest, GetParameterTypes,
+                GetParameterTypesRequest, GetParameters, GetParametersRequest, ListParameters,
+                ListParametersRequest, SetParameters, SetParametersAtomically,
+                SetParametersAtomicallyRequest, SetParametersRequest,
+            },
+        },
+        RosStringSeq, ServiceMsg,
+    },
+    node::Node,
+    qos::Profile,
+    service::client::Client,
+    RecvResult,
+};
+use std::{sync::Arc, time::Duration};
+
+/// Result of `list_parameters`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ListParametersResult {
+    /// Names of the parameters.
+    pub names: Vec<String>,
+
+    /// Prefixes of the parameters.
+    pub prefixes: Vec<String>,
+}
+
+/// Client of parameters of a remote node.
+/// See `safe_drive::parameter::client`.
+pub struct ParameterClient {
+    remote_node_name: String,
+    get: Service<GetParameters>,
+    set: Service<SetParameters>,
+    set_atomically: Service<SetParametersAtomically>,
+    list: Service<ListParameters>,
+    describe: Service<DescribeParameters>,
+    get_types: Service<GetParameterTypes>,
+    node: Arc<Node>,
+}
+
+impl ParameterClient {
+    pub(crate) fn new(node: Arc<Node>, remote_node_name: &str) -> Result<Self, DynError> {
+        Ok(Self {
+            remote_node_name: remote_node_name.to_string(),
+            get: Service::new(&node, remote_node_name, "get_parameters")?,
+            set: Service::new(&node, remote_node_name, "set_parameters")?,
+            set_atomically: Service::new(&node, remote_node_name, "set_parameters_atomically")?,
+            list: Service::new(&node, remote_node_name, "list_parameters")?,
+            describe: Service::new(&node, remote_node_name, "describe_parameters")?,
+            get_types: Service::new(&node, remote_node_name, "get_parameter_types")?,
+            node,
+        })
+    }
+
+    /// Get the name of the remote node.
+    pub fn get_remote_node_name(&self) -> &str {
+        &self.remote_node_name
+    }
+
+    /// Get values of parameters.
+    /// `Value::NotSet` is returned for parameters which do not exist.
+    pub async fn get_parameters(&mut self, names: &[&str]) -> Result<Vec<Value>, DynError> {
+        let req = get_request(names)?;
+        let res = self.get.call(&self.node, &req).await?;
+        Ok(res.values.iter().map(|v| v.into()).collect())
+    }
+
+    /// Same as `get_parameters` but this blocks until `t` duration.
+    pub fn get_parameters_timeout(
+        &mut self,
+        names: &[&str],
+        t: Duration,
+    ) -> Result<Vec<Value>, DynError> {
+        let req = get_request(names)?;
+        let res = self.get.call_timeout(&self.node, &req, t)?;
+        Ok(res.values.iter().map(|v| v.into()).collect())
+    }
+
+    /// Set values of parameters.
+    /// The result of each parameter is returned in order,
+    /// and `Err` contains the reason why the parameter was not set.
+    pub async fn set_parameters(
+        &mut self,
+        params: &[(String, Value)],
+    ) -> Result<Vec<Result<(), String>>, DynError> {
+        let req = SetParametersRequest {
+            parameters: parameter_seq(params)?,
+        };
+        let res = self.set.call(&self.node, &req).await?;
+        Ok(res.results.iter().map(to_result).collect())
+    }
+
+    /// Same as `set_parameters` but this blocks until `t` duration.
+    pub fn set_parameters_timeout(
+        &mut self,
+        params: &[(String, Value)],
+        t: Duration,
+    ) -> Result<Vec<Result<(), String>>, DynError> {
+        let req = SetParametersRequest {
+            parameters: parameter_seq(params)?,
+        };
+        let res = self.set.call_timeout(&self.node, &req, t)?;
+        Ok(res.results.iter().map(to_result).collect())
+    }
+
+    /// Set values of parameters atomically.
+    /// All or none of the parameters are set,
+    /// and `Err` contains the reason why the parameters were not set.
+    pub async fn set_parameters_atomically(
+        &mut self,
+        params: &[(String, Value)],
+    ) -> Result<Result<(), String>, DynError> {
+        let req = SetParametersAtomicallyRequest {
+            parameters: parameter_seq(params)?,
+        };
+        let res = self.set_atomically.call(&self.node, &req).await?;
+        Ok(to_result(&res.result))
+    }
+
+    /// Same as `set_parameters_atomically` but this blocks until `t` duration.
+    pub fn set_parameters_atomically_timeout(
+        &mut self,
+        params: &[(String, Value)],
+        t: Duration,
+    ) -> Result<Result<(), String>, DynError> {
+        let req = SetParametersAtomicallyRequest {
+            parameters: parameter_seq(params)?,
+        };
+        let res = self.set_atomically.call_timeout(&self.node, &req, t)?;
+        Ok(to_result(&res.result))
+    }
+
+    /// List names of parameters beginning with `prefixes`.
+    /// If `prefixes` is empty, all parameters are listed.
+    /// `depth` is the maximum number of separators `.` in the names, and 0 means unlimited.
+    pub async fn list_parameters(
+        &mut self,
+        prefixes: &[&str],
+        depth: u64,
+    ) -> Result<ListParametersResult, DynError> {
+        let req = list_request(prefixes, depth)?;
+        let res = self.list.call(&self.node, &req).await?;
+        Ok(ListParametersResult {
+            names: res.result.names.iter().map(|s| s.get_string()).collect(),
+            prefixes: res.result.prefixes.iter().map(|s| s.get_string()).collect(),
+        })
+    }
+
+    /// Same as `list_parameters` but this blocks until `t` duration.
+    pub fn list_parameters_timeout(
+        &mut self,
+        prefixes: &[&str],
+        depth: u64,
+        t: Duration,
+    ) -> Result<ListParametersResult, DynError> {
+        let req = list_request(prefixes, depth)?;
+        let res = self.list.call_timeout(&self.node, &req, t)?;
+        Ok(ListParametersResult {
+            names: res.result.names.iter().map(|s| s.get_string()).collect(),
+            prefixes: res.result.prefixes.iter().map(|s| s.get_string()).collect(),
+        })
+    }
+
+    /// Get descriptors of parameters as pairs of the name and the descriptor.
+    /// Parameters which do not exist are not contained.
+    pub async fn describe_parameters(
+        &mut self,
+        names: &[&str],
+    ) -> Result<Vec<(String, Descriptor)>, DynError> {
+        let req = DescribeParametersRequest {
+            names: string_seq(names)?,
+        };
+        let res = self.describe.call(&self.node, &req).await?;
+        Ok(res
+            .descriptors
+            .iter()
+            .map(|d| (d.name.get_string(), d.into()))
+            .collect())
+    }
+
+    /// Same as `describe_parameters` but this blocks until `t` duration.
+    pub fn describe_parameters_timeout(
+        &mut self,
+        names: &[&str],
+        t: Duration,
+    ) -> Result<Vec<(String, Descriptor)>, DynError> {
+        let req = DescribeParametersRequest {
+            names: string_seq(names)?,
+        };
+        let res = self.describe.call_timeout(&self.node, &req, t)?;
+        Ok(res
+            .descriptors
+            .iter()
+            .map(|d| (d.name.get_string(), d.into()))
+            .collect())
+    }
+
+    /// Get types of parameters.
+    /// `ParameterType::NotSet` is returned for parameters which do not exist.
+    pub async fn get_parameter_types(
+        &mut self,
+        names: &[&str],
+    ) -> Result<Vec<ParameterType>, DynError> {
+        let req = GetParameterTypesRequest {
+            names: string_seq(names)?,
+        };
+        let res = self.get_types.call(&self.node, &req).await?;
+        Ok(res.types.iter().map(|t| (*t).into()).collect())
+    }
+
+    /// Same as `get_parameter_types` but this blocks until `t` duration.
+    pub fn get_parameter_types_timeout(
+        &mut self,
+        names: &[&str],
+        t: Duration,
+    ) -> Result<Vec<ParameterType>, DynError> {
+        let req = GetParameterTypesRequest {
+            names: string_seq(names)?,
+        };
+        let res = self.get_types.call_timeout(&self.node, &req, t)?;
+        Ok(res.types.iter().map(|t| (*t).into()).collect())
+    }
+}
+
+/// A service client which is recreated if a request was canceled.
+struct Service<T> {
+    service_name: String,
+    client: Option<Client<T>>,
+}
+
+impl<T: ServiceMsg> Service<T> {
+    fn new(node: &Arc<Node>, remote_node_name: &str, name: &str) -> Result<Self, DynError> {
+        let service_name = format!("{remote_node_name}/{name}");
+        let client = node.create_client(&service_name, Some(Profile::default()))?;
+        Ok(Self {
+            service_name,
+            client: Some(client),
+        })
+    }
+
+    fn take(&mut self, node: &Arc<Node>) -> Result<Client<T>, DynError> {
+        match self.client.take() {
+            Some(client) => Ok(client),
+            None => Ok(node.create_client(&self.service_name, Some(Profile::default()))?),
+        }
+    }
+
+    async fn call(
+        &mut self,
+        node: &Arc<Node>,
+        req: &<T as ServiceMsg>::Request,
+    ) -> Result<<T as ServiceMsg>::Response, DynError> {
+        let client = self.take(node)?;
+        let (client, res, _header) = client.send(req)?.recv().await?;
+        self.client = Some(client);
+        Ok(res)
+    }
+
+    fn call_timeout(
+        &mut self,
+        node: &Arc<Node>,
+        req: &<T as ServiceMsg>::Request,
+        t: Duration,
+    ) -> Result<<T as ServiceMsg>::Response, DynError> {
+        let client = self.take(node)?;
+        let mut selector = node.context.create_selector()?;
+        match client.send(req)?.recv_timeout(t, &mut selector) {
+            RecvResult::Ok((client, res, _header)) => {
+                self.client = Some(client);
+                Ok(res)
+            }
+            RecvResult::RetryLater(receiver) => {
+                self.client = Some(receiver.give_up());
+                Err(format!("{}: timed out", self.service_name).into())
+            }
+            RecvResult::Err(e) => Err(e),
+        }
+    }
+}
+
+fn to_result(result: &SetParametersResult) -> Result<(), String> {
+    if result.successful {
+        Ok(())
+    } else {
+        Err(result.reason.get_string())
+    }
+}
+
+fn string_seq(strs: &[&str]) -> Result<RosStringSeq<0, 0>, DynError> {
+    let mut seq = RosStringSeq::new(strs.len()).ok_or("failed allocation")?;
+    seq.iter_mut().zip(strs.iter()).for_each(|(dst, src)| {
+        dst.assign(src);
+    });
+    Ok(seq)
+}
+
+fn parameter_seq(params: &[(String, Value)]) -> Result<ParameterSeq<0>, DynError> {
+    let mut seq = ParameterSeq::new(params.len()).ok_or("failed allocation")?;
+    for (dst, (name, value)) in seq.iter_mut().zip(params.iter()) {
+        dst.name.assign(name);
+        dst.value = value.into();
+    }
+    Ok(seq)
+}
+
+fn get_request(names: &[&str]) -> Result<GetParametersRequest, DynError> {
+    Ok(GetParametersRequest {
+        names: string_seq(names)?,
+    })
+}
+
+fn list_request(prefixes: &[&str], depth: u64) -> Result<ListParametersRequest, DynError> {
+    Ok(ListParametersRequest {
+        prefixes: string_seq(prefixes)?,
+        depth,
+    })
+}
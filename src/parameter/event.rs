@@ -0,0 +1,238 @@
+//! Handler of `/parameter_events`, which dispatches changes of parameters of nodes to callbacks.
+//!
+//! `ParameterServer` publishes new, changed and deleted parameters to `/parameter_events`.
+//! `ParameterEventHandler` subscribes it and invokes callbacks registered
+//! for each parameter of each node or for every event.
+//!
+//! # Example
+//!
+//! ```
+//! use safe_drive::{context::Context, logger::Logger, pr_info};
+//!
+//! let ctx = Context::new().unwrap();
+//! let node = ctx
+//!     .create_node("param_event_handler_rs", None, Default::default())
+//!     .unwrap();
+//!
+//! let mut handler = node.create_parameter_event_handler().unwrap();
+//!
+//! // Invoked when "my_flag" of "/param_server_rs" is set.
+//! let logger = Logger::new("param_event_handler_rs");
+//! let id = handler.add_parameter_callback(
+//!     "my_flag",
+//!     "/param_server_rs",
+//!     Box::new(move |name, value| pr_info!(logger, "{name} = {value}")),
+//! );
+//!
+//! // Invoked for every event.
+//! handler.add_event_callback(Box::new(|event| println!("{}", event.node.get_string())));
+//!
+//! // Callbacks are invoked by a selector.
+//! let mut selector = ctx.create_selector().unwrap();
+//! selector.add_parameter_event_handler(&mut handler);
+//! // loop {
+//! //     selector.wait().unwrap();
+//! // }
+//!
+//! // Callbacks can be removed even after the handler is added to the selector,
+//! // and callbacks can add or remove callbacks.
+//! assert!(handler.remove_parameter_callback(id));
+//! ```
+
+use super::Value;
+use crate::{
+    error::DynError, msg::interfaces::rcl_interfaces::msg::ParameterEvent, node::Node,
+    qos::Profile, topic::subscriber::Subscriber,
+};
+use parking_lot::Mutex;
+use std::sync::Arc;
+
+/// Callback invoked with the name and the value of a parameter.
+pub type ParameterEventCallback = Box<dyn FnMut(&str, &Value) + Send>;
+
+/// Callback invoked with a received event.
+pub type EventCallback = Box<dyn FnMut(&ParameterEvent) + Send>;
+
+/// ID of a callback, which is used to remove it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CallbackId(u64);
+
+/// Handler of `/parameter_events`.
+/// See `safe_drive::parameter::event`.
+pub struct ParameterEventHandler {
+    subscriber: Option<Subscriber<ParameterEvent>>,
+    callbacks: Arc<Mutex<Callbacks>>,
+    pub(crate) node: Arc<Node>,
+}
+
+impl ParameterEventHandler {
+    pub(crate) fn new(node: Arc<Node>) -> Result<Self, DynError> {
+        let subscriber =
+            node.create_subscriber("/parameter_events", Some(Profile::parameter_events()))?;
+        Ok(Self {
+            subscriber: Some(subscriber),
+            callbacks: Arc::new(Mutex::new(Callbacks::default())),
+            node,
+        })
+    }
+
+    /// Add a callback invoked when a parameter `parameter_name` of a node `node_name`
+    /// is declared or changed.
+    /// Relative node names are expanded by the namespace of the node of this handler,
+    /// and an empty node name means the node of this handler.
+    pub fn add_parameter_callback(
+        &self,
+        parameter_name: &str,
+        node_name: &str,
+        callback: ParameterEventCallback,
+    ) -> CallbackId {
+        let node_name = self.resolve_node_name(node_name);
+        let mut guard = self.callbacks.lock();
+        let id = guard.new_id();
+        guard
+            .parameter_callbacks
+            .push((id, node_name, parameter_name.to_string(), callback));
+        id
+    }
+
+    /// Remove a callback added by `add_parameter_callback`.
+    /// Return `false` if there is no such callback.
+    pub fn remove_parameter_callback(&self, id: CallbackId) -> bool {
+        let mut guard = self.callbacks.lock();
+        let len = guard.parameter_callbacks.len();
+        guard.parameter_callbacks.retain(|(i, _, _, _)| *i != id);
+        len != guard.parameter_callbacks.len() || remove_id(&mut guard.taken_parameter, id)
+    }
+
+    /// Add a callback invoked for every received event.
+    pub fn add_event_callback(&self, callback: EventCallback) -> CallbackId {
+        let mut guard = self.callbacks.lock();
+        let id = guard.new_id();
+        guard.event_callbacks.push((id, callback));
+        id
+    }
+
+    /// Remove a callback added by `add_event_callback`.
+    /// Return `false` if there is no such callback.
+    pub fn remove_event_callback(&self, id: CallbackId) -> bool {
+        let mut guard = self.callbacks.lock();
+        let len = guard.event_callbacks.len();
+        guard.event_callbacks.retain(|(i, _)| *i != id);
+        len != guard.event_callbacks.len() || remove_id(&mut guard.taken_event, id)
+    }
+
+    /// Receive an event asynchronously, and invoke callbacks.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this handler has been added to a selector.
+    pub async fn recv(&mut self) -> Result<(), DynError> {
+        let subscriber = self
+            .subscriber
+            .as_mut()
+            .ok_or("the handler has been added to a selector")?;
+        let event = subscriber.recv().await?;
+        Callbacks::dispatch(&self.callbacks, &event);
+        Ok(())
+    }
+
+    pub(crate) fn take_subscriber(&mut self) -> Option<Subscriber<ParameterEvent>> {
+        self.subscriber.take()
+    }
+
+    pub(crate) fn get_callbacks(&self) -> Arc<Mutex<Callbacks>> {
+        self.callbacks.clone()
+    }
+
+    fn resolve_node_name(&self, node_name: &str) -> String {
+        if node_name.is_empty() {
+            self.node.get_fully_qualified_name().unwrap_or_default()
+        } else if node_name.starts_with('/') {
+            node_name.to_string()
+        } else {
+            match self.node.get_namespace() {
+                Ok(ns) if ns != "/" => format!("{ns}/{node_name}"),
+                _ => format!("/{node_name}"),
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct Callbacks {
+    parameter_callbacks: Vec<(CallbackId, String, String, ParameterEventCallback)>,
+    event_callbacks: Vec<(CallbackId, EventCallback)>,
+    next_id: u64,
+
+    /// IDs of callbacks taken out by `dispatch`.
+    /// Removing callbacks while dispatching removes their IDs from these.
+    taken_parameter: Vec<CallbackId>,
+    taken_event: Vec<CallbackId>,
+}
+
+impl Callbacks {
+    fn new_id(&mut self) -> CallbackId {
+        let id = CallbackId(self.next_id);
+        self.next_id += 1;
+        id
+    }
+
+    /// Invoke callbacks.
+    /// The callbacks are taken out while they are running,
+    /// so that they can add or remove callbacks of the handler.
+    pub(crate) fn dispatch(callbacks: &Mutex<Self>, event: &ParameterEvent) {
+        let (mut parameter_callbacks, mut event_callbacks) = {
+            let mut guard = callbacks.lock();
+            let parameter_callbacks = std::mem::take(&mut guard.parameter_callbacks);
+            let event_callbacks = std::mem::take(&mut guard.event_callbacks);
+            guard.taken_parameter = parameter_callbacks.iter().map(|c| c.0).collect();
+            guard.taken_event = event_callbacks.iter().map(|c| c.0).collect();
+            (parameter_callbacks, event_callbacks)
+        };
+
+        let node_name = event.node.get_string();
+
+        for param in event
+            .new_parameters
+            .iter()
+            .chain(event.changed_parameters.iter())
+        {
+            let name = param.name.get_string();
+            let value: Value = (&param.value).into();
+            for (id, node, param_name, callback) in parameter_callbacks.iter_mut() {
+                if *node == node_name
+                    && *param_name == name
+                    && callbacks.lock().taken_parameter.contains(id)
+                {
+                    callback(&name, &value);
+                }
+            }
+        }
+
+        for (id, callback) in event_callbacks.iter_mut() {
+            if callbacks.lock().taken_event.contains(id) {
+                callback(event);
+            }
+        }
+
+        // Put back the callbacks which were not removed before the added ones.
+        let mut guard = callbacks.lock();
+
+        let taken = std::mem::take(&mut guard.taken_parameter);
+        parameter_callbacks.retain(|c| taken.contains(&c.0));
+        parameter_callbacks.append(&mut guard.parameter_callbacks);
+        guard.parameter_callbacks = parameter_callbacks;
+
+        let taken = std::mem::take(&mut guard.taken_event);
+        event_callbacks.retain(|c| taken.contains(&c.0));
+        event_callbacks.append(&mut guard.event_callbacks);
+        guard.event_callbacks = event_callbacks;
+    }
+}
+
+/// Remove `id` from `ids`, and return whether it existed.
+fn remove_id(ids: &mut Vec<CallbackId>, id: CallbackId) -> bool {
+    let len = ids.len();
+    ids.retain(|i| *i != id);
+    len != ids.len()
+}
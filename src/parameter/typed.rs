@@ -0,0 +1,285 @@
+//! Typed parameters, which bind fields of a struct to parameters.
+//!
+//! A struct implementing `TypedParameters` declares its fields as parameters
+//! by `Parameters::bind`.
+//! Initial values are taken from parameter overrides given by command line arguments
+//! or parameter files if they exist, and otherwise fields' values are used as default values.
+//! After that, the struct is kept updated when the parameters are set locally or remotely.
+//! If binding fails, the parameters declared by the binding are undeclared.
+//!
+//! # Example
+//!
+//! ```
+//! use safe_drive::{
+//!     context::Context,
+//!     error::DynError,
+//!     parameter::{
+//!         typed::{Binder, TypedParameters},
+//!         Descriptor, Value,
+//!     },
+//! };
+//!
+//! #[derive(Debug, Default)]
+//! struct Motor {
+//!     max_speed: f64,
+//!     reversed: bool,
+//! }
+//!
+//! impl TypedParameters for Motor {
+//!     fn bind(&mut self, binder: &mut Binder) -> Result<(), DynError> {
+//!         binder.field(
+//!             "max_speed",
+//!             &mut self.max_speed,
+//!             Descriptor::new("max speed [m/s]").floating_point_range(0.0, 10.0, 0.0),
+//!         )?;
+//!         binder.field("reversed", &mut self.reversed, "reverse the direction")?;
+//!         Ok(())
+//!     }
+//! }
+//!
+//! #[derive(Debug, Default)]
+//! struct Config {
+//!     name: String,
+//!     left: Motor,
+//!     right: Motor,
+//! }
+//!
+//! impl TypedParameters for Config {
+//!     fn bind(&mut self, binder: &mut Binder) -> Result<(), DynError> {
+//!         binder.field("name", &mut self.name, Descriptor::new("robot name").read_only())?;
+//!         binder.group("left", &mut self.left)?; // "left.max_speed" and "left.reversed"
+//!         binder.group("right", &mut self.right)?;
+//!         Ok(())
+//!     }
+//! }
+//!
+//! let ctx = Context::new().unwrap();
+//! let node = ctx.create_node("typed_param_rs", None, Default::default()).unwrap();
+//! let param_server = node.create_parameter_server().unwrap();
+//!
+//! let config = param_server.params.write().bind(Config::default()).unwrap();
+//!
+//! // The struct is updated when the parameter is set.
+//! param_server
+//!     .params
+//!     .write()
+//!     .set_parameter("left.max_speed".to_string(), Value::F64(2.5), false, None)
+//!     .unwrap();
+//! assert_eq!(config.read().left.max_speed, 2.5);
+//! ```
+
+use super::{Descriptor, Parameter, Parameters, Value, ValueTypeError};
+use crate::error::DynError;
+use parking_lot::RwLock;
+use std::sync::Arc;
+
+/// Struct whose fields are bound to parameters.
+/// See `safe_drive::parameter::typed`.
+pub trait TypedParameters: Send + Sync + 'static {
+    /// Pass every field to `binder.field` or `binder.group`.
+    ///
+    /// This is invoked both when declaring the parameters and when updating the fields,
+    /// so the names and the fields must be the same every time.
+    fn bind(&mut self, binder: &mut Binder) -> Result<(), DynError>;
+}
+
+/// Type of a field bound to a parameter.
+pub trait ParameterField:
+    Clone + Into<Value> + for<'a> TryFrom<&'a Value, Error = ValueTypeError>
+{
+}
+
+impl<T> ParameterField for T where
+    T: Clone + Into<Value> + for<'a> TryFrom<&'a Value, Error = ValueTypeError>
+{
+}
+
+enum Mode<'a> {
+    /// Declare parameters, or load values from overrides.
+    Declare(&'a mut Parameters),
+
+    /// Check whether a value can be converted to the type of the field.
+    Check(&'a str, &'a Value),
+
+    /// Set a value to the field.
+    Update(&'a str, &'a Value),
+}
+
+/// Binder of fields and parameters, which is passed to `TypedParameters::bind`.
+pub struct Binder<'a> {
+    mode: Mode<'a>,
+    prefix: String,
+
+    /// Names of parameters declared in `Mode::Declare`, which are rolled back on failure.
+    /// The previous descriptors are kept if the parameters were declared by overrides.
+    declared: Vec<(String, Option<Descriptor>)>,
+}
+
+impl Binder<'_> {
+    /// Bind a field to a parameter `name`.
+    /// `descriptor` is used when the parameter is declared.
+    ///
+    /// # Errors
+    ///
+    /// When declaring, returns an error if a value given by parameter overrides
+    /// is of a different type or out of the range.
+    /// When updating, returns an error if the value is of a different type.
+    pub fn field<T: ParameterField>(
+        &mut self,
+        name: &str,
+        field: &mut T,
+        descriptor: impl Into<Descriptor>,
+    ) -> Result<(), DynError> {
+        let full_name = format!("{}{name}", self.prefix);
+
+        match &mut self.mode {
+            Mode::Declare(params) => {
+                let mut descriptor = descriptor.into();
+                descriptor.dynamic_typing = false;
+
+                if let Some(param) = params.params.get_mut(&full_name) {
+                    // Declared by parameter overrides.
+                    let value =
+                        T::try_from(&param.value).map_err(|e| format!("{full_name}: {e}"))?;
+
+                    descriptor.validate(&full_name, &param.value)?;
+
+                    let previous = std::mem::replace(&mut param.descriptor, descriptor);
+                    self.declared.push((full_name, Some(previous)));
+                    *field = value;
+                } else {
+                    // The value given by parameter overrides is used if exists.
+                    let param = Parameter {
+                        descriptor,
+                        value: field.clone().into(),
+                    };
+                    params.add_parameter(full_name.clone(), param)?;
+                    self.declared.push((full_name.clone(), None));
+
+                    if let Some(param) = params.params.get(&full_name) {
+                        *field =
+                            T::try_from(&param.value).map_err(|e| format!("{full_name}: {e}"))?;
+                    }
+                }
+            }
+            Mode::Check(target, value) => {
+                if *target == full_name {
+                    T::try_from(value).map_err(|e| format!("{full_name}: {e}"))?;
+                }
+            }
+            Mode::Update(target, value) => {
+                if *target == full_name {
+                    *field = T::try_from(value).map_err(|e| format!("{full_name}: {e}"))?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Bind fields of `group` to parameters whose names begin with `prefix` and `.`.
+    pub fn group<T: TypedParameters>(
+        &mut self,
+        prefix: &str,
+        group: &mut T,
+    ) -> Result<(), DynError> {
+        let len = self.prefix.len();
+        self.prefix.push_str(prefix);
+        self.prefix.push('.');
+
+        let result = group.bind(self);
+
+        self.prefix.truncate(len);
+        result
+    }
+}
+
+/// Fields bound to parameters, which are held by `Parameters`.
+pub(crate) trait Binding: Send + Sync {
+    /// Check whether the proposed values can be set to the fields.
+    fn check(&self, proposed: &[(String, Value)]) -> Result<(), String>;
+
+    /// Set a value to the field bound to `name`.
+    fn update(&self, name: &str, value: &Value);
+}
+
+impl<T: TypedParameters> Binding for Arc<RwLock<T>> {
+    fn check(&self, proposed: &[(String, Value)]) -> Result<(), String> {
+        let mut guard = self.write();
+        for (name, value) in proposed {
+            let mut binder = Binder {
+                mode: Mode::Check(name, value),
+                prefix: String::new(),
+                declared: Vec::new(),
+            };
+            guard.bind(&mut binder).map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    }
+
+    fn update(&self, name: &str, value: &Value) {
+        let mut binder = Binder {
+            mode: Mode::Update(name, value),
+            prefix: String::new(),
+            declared: Vec::new(),
+        };
+        let _ = self.write().bind(&mut binder);
+    }
+}
+
+impl Parameters {
+    /// Declare fields of `value` as parameters, and keep them updated.
+    /// See `safe_drive::parameter::typed`.
+    ///
+    /// Values given by parameter overrides are set to the fields,
+    /// and the other parameters are declared with the values of the fields.
+    ///
+    /// Do not lock `Parameters` while the returned struct is locked,
+    /// because the struct is locked when the parameters are updated.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a field cannot be declared.
+    /// In that case, the parameters declared by this call are undeclared,
+    /// and the descriptors of the parameters declared by overrides are restored.
+    pub fn bind<T: TypedParameters>(&mut self, mut value: T) -> Result<Arc<RwLock<T>>, DynError> {
+        let mut binder = Binder {
+            mode: Mode::Declare(self),
+            prefix: String::new(),
+            declared: Vec::new(),
+        };
+
+        if let Err(e) = value.bind(&mut binder) {
+            let declared = binder.declared;
+            self.rollback(declared);
+            return Err(e);
+        }
+
+        let value = Arc::new(RwLock::new(value));
+        self.bindings.push(Box::new(value.clone()));
+        Ok(value)
+    }
+
+    /// Undo declarations of a failed binding in reverse order.
+    fn rollback(&mut self, declared: Vec<(String, Option<Descriptor>)>) {
+        for (name, previous) in declared.into_iter().rev() {
+            match previous {
+                Some(descriptor) => {
+                    if let Some(param) = self.params.get_mut(&name) {
+                        param.descriptor = descriptor;
+                    }
+                }
+                None => {
+                    if let Some(param) = self.params.remove(&name) {
+                        self.publish_event(&[], &[], &[(&name, &param.value)]);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Add a binding updated when parameters are set.
+    pub(crate) fn add_binding(&mut self, binding: Box<dyn Binding>) {
+        self.bindings.push(binding);
+    }
+}
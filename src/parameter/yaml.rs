@@ -0,0 +1,224 @@
+//! Load and dump parameters in the YAML format of ROS 2.
+//!
+//! The format is same as parameter files given by `--params-file`.
+//!
+//! ```yaml
+//! /my_node:
+//!   ros__parameters:
+//!     my_flag: true
+//!     motor:
+//!       max_speed: 2.5
+//!       ids: [1, 2]
+//! ```
+//!
+//! Node names can contain wildcards such as `/**`.
+//!
+//! # Example
+//!
+//! ```
+//! use safe_drive::{context::Context, parameter::Value};
+//!
+//! let ctx = Context::new().unwrap();
+//! let node = ctx.create_node("param_yaml_rs", None, Default::default()).unwrap();
+//! let param_server = node.create_parameter_server().unwrap();
+//!
+//! param_server
+//!     .params
+//!     .write()
+//!     .set_parameter("motor.max_speed".to_string(), Value::F64(2.5), false, None)
+//!     .unwrap();
+//!
+//! // Snapshot the parameters.
+//! let path = std::env::temp_dir().join("param_yaml_rs.yaml");
+//! param_server.dump_file(&path).unwrap();
+//!
+//! param_server
+//!     .params
+//!     .write()
+//!     .set_parameter("motor.max_speed".to_string(), Value::F64(1.0), false, None)
+//!     .unwrap();
+//!
+//! // Restore the parameters.
+//! param_server.load_file(&path).unwrap();
+//!
+//! let params = param_server.params.read();
+//! let param = params.get_parameter("motor.max_speed").unwrap();
+//! assert_eq!(param.value, Value::F64(2.5));
+//! ```
+
+use super::{Changes, ParameterServer, Value};
+use crate::{error::DynError, rcl};
+use std::{collections::BTreeMap, ffi::CString, fmt::Write, path::Path};
+
+impl ParameterServer {
+    /// Load parameters of this node from a YAML file.
+    ///
+    /// Parameters are validated and set atomically like the `set_parameters_atomically` service,
+    /// and parameters whose values are not changed are ignored,
+    /// so a file dumped by `dump_file` can be loaded even if it contains read only parameters.
+    /// Undeclared parameters are declared if `allow_undeclared_parameters` is enabled,
+    /// and otherwise their values are used when they are declared.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be parsed, or if the parameters cannot be set.
+    pub fn load_file(&self, path: impl AsRef<Path>) -> Result<(), DynError> {
+        let path = path.as_ref();
+        let c_path = CString::new(path.to_str().ok_or("the path is not valid UTF-8")?)?;
+        let fqn = self.node.get_fully_qualified_name()?;
+
+        let loaded = {
+            let mut guard = rcl::MT_UNSAFE_FN.lock();
+            guard
+                .rcl_parse_yaml_file(&c_path, &fqn)
+                .map_err(|e| format!("failed to load {}: {e}", path.display()))?
+        };
+
+        let is_updated = {
+            let mut guard = self.params.write();
+
+            let mut proposed = Vec::new();
+            let mut overrides = Vec::new();
+            for (name, value) in loaded {
+                match guard.params.get(&name) {
+                    Some(param) if param.value == value => (),
+                    Some(_) => proposed.push((name, value)),
+                    None if guard.allow_undeclared => proposed.push((name, value)),
+                    None => overrides.push((name, value)),
+                }
+            }
+
+            guard
+                .check_remote_set(&proposed)
+                .and_then(|_| guard.call_on_set_callbacks(&proposed))?;
+
+            guard.overrides.extend(overrides);
+
+            let mut changes = Changes::default();
+            for (name, value) in proposed {
+                guard.apply_remote(name, value, &mut changes);
+            }
+
+            guard.commit_changes(changes)
+        };
+
+        if is_updated {
+            self.cond_callback.trigger()?;
+        }
+
+        Ok(())
+    }
+
+    /// Dump parameters of this node in the YAML format.
+    ///
+    /// Byte arrays and empty arrays are not dumped
+    /// because they cannot be loaded by the parser of ROS 2.
+    pub fn dump_yaml(&self) -> Result<String, DynError> {
+        let fqn = self.node.get_fully_qualified_name()?;
+
+        let mut root = Tree::default();
+        for (name, param) in self.params.read().params.iter() {
+            if let Some(value) = to_yaml_value(&param.value) {
+                root.insert(name, value);
+            }
+        }
+
+        let mut yaml = format!("{fqn}:\n  ros__parameters:\n");
+        root.write(&mut yaml, 2, "")?;
+        Ok(yaml)
+    }
+
+    /// Dump parameters of this node to a YAML file.
+    /// See `dump_yaml`.
+    pub fn dump_file(&self, path: impl AsRef<Path>) -> Result<(), DynError> {
+        std::fs::write(path, self.dump_yaml()?)?;
+        Ok(())
+    }
+}
+
+/// Parameters nested by `.` in their names.
+#[derive(Default)]
+struct Tree {
+    value: Option<String>,
+    children: BTreeMap<String, Tree>,
+}
+
+impl Tree {
+    fn insert(&mut self, name: &str, value: String) {
+        let mut node = self;
+        for key in name.split('.') {
+            node = node.children.entry(key.to_string()).or_default();
+        }
+        node.value = Some(value);
+    }
+
+    fn write(&self, yaml: &mut String, depth: usize, prefix: &str) -> std::fmt::Result {
+        let indent = "  ".repeat(depth);
+        for (key, child) in self.children.iter() {
+            let key = format!("{prefix}{key}");
+            if let Some(value) = &child.value {
+                writeln!(yaml, "{indent}{key}: {value}")?;
+
+                // A parameter cannot have nested parameters,
+                // so they are written with their full names.
+                child.write(yaml, depth, &format!("{key}."))?;
+            } else {
+                writeln!(yaml, "{indent}{key}:")?;
+                child.write(yaml, depth + 1, "")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn to_yaml_value(value: &Value) -> Option<String> {
+    let value = match value {
+        Value::Bool(x) => x.to_string(),
+        Value::I64(x) => x.to_string(),
+        Value::F64(x) => to_yaml_f64(*x),
+        Value::String(x) => to_yaml_string(x),
+        Value::VecBool(v) if !v.is_empty() => to_yaml_seq(v.iter().map(|x| x.to_string())),
+        Value::VecI64(v) if !v.is_empty() => to_yaml_seq(v.iter().map(|x| x.to_string())),
+        Value::VecF64(v) if !v.is_empty() => to_yaml_seq(v.iter().map(|x| to_yaml_f64(*x))),
+        Value::VecString(v) if !v.is_empty() => to_yaml_seq(v.iter().map(|x| to_yaml_string(x))),
+        _ => return None,
+    };
+    Some(value)
+}
+
+fn to_yaml_f64(x: f64) -> String {
+    if x.is_nan() {
+        ".nan".to_string()
+    } else if x.is_infinite() {
+        if x.is_sign_positive() {
+            ".inf".to_string()
+        } else {
+            "-.inf".to_string()
+        }
+    } else {
+        // Debug formatting keeps the decimal point, such as `1.0`.
+        format!("{x:?}")
+    }
+}
+
+/// Quoted strings are always parsed as strings, such as `"true"` and `"10"`.
+fn to_yaml_string(s: &str) -> String {
+    let mut result = String::with_capacity(s.len() + 2);
+    result.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            _ => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+fn to_yaml_seq(values: impl Iterator<Item = String>) -> String {
+    format!("[{}]", values.collect::<Vec<_>>().join(", "))
+}
@@ -9,14 +9,33 @@ pub mod humble;
 #[cfg(feature = "iron")]
 pub mod iron;
 
+pub mod overriding;
 pub mod policy;
 
 // pub mod policy;
 
-use crate::rcl;
+use crate::{error::RCLResult, rcl};
 use num_traits::{FromPrimitive, ToPrimitive};
 use policy::*;
-use std::time::Duration;
+use std::{ffi::CStr, os::raw::c_char, time::Duration};
+
+/// Unspecified duration, which will use the RMW implementation's default value.
+/// This is equivalent to `RMW_DURATION_UNSPECIFIED`.
+pub const DURATION_UNSPECIFIED: Duration = Duration::ZERO;
+
+/// Infinite duration. This is equivalent to `RMW_DURATION_INFINITE`,
+/// which is `{9223372036, 854775807}`, i.e. `i64::MAX` nanoseconds.
+pub const DURATION_INFINITE: Duration = Duration::new(9223372036, 854775807);
+
+/// Deadline which will match the majority of endpoints while maintaining as strict a policy as possible.
+/// This is equivalent to `RMW_QOS_DEADLINE_BEST_AVAILABLE`.
+#[cfg(feature = "iron")]
+pub const DEADLINE_BEST_AVAILABLE: Duration = Duration::new(9223372036, 854775806);
+
+/// Liveliness lease duration which will match the majority of endpoints while maintaining as strict a policy as possible.
+/// This is equivalent to `RMW_QOS_LIVELINESS_LEASE_DURATION_BEST_AVAILABLE`.
+#[cfg(feature = "iron")]
+pub const LIVELINESS_LEASE_DURATION_BEST_AVAILABLE: Duration = Duration::new(9223372036, 854775806);
 
 /// Represent QoS profile.
 #[derive(Debug, Clone)]
@@ -35,24 +54,24 @@ pub struct Profile {
     pub durability: DurabilityPolicy,
 
     /// The period at which messages are expected to be sent/received.
-    /// RMW_DURATION_UNSPEFICIED will use the RMW implementation's default value,
+    /// `DURATION_UNSPECIFIED` will use the RMW implementation's default value,
     /// which may or may not be infinite.
-    /// RMW_DURATION_INFINITE explicitly states that messages never miss a deadline expectation.
+    /// `DURATION_INFINITE` explicitly states that messages never miss a deadline expectation.
     pub deadline: Duration,
 
     /// The age at which messages are considered expired and no longer valid.
-    /// RMW_DURATION_UNSPEFICIED will use the RMW implementation's default value,
+    /// `DURATION_UNSPECIFIED` will use the RMW implementation's default value,
     /// which may or may not be infinite.
-    /// RMW_DURATION_INFINITE explicitly states that messages do not expire.
+    /// `DURATION_INFINITE` explicitly states that messages do not expire.
     pub lifespan: Duration,
 
     /// Liveliness QoS policy setting.
     pub liveliness: LivelinessPolicy,
 
     /// The time within which the RMW node or publisher must show that it is alive.
-    /// RMW_DURATION_UNSPEFICIED will use the RMW implementation's default value,
+    /// `DURATION_UNSPECIFIED` will use the RMW implementation's default value,
     /// which may or may not be infinite.
-    /// RMW_DURATION_INFINITE explicitly states that liveliness is not enforced.
+    /// `DURATION_INFINITE` explicitly states that liveliness is not enforced.
     pub liveliness_lease_duration: Duration,
 
     /// If true, any ROS specific namespacing conventions will be circumvented.
@@ -93,10 +112,10 @@ impl Profile {
             depth: rcl::RMW_QOS_POLICY_DEPTH_SYSTEM_DEFAULT as usize,
             reliability: ReliabilityPolicy::SystemDefault,
             durability: DurabilityPolicy::SystemDefault,
-            deadline: Duration::ZERO,
-            lifespan: Duration::ZERO,
+            deadline: DURATION_UNSPECIFIED,
+            lifespan: DURATION_UNSPECIFIED,
             liveliness: LivelinessPolicy::SystemDefault,
-            liveliness_lease_duration: Duration::ZERO,
+            liveliness_lease_duration: DURATION_UNSPECIFIED,
             avoid_ros_namespace_conventions: false,
         }
     }
@@ -160,6 +179,262 @@ impl Profile {
             ..Self::common()
         }
     }
+
+    /// Parameter events QoS class
+    /// - History: Keep last,
+    /// - Depth: 1000,
+    /// - Reliability: Reliable,
+    /// - Durability: Volatile,
+    /// - Deadline: Default,
+    /// - Lifespan: Default,
+    /// - Liveliness: System default,
+    /// - Liveliness lease duration: Default,
+    /// - Avoid ros namespace conventions: false
+    pub const fn parameter_events() -> Self {
+        Self {
+            history: HistoryPolicy::KeepLast,
+            depth: 1000,
+            reliability: ReliabilityPolicy::Reliable,
+            durability: DurabilityPolicy::Volatile,
+            ..Self::common()
+        }
+    }
+
+    /// System default QoS class
+    /// - History: System default,
+    /// - Depth: System default,
+    /// - Reliability: System default,
+    /// - Durability: System default,
+    /// - Deadline: Default,
+    /// - Lifespan: Default,
+    /// - Liveliness: System default,
+    /// - Liveliness lease duration: Default,
+    /// - Avoid ros namespace conventions: false
+    pub const fn system_default() -> Self {
+        Self::common()
+    }
+
+    /// Rosout QoS class
+    /// - History: Keep last,
+    /// - Depth: 1000,
+    /// - Reliability: Reliable,
+    /// - Durability: Transient local,
+    /// - Deadline: Default,
+    /// - Lifespan: 10 seconds,
+    /// - Liveliness: System default,
+    /// - Liveliness lease duration: Default,
+    /// - Avoid ros namespace conventions: false
+    pub const fn rosout() -> Self {
+        Self {
+            history: HistoryPolicy::KeepLast,
+            depth: 1000,
+            reliability: ReliabilityPolicy::Reliable,
+            durability: DurabilityPolicy::TransientLocal,
+            lifespan: Duration::from_secs(10),
+            ..Self::common()
+        }
+    }
+
+    /// Clock QoS class
+    /// - History: Keep last,
+    /// - Depth: 1,
+    /// - Reliability: Best effort,
+    /// - Durability: Volatile,
+    /// - Deadline: Default,
+    /// - Lifespan: Default,
+    /// - Liveliness: System default,
+    /// - Liveliness lease duration: Default,
+    /// - Avoid ros namespace conventions: false
+    pub const fn clock() -> Self {
+        Self {
+            history: HistoryPolicy::KeepLast,
+            depth: 1,
+            reliability: ReliabilityPolicy::BestEffort,
+            durability: DurabilityPolicy::Volatile,
+            ..Self::common()
+        }
+    }
+
+    /// Best available QoS class, which matches the majority of endpoints
+    /// while maintaining as strict a policy as possible.
+    /// - History: Keep last,
+    /// - Depth: 10,
+    /// - Reliability: Best available,
+    /// - Durability: Best available,
+    /// - Deadline: Best available,
+    /// - Lifespan: Default,
+    /// - Liveliness: Best available,
+    /// - Liveliness lease duration: Best available,
+    /// - Avoid ros namespace conventions: false
+    #[cfg(feature = "iron")]
+    pub const fn best_available() -> Self {
+        Self {
+            history: HistoryPolicy::KeepLast,
+            depth: 10,
+            reliability: ReliabilityPolicy::BestAvailable,
+            durability: DurabilityPolicy::BestAvailable,
+            deadline: DEADLINE_BEST_AVAILABLE,
+            liveliness: LivelinessPolicy::BestAvailable,
+            liveliness_lease_duration: LIVELINESS_LEASE_DURATION_BEST_AVAILABLE,
+            ..Self::common()
+        }
+    }
+
+    /// Create a profile keeping last `depth` samples.
+    /// The other policies are the same as `Profile::default()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::qos::Profile;
+    ///
+    /// let profile = Profile::keep_last(5).best_effort().transient_local();
+    /// ```
+    pub fn keep_last(depth: usize) -> Self {
+        Self {
+            history: HistoryPolicy::KeepLast,
+            depth,
+            ..Self::default()
+        }
+    }
+
+    /// Create a profile keeping all samples.
+    /// The other policies are the same as `Profile::default()`.
+    pub fn keep_all() -> Self {
+        Self {
+            history: HistoryPolicy::KeepAll,
+            ..Self::default()
+        }
+    }
+
+    /// Set the depth of the history.
+    pub fn depth(mut self, depth: usize) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    /// Set the reliability to `ReliabilityPolicy::Reliable`.
+    pub fn reliable(mut self) -> Self {
+        self.reliability = ReliabilityPolicy::Reliable;
+        self
+    }
+
+    /// Set the reliability to `ReliabilityPolicy::BestEffort`.
+    pub fn best_effort(mut self) -> Self {
+        self.reliability = ReliabilityPolicy::BestEffort;
+        self
+    }
+
+    /// Set the durability to `DurabilityPolicy::TransientLocal`.
+    pub fn transient_local(mut self) -> Self {
+        self.durability = DurabilityPolicy::TransientLocal;
+        self
+    }
+
+    /// Set the durability to `DurabilityPolicy::Volatile`.
+    pub fn volatile(mut self) -> Self {
+        self.durability = DurabilityPolicy::Volatile;
+        self
+    }
+
+    /// Set the deadline.
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    /// Set the lifespan.
+    pub fn lifespan(mut self, lifespan: Duration) -> Self {
+        self.lifespan = lifespan;
+        self
+    }
+
+    /// Set the liveliness policy and the lease duration.
+    pub fn liveliness(mut self, liveliness: LivelinessPolicy, lease_duration: Duration) -> Self {
+        self.liveliness = liveliness;
+        self.liveliness_lease_duration = lease_duration;
+        self
+    }
+
+    /// Set whether ROS specific namespacing conventions are circumvented.
+    pub fn avoid_ros_namespace_conventions(mut self, avoid: bool) -> Self {
+        self.avoid_ros_namespace_conventions = avoid;
+        self
+    }
+
+    /// Check if this profile of a publisher is compatible with a profile of a subscription.
+    ///
+    /// `Compatibility::Warning` is returned if the compatibility cannot be determined,
+    /// for example, because some policies are `SystemDefault`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::qos::{Compatibility, Profile};
+    ///
+    /// let publisher = Profile::default().best_effort();
+    /// let subscription = Profile::default().reliable();
+    ///
+    /// if let Compatibility::Error(reason) = publisher.check_compatible(&subscription).unwrap() {
+    ///     println!("incompatible: {reason}");
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::InvalidArgument` if any arguments are invalid, or
+    /// - `RCLError::Error` if an unspecified error occurs.
+    pub fn check_compatible(&self, subscription: &Profile) -> RCLResult<Compatibility> {
+        let mut compatibility = COMPATIBILITY_OK;
+        let mut reason: [c_char; 2048] = [0; 2048];
+
+        rcl::MTSafeFn::rmw_qos_profile_check_compatible(
+            self.into(),
+            subscription.into(),
+            &mut compatibility,
+            reason.as_mut_ptr(),
+            reason.len(),
+        )?;
+
+        let reason = unsafe { CStr::from_ptr(reason.as_ptr()) }
+            .to_string_lossy()
+            .into_owned();
+
+        Ok(match compatibility {
+            COMPATIBILITY_OK => Compatibility::Ok,
+            COMPATIBILITY_WARNING => Compatibility::Warning(reason),
+            _ => Compatibility::Error(reason),
+        })
+    }
+}
+
+#[cfg(feature = "galactic")]
+const COMPATIBILITY_OK: rcl::rmw_qos_compatibility_type_t =
+    rcl::rmw_qos_compatibility_type_t_RMW_QOS_COMPATIBILITY_OK;
+
+#[cfg(feature = "galactic")]
+const COMPATIBILITY_WARNING: rcl::rmw_qos_compatibility_type_t =
+    rcl::rmw_qos_compatibility_type_t_RMW_QOS_COMPATIBILITY_WARNING;
+
+#[cfg(any(feature = "humble", feature = "iron"))]
+const COMPATIBILITY_OK: rcl::rmw_qos_compatibility_type_t =
+    rcl::rmw_qos_compatibility_type_e_RMW_QOS_COMPATIBILITY_OK;
+
+#[cfg(any(feature = "humble", feature = "iron"))]
+const COMPATIBILITY_WARNING: rcl::rmw_qos_compatibility_type_t =
+    rcl::rmw_qos_compatibility_type_e_RMW_QOS_COMPATIBILITY_WARNING;
+
+/// Result of `Profile::check_compatible`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Compatibility {
+    /// The profiles are compatible.
+    Ok,
+
+    /// The profiles may not be compatible. This contains the reason.
+    Warning(String),
+
+    /// The profiles are not compatible. This contains the reason.
+    Error(String),
 }
 
 impl From<&rcl::rmw_qos_profile_t> for Profile {
@@ -220,3 +495,59 @@ impl From<&Profile> for rcl::rmw_qos_profile_t {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// `(sec, nsec)` of `RMW_DURATION_INFINITE`.
+    const RMW_DURATION_INFINITE: (u64, u64) = (9223372036, 854775807);
+
+    /// `(sec, nsec)` of `RMW_QOS_DEADLINE_BEST_AVAILABLE` and
+    /// `RMW_QOS_LIVELINESS_LEASE_DURATION_BEST_AVAILABLE`.
+    #[cfg(feature = "iron")]
+    const RMW_BEST_AVAILABLE: (u64, u64) = (9223372036, 854775806);
+
+    fn to_rmw(duration: Duration) -> (u64, u64) {
+        let t: rcl::rmw_time_t = duration.into();
+        assert_eq!(Duration::from(t), duration);
+        (t.sec as u64, t.nsec as u64)
+    }
+
+    #[test]
+    fn test_durations() {
+        assert_eq!(to_rmw(DURATION_UNSPECIFIED), (0, 0));
+        assert_eq!(to_rmw(DURATION_INFINITE), RMW_DURATION_INFINITE);
+
+        let profile = Profile::default().deadline(DURATION_INFINITE);
+        let rmw_profile: rcl::rmw_qos_profile_t = (&profile).into();
+        assert_eq!(
+            (
+                rmw_profile.deadline.sec as u64,
+                rmw_profile.deadline.nsec as u64
+            ),
+            RMW_DURATION_INFINITE
+        );
+        assert_eq!(Profile::from(&rmw_profile).deadline, DURATION_INFINITE);
+    }
+
+    #[cfg(feature = "iron")]
+    #[test]
+    fn test_best_available_durations() {
+        assert_eq!(to_rmw(DEADLINE_BEST_AVAILABLE), RMW_BEST_AVAILABLE);
+        assert_eq!(
+            to_rmw(LIVELINESS_LEASE_DURATION_BEST_AVAILABLE),
+            RMW_BEST_AVAILABLE
+        );
+
+        let rmw_profile: rcl::rmw_qos_profile_t = (&Profile::best_available()).into();
+        assert_eq!(
+            to_rmw(Duration::from(rmw_profile.deadline)),
+            RMW_BEST_AVAILABLE
+        );
+        assert_eq!(
+            to_rmw(Duration::from(rmw_profile.liveliness_lease_duration)),
+            RMW_BEST_AVAILABLE
+        );
+    }
+}
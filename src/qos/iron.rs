@@ -33,6 +33,10 @@ pub enum ReliabilityPolicy {
 
     /// Reliability policy has not yet been set
     Unknown = rcl::rmw_qos_reliability_policy_e_RMW_QOS_POLICY_RELIABILITY_UNKNOWN,
+
+    /// Will match the majority of endpoints and use a reliable policy if possible.
+    /// A policy will be chosen at the time of creating a subscription based on any discovered publishers.
+    BestAvailable = rcl::rmw_qos_reliability_policy_e_RMW_QOS_POLICY_RELIABILITY_BEST_AVAILABLE,
 }
 
 /// QoS durability enumerations describing how samples persist
@@ -50,6 +54,10 @@ pub enum DurabilityPolicy {
 
     /// Durability policy has not yet been set
     Unknown = rcl::rmw_qos_durability_policy_e_RMW_QOS_POLICY_DURABILITY_UNKNOWN,
+
+    /// Will match the majority of endpoints and use a transient local policy if possible.
+    /// A policy will be chosen at the time of creating a subscription based on any discovered publishers.
+    BestAvailable = rcl::rmw_qos_durability_policy_e_RMW_QOS_POLICY_DURABILITY_BEST_AVAILABLE,
 }
 
 /// QoS liveliness enumerations that describe a publisher's reporting policy for its alive status.
@@ -76,4 +84,8 @@ pub enum LivelinessPolicy {
 
     /// Liveliness policy has not yet been set
     Unknown = rcl::rmw_qos_liveliness_policy_e_RMW_QOS_POLICY_LIVELINESS_UNKNOWN,
+
+    /// Will match the majority of endpoints and use a manual by topic policy if possible.
+    /// A policy will be chosen at the time of creating a subscription based on any discovered publishers.
+    BestAvailable = rcl::rmw_qos_liveliness_policy_e_RMW_QOS_POLICY_LIVELINESS_BEST_AVAILABLE,
 }
@@ -0,0 +1,340 @@
+//! QoS overriding by parameters.
+//!
+//! QoS profiles of publishers and subscribers can be overridden by parameters
+//! without recompiling nodes, which is compatible with QoS overriding of `rclcpp`.
+//! Overriding is opt-in, and only the policies specified by `QosOverridingOptions`
+//! can be overridden.
+//!
+//! The name of a parameter is as follows.
+//!
+//! ```text
+//! qos_overrides.<fully qualified topic name>.<publisher|subscription>[_<id>].<policy>
+//! ```
+//!
+//! For example, the depth of a publisher of `/chatter` can be overridden as follows.
+//!
+//! ```yaml
+//! /my_node:
+//!   ros__parameters:
+//!     qos_overrides:
+//!       /chatter:
+//!         publisher:
+//!           depth: 100
+//!           reliability: best_effort
+//! ```
+//!
+//! | Policy                            | Type   | Values                                                        |
+//! |-----------------------------------|--------|---------------------------------------------------------------|
+//! | `history`                         | string | `keep_last`, `keep_all`, `system_default`                     |
+//! | `depth`                           | int    | non-negative integer                                          |
+//! | `reliability`                     | string | `reliable`, `best_effort`, `best_available`, `system_default` |
+//! | `durability`                      | string | `volatile`, `transient_local`, `best_available`, `system_default` |
+//! | `deadline`                        | int    | nanoseconds                                                   |
+//! | `lifespan`                        | int    | nanoseconds                                                   |
+//! | `liveliness`                      | string | `automatic`, `manual_by_topic`, `best_available`, `system_default` |
+//! | `liveliness_lease_duration`       | int    | nanoseconds                                                   |
+//! | `avoid_ros_namespace_conventions` | bool   | `true`, `false`                                               |
+//!
+//! `best_available` is available only in Iron.
+//!
+//! If the node has a parameter server, the parameters of the policies which can be overridden
+//! are declared as read-only parameters whose values are the actual policies,
+//! because changing them does not affect created publishers and subscribers.
+//!
+//! # Example
+//!
+//! ```
+//! use safe_drive::{
+//!     context::Context, msg::common_interfaces::std_msgs, qos::overriding::QosOverridingOptions,
+//! };
+//!
+//! let ctx = Context::new().unwrap();
+//! let node = ctx
+//!     .create_node("qos_overriding_rs", None, Default::default())
+//!     .unwrap();
+//!
+//! // History, depth, and reliability can be overridden by parameters.
+//! let publisher = node
+//!     .create_publisher_with_qos_overriding::<std_msgs::msg::UInt64>(
+//!         "qos_overriding_rs_topic",
+//!         None,
+//!         QosOverridingOptions::default(),
+//!     )
+//!     .unwrap();
+//!
+//! let qos = publisher.actual_qos().unwrap();
+//! ```
+
+use super::{policy::*, Profile};
+use crate::{error::DynError, node::Node, parameter::Value};
+use std::{collections::BTreeMap, time::Duration};
+
+/// Kinds of QoS policies which can be overridden.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QosPolicyKind {
+    History,
+    Depth,
+    Reliability,
+    Durability,
+    Deadline,
+    Lifespan,
+    Liveliness,
+    LivelinessLeaseDuration,
+    AvoidRosNamespaceConventions,
+}
+
+impl QosPolicyKind {
+    /// Name of the policy used in parameter names.
+    pub fn name(&self) -> &'static str {
+        match self {
+            QosPolicyKind::History => "history",
+            QosPolicyKind::Depth => "depth",
+            QosPolicyKind::Reliability => "reliability",
+            QosPolicyKind::Durability => "durability",
+            QosPolicyKind::Deadline => "deadline",
+            QosPolicyKind::Lifespan => "lifespan",
+            QosPolicyKind::Liveliness => "liveliness",
+            QosPolicyKind::LivelinessLeaseDuration => "liveliness_lease_duration",
+            QosPolicyKind::AvoidRosNamespaceConventions => "avoid_ros_namespace_conventions",
+        }
+    }
+}
+
+/// Options of QoS overriding.
+#[derive(Debug, Clone)]
+pub struct QosOverridingOptions {
+    /// Policies which can be overridden.
+    /// The default is history, depth, and reliability.
+    pub policies: Vec<QosPolicyKind>,
+
+    /// ID to distinguish several publishers or subscribers of the same topic in a node.
+    /// If this is `Some(id)`, `publisher_<id>` or `subscription_<id>` is used in parameter names.
+    pub id: Option<String>,
+}
+
+impl Default for QosOverridingOptions {
+    fn default() -> Self {
+        QosOverridingOptions {
+            policies: vec![
+                QosPolicyKind::History,
+                QosPolicyKind::Depth,
+                QosPolicyKind::Reliability,
+            ],
+            id: None,
+        }
+    }
+}
+
+impl QosOverridingOptions {
+    /// Options which allow all policies to be overridden.
+    pub fn all_policies() -> Self {
+        QosOverridingOptions {
+            policies: vec![
+                QosPolicyKind::History,
+                QosPolicyKind::Depth,
+                QosPolicyKind::Reliability,
+                QosPolicyKind::Durability,
+                QosPolicyKind::Deadline,
+                QosPolicyKind::Lifespan,
+                QosPolicyKind::Liveliness,
+                QosPolicyKind::LivelinessLeaseDuration,
+                QosPolicyKind::AvoidRosNamespaceConventions,
+            ],
+            id: None,
+        }
+    }
+
+    /// Set the ID.
+    pub fn id(mut self, id: &str) -> Self {
+        self.id = Some(id.to_string());
+        self
+    }
+}
+
+/// Kinds of entities whose QoS profiles are overridden.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum EntityKind {
+    Publisher,
+    Subscription,
+}
+
+/// Return `qos` overridden by parameters of `node`.
+/// `topic_name` must be resolved by `Node::resolve_topic_name`.
+pub(crate) fn apply_overrides(
+    node: &Node,
+    topic_name: &str,
+    entity: EntityKind,
+    mut qos: Profile,
+    options: &QosOverridingOptions,
+) -> Result<Profile, DynError> {
+    let prefix = prefix(topic_name, entity, options.id.as_deref());
+    let params = filter_overrides(node.parameter_overrides()?, &prefix);
+
+    for kind in options.policies.iter() {
+        if let Some(value) = params.get(kind.name()) {
+            apply_policy(&mut qos, *kind, value)?;
+        }
+    }
+
+    if let Some(node_params) = node.get_parameters() {
+        let mut guard = node_params.write();
+        for kind in options.policies.iter() {
+            let name = format!("{prefix}{}", kind.name());
+            guard.declare_read_only(name, policy_value(&qos, *kind));
+        }
+    }
+
+    Ok(qos)
+}
+
+/// Return `qos_overrides.<topic_name>.<entity>[_<id>].`.
+fn prefix(topic_name: &str, entity: EntityKind, id: Option<&str>) -> String {
+    let entity = match entity {
+        EntityKind::Publisher => "publisher",
+        EntityKind::Subscription => "subscription",
+    };
+
+    if let Some(id) = id {
+        format!("qos_overrides.{topic_name}.{entity}_{id}.")
+    } else {
+        format!("qos_overrides.{topic_name}.{entity}.")
+    }
+}
+
+/// Return parameters starting with `prefix` whose keys are policy names.
+fn filter_overrides(params: BTreeMap<String, Value>, prefix: &str) -> BTreeMap<String, Value> {
+    params
+        .into_iter()
+        .filter_map(|(k, v)| Some((k.strip_prefix(prefix)?.to_string(), v)))
+        .collect()
+}
+
+/// Return the value of a policy of `qos` as a parameter.
+fn policy_value(qos: &Profile, kind: QosPolicyKind) -> Value {
+    let s = match kind {
+        QosPolicyKind::History => match qos.history {
+            HistoryPolicy::KeepLast => "keep_last",
+            HistoryPolicy::KeepAll => "keep_all",
+            HistoryPolicy::SystemDefault => "system_default",
+            HistoryPolicy::Unknown => "unknown",
+        },
+        QosPolicyKind::Depth => return Value::I64(qos.depth.try_into().unwrap_or(i64::MAX)),
+        QosPolicyKind::Reliability => match qos.reliability {
+            ReliabilityPolicy::Reliable => "reliable",
+            ReliabilityPolicy::BestEffort => "best_effort",
+            #[cfg(feature = "iron")]
+            ReliabilityPolicy::BestAvailable => "best_available",
+            ReliabilityPolicy::SystemDefault => "system_default",
+            ReliabilityPolicy::Unknown => "unknown",
+        },
+        QosPolicyKind::Durability => match qos.durability {
+            DurabilityPolicy::Volatile => "volatile",
+            DurabilityPolicy::TransientLocal => "transient_local",
+            #[cfg(feature = "iron")]
+            DurabilityPolicy::BestAvailable => "best_available",
+            DurabilityPolicy::SystemDefault => "system_default",
+            DurabilityPolicy::Unknown => "unknown",
+        },
+        QosPolicyKind::Deadline => return from_duration(qos.deadline),
+        QosPolicyKind::Lifespan => return from_duration(qos.lifespan),
+        QosPolicyKind::Liveliness => match qos.liveliness {
+            LivelinessPolicy::Automatic => "automatic",
+            LivelinessPolicy::ManualByTopic => "manual_by_topic",
+            #[cfg(feature = "iron")]
+            LivelinessPolicy::BestAvailable => "best_available",
+            LivelinessPolicy::SystemDefault => "system_default",
+            LivelinessPolicy::Unknown => "unknown",
+        },
+        QosPolicyKind::LivelinessLeaseDuration => {
+            return from_duration(qos.liveliness_lease_duration)
+        }
+        QosPolicyKind::AvoidRosNamespaceConventions => {
+            return Value::Bool(qos.avoid_ros_namespace_conventions)
+        }
+    };
+
+    Value::String(s.to_string())
+}
+
+fn from_duration(dur: Duration) -> Value {
+    Value::I64(dur.as_nanos().try_into().unwrap_or(i64::MAX))
+}
+
+fn apply_policy(qos: &mut Profile, kind: QosPolicyKind, value: &Value) -> Result<(), DynError> {
+    match (kind, value) {
+        (QosPolicyKind::History, Value::String(s)) => {
+            qos.history = match s.as_str() {
+                "keep_last" => HistoryPolicy::KeepLast,
+                "keep_all" => HistoryPolicy::KeepAll,
+                "system_default" => HistoryPolicy::SystemDefault,
+                _ => return Err(invalid_value(kind, s)),
+            };
+        }
+        (QosPolicyKind::Depth, Value::I64(n)) => {
+            qos.depth = (*n).try_into().map_err(|_| invalid_value(kind, n))?;
+        }
+        (QosPolicyKind::Reliability, Value::String(s)) => {
+            qos.reliability = match s.as_str() {
+                "reliable" => ReliabilityPolicy::Reliable,
+                "best_effort" => ReliabilityPolicy::BestEffort,
+                #[cfg(feature = "iron")]
+                "best_available" => ReliabilityPolicy::BestAvailable,
+                "system_default" => ReliabilityPolicy::SystemDefault,
+                _ => return Err(invalid_value(kind, s)),
+            };
+        }
+        (QosPolicyKind::Durability, Value::String(s)) => {
+            qos.durability = match s.as_str() {
+                "volatile" => DurabilityPolicy::Volatile,
+                "transient_local" => DurabilityPolicy::TransientLocal,
+                #[cfg(feature = "iron")]
+                "best_available" => DurabilityPolicy::BestAvailable,
+                "system_default" => DurabilityPolicy::SystemDefault,
+                _ => return Err(invalid_value(kind, s)),
+            };
+        }
+        (QosPolicyKind::Deadline, Value::I64(n)) => {
+            qos.deadline = to_duration(kind, *n)?;
+        }
+        (QosPolicyKind::Lifespan, Value::I64(n)) => {
+            qos.lifespan = to_duration(kind, *n)?;
+        }
+        (QosPolicyKind::Liveliness, Value::String(s)) => {
+            qos.liveliness = match s.as_str() {
+                "automatic" => LivelinessPolicy::Automatic,
+                "manual_by_topic" => LivelinessPolicy::ManualByTopic,
+                #[cfg(feature = "iron")]
+                "best_available" => LivelinessPolicy::BestAvailable,
+                "system_default" => LivelinessPolicy::SystemDefault,
+                _ => return Err(invalid_value(kind, s)),
+            };
+        }
+        (QosPolicyKind::LivelinessLeaseDuration, Value::I64(n)) => {
+            qos.liveliness_lease_duration = to_duration(kind, *n)?;
+        }
+        (QosPolicyKind::AvoidRosNamespaceConventions, Value::Bool(b)) => {
+            qos.avoid_ros_namespace_conventions = *b;
+        }
+        (_, value) => {
+            let msg = format!(
+                "QoS overriding: invalid type of {}: {}",
+                kind.name(),
+                value.type_name()
+            );
+            return Err(msg.into());
+        }
+    }
+
+    Ok(())
+}
+
+fn to_duration(kind: QosPolicyKind, nanosec: i64) -> Result<Duration, DynError> {
+    let nanosec: u64 = nanosec
+        .try_into()
+        .map_err(|_| invalid_value(kind, nanosec))?;
+    Ok(Duration::from_nanos(nanosec))
+}
+
+fn invalid_value(kind: QosPolicyKind, value: impl std::fmt::Display) -> DynError {
+    format!("QoS overriding: invalid value of {}: {value}", kind.name()).into()
+}
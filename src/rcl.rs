@@ -21,7 +21,10 @@ use regex::Regex;
 #[cfg(feature = "galactic")]
 mod galactic;
 
-use std::{collections::BTreeMap, ffi::CStr};
+use std::{
+    collections::BTreeMap,
+    ffi::{CStr, CString},
+};
 
 #[cfg(feature = "galactic")]
 pub(crate) use galactic::*;
@@ -109,6 +112,16 @@ impl MTUnsafeFn {
         ret_val_to_err(unsafe { self::rcl_init_options_init(init_options, allocator) })
     }
 
+    pub fn rcl_init_options_set_domain_id(
+        &self,
+        init_options: *mut rcl_init_options_t,
+        domain_id: usize,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe {
+            self::rcl_init_options_set_domain_id(init_options, domain_id as _)
+        })
+    }
+
     pub fn rcl_init_options_fini(&self, init_options: *mut rcl_init_options_t) -> RCLResult<()> {
         ret_val_to_err(unsafe { self::rcl_init_options_fini(init_options) })
     }
@@ -128,6 +141,16 @@ impl MTUnsafeFn {
         ret_val_to_err(unsafe { self::rcl_node_fini(node) })
     }
 
+    pub fn rcl_parse_arguments(
+        &self,
+        argc: ::std::os::raw::c_int,
+        argv: *const *const ::std::os::raw::c_char,
+        allocator: rcl_allocator_t,
+        args_output: *mut rcl_arguments_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe { self::rcl_parse_arguments(argc, argv, allocator, args_output) })
+    }
+
     pub fn rcl_node_options_fini(&self, options: *mut rcl_node_options_t) -> RCLResult<()> {
         ret_val_to_err(unsafe { self::rcl_node_options_fini(options) })
     }
@@ -416,6 +439,62 @@ impl MTUnsafeFn {
         ret_val_to_err(unsafe { self::rcl_ros_clock_fini(clock) })
     }
 
+    pub fn rcl_clock_init(
+        &self,
+        clock_type: rcl_clock_type_t,
+        clock: *mut rcl_clock_t,
+        allocator: *mut rcl_allocator_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe { self::rcl_clock_init(clock_type, clock, allocator) })
+    }
+
+    pub fn rcl_clock_fini(&self, clock: *mut rcl_clock_t) -> RCLResult<()> {
+        ret_val_to_err(unsafe { self::rcl_clock_fini(clock) })
+    }
+
+    pub fn rcl_enable_ros_time_override(&self, clock: *mut rcl_clock_t) -> RCLResult<()> {
+        ret_val_to_err(unsafe { self::rcl_enable_ros_time_override(clock) })
+    }
+
+    pub fn rcl_disable_ros_time_override(&self, clock: *mut rcl_clock_t) -> RCLResult<()> {
+        ret_val_to_err(unsafe { self::rcl_disable_ros_time_override(clock) })
+    }
+
+    pub fn rcl_is_enabled_ros_time_override(&self, clock: *mut rcl_clock_t) -> RCLResult<bool> {
+        let mut is_enabled = false;
+        ret_val_to_err(unsafe { self::rcl_is_enabled_ros_time_override(clock, &mut is_enabled) })?;
+        Ok(is_enabled)
+    }
+
+    pub fn rcl_set_ros_time_override(
+        &self,
+        clock: *mut rcl_clock_t,
+        time_value: rcl_time_point_value_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe { self::rcl_set_ros_time_override(clock, time_value) })
+    }
+
+    pub fn rcl_clock_add_jump_callback(
+        &self,
+        clock: *mut rcl_clock_t,
+        threshold: rcl_jump_threshold_t,
+        callback: rcl_jump_callback_t,
+        user_data: *mut ::std::os::raw::c_void,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe {
+            self::rcl_clock_add_jump_callback(clock, threshold, callback, user_data)
+        })
+    }
+
+    pub fn rcl_clock_remove_jump_callback(
+        &self,
+        clock: *mut rcl_clock_t,
+        callback: rcl_jump_callback_t,
+        user_data: *mut ::std::os::raw::c_void,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe { self::rcl_clock_remove_jump_callback(clock, callback, user_data) })
+    }
+
     pub fn rcl_return_loaned_message_from_subscription(
         &self,
         subscription: *const rcl_subscription_t,
@@ -766,6 +845,239 @@ impl MTUnsafeFn {
         ret_val_to_err(unsafe { self::rcl_logging_fini() })
     }
 
+    pub fn rcl_get_node_names(
+        &self,
+        node: *const rcl_node_t,
+        allocator: rcl_allocator_t,
+        node_names: *mut rcutils_string_array_t,
+        node_namespaces: *mut rcutils_string_array_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe {
+            self::rcl_get_node_names(node, allocator, node_names, node_namespaces)
+        })
+    }
+
+    pub fn rcl_get_topic_names_and_types(
+        &self,
+        node: *const rcl_node_t,
+        allocator: *mut rcl_allocator_t,
+        no_demangle: bool,
+        topic_names_and_types: *mut rcl_names_and_types_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe {
+            self::rcl_get_topic_names_and_types(node, allocator, no_demangle, topic_names_and_types)
+        })
+    }
+
+    pub fn rcl_get_service_names_and_types(
+        &self,
+        node: *const rcl_node_t,
+        allocator: *mut rcl_allocator_t,
+        service_names_and_types: *mut rcl_names_and_types_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe {
+            self::rcl_get_service_names_and_types(node, allocator, service_names_and_types)
+        })
+    }
+
+    pub fn rcl_get_publisher_names_and_types_by_node(
+        &self,
+        node: *const rcl_node_t,
+        allocator: *mut rcl_allocator_t,
+        no_demangle: bool,
+        node_name: *const ::std::os::raw::c_char,
+        node_namespace: *const ::std::os::raw::c_char,
+        topic_names_and_types: *mut rcl_names_and_types_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe {
+            self::rcl_get_publisher_names_and_types_by_node(
+                node,
+                allocator,
+                no_demangle,
+                node_name,
+                node_namespace,
+                topic_names_and_types,
+            )
+        })
+    }
+
+    pub fn rcl_get_subscriber_names_and_types_by_node(
+        &self,
+        node: *const rcl_node_t,
+        allocator: *mut rcl_allocator_t,
+        no_demangle: bool,
+        node_name: *const ::std::os::raw::c_char,
+        node_namespace: *const ::std::os::raw::c_char,
+        topic_names_and_types: *mut rcl_names_and_types_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe {
+            self::rcl_get_subscriber_names_and_types_by_node(
+                node,
+                allocator,
+                no_demangle,
+                node_name,
+                node_namespace,
+                topic_names_and_types,
+            )
+        })
+    }
+
+    pub fn rcl_get_service_names_and_types_by_node(
+        &self,
+        node: *const rcl_node_t,
+        allocator: *mut rcl_allocator_t,
+        node_name: *const ::std::os::raw::c_char,
+        node_namespace: *const ::std::os::raw::c_char,
+        service_names_and_types: *mut rcl_names_and_types_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe {
+            self::rcl_get_service_names_and_types_by_node(
+                node,
+                allocator,
+                node_name,
+                node_namespace,
+                service_names_and_types,
+            )
+        })
+    }
+
+    pub fn rcl_get_client_names_and_types_by_node(
+        &self,
+        node: *const rcl_node_t,
+        allocator: *mut rcl_allocator_t,
+        node_name: *const ::std::os::raw::c_char,
+        node_namespace: *const ::std::os::raw::c_char,
+        service_names_and_types: *mut rcl_names_and_types_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe {
+            self::rcl_get_client_names_and_types_by_node(
+                node,
+                allocator,
+                node_name,
+                node_namespace,
+                service_names_and_types,
+            )
+        })
+    }
+
+    pub fn rcl_get_publishers_info_by_topic(
+        &self,
+        node: *const rcl_node_t,
+        allocator: *mut rcutils_allocator_t,
+        topic_name: *const ::std::os::raw::c_char,
+        no_mangle: bool,
+        publishers_info: *mut rcl_topic_endpoint_info_array_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe {
+            self::rcl_get_publishers_info_by_topic(
+                node,
+                allocator,
+                topic_name,
+                no_mangle,
+                publishers_info,
+            )
+        })
+    }
+
+    pub fn rcl_get_subscriptions_info_by_topic(
+        &self,
+        node: *const rcl_node_t,
+        allocator: *mut rcutils_allocator_t,
+        topic_name: *const ::std::os::raw::c_char,
+        no_mangle: bool,
+        subscriptions_info: *mut rcl_topic_endpoint_info_array_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe {
+            self::rcl_get_subscriptions_info_by_topic(
+                node,
+                allocator,
+                topic_name,
+                no_mangle,
+                subscriptions_info,
+            )
+        })
+    }
+
+    pub fn rcl_count_publishers(
+        &self,
+        node: *const rcl_node_t,
+        topic_name: *const ::std::os::raw::c_char,
+    ) -> RCLResult<usize> {
+        let mut count = 0;
+        ret_val_to_err(unsafe {
+            self::rcl_count_publishers(node, topic_name, &mut count as *mut _ as _)
+        })?;
+        Ok(count)
+    }
+
+    pub fn rcl_count_subscribers(
+        &self,
+        node: *const rcl_node_t,
+        topic_name: *const ::std::os::raw::c_char,
+    ) -> RCLResult<usize> {
+        let mut count = 0;
+        ret_val_to_err(unsafe {
+            self::rcl_count_subscribers(node, topic_name, &mut count as *mut _ as _)
+        })?;
+        Ok(count)
+    }
+
+    pub fn rcl_names_and_types_fini(
+        &self,
+        names_and_types: *mut rcl_names_and_types_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe { self::rcl_names_and_types_fini(names_and_types) })
+    }
+
+    pub fn rmw_topic_endpoint_info_array_fini(
+        &self,
+        info_array: *mut rcl_topic_endpoint_info_array_t,
+        allocator: *mut rcutils_allocator_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe { self::rmw_topic_endpoint_info_array_fini(info_array, allocator) })
+    }
+
+    pub fn rcutils_string_array_fini(
+        &self,
+        string_array: *mut rcutils_string_array_t,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe { self::rcutils_string_array_fini(string_array) })
+    }
+
+    /// Expand and remap a topic or service name, and return the fully qualified name.
+    pub fn rcl_node_resolve_name(
+        &self,
+        node: *const rcl_node_t,
+        input_name: &str,
+        is_service: bool,
+        only_expand: bool,
+    ) -> RCLResult<String> {
+        let input_name = CString::new(input_name).map_err(|_| RCLError::InvalidArgument)?;
+        let allocator = crate::get_allocator();
+        let mut output_name: *mut ::std::os::raw::c_char = std::ptr::null_mut();
+
+        ret_val_to_err(unsafe {
+            self::rcl_node_resolve_name(
+                node,
+                input_name.as_ptr(),
+                allocator,
+                is_service,
+                only_expand,
+                &mut output_name,
+            )
+        })?;
+
+        let result = unsafe { CStr::from_ptr(output_name) }
+            .to_string_lossy()
+            .into_owned();
+
+        if let Some(deallocate) = allocator.deallocate {
+            unsafe { deallocate(output_name as *mut _, allocator.state) };
+        }
+
+        Ok(result)
+    }
+
     /// This implementation is based on [rclcpp rclcpp::parameter_map_from](https://github.com/ros2/rclcpp/blob/rolling/rclcpp/src/rclcpp/parameter_map.cpp)
     pub fn parameter_map(
         &mut self,
@@ -783,53 +1095,78 @@ impl MTUnsafeFn {
             return Ok(params_map);
         }
 
-        let node_names = unsafe {
-            std::slice::from_raw_parts(
-                (*(*params.as_ref())).node_names,
-                (*(*params.as_ref())).num_nodes.try_into().unwrap(),
-            )
+        params_to_map(*params, node_fqn, &mut params_map);
+        Ok(params_map)
+    }
+
+    /// Parse a parameter file in the YAML format,
+    /// and get parameters of the node whose name matches `node_fqn`.
+    pub fn rcl_parse_yaml_file(
+        &mut self,
+        file_path: &CStr,
+        node_fqn: &str,
+    ) -> RCLResult<BTreeMap<String, Value>> {
+        let params =
+            unsafe { self::rcl_yaml_node_struct_init(self::rcutils_get_default_allocator()) };
+        if params.is_null() {
+            return Err(RCLError::BadAlloc);
+        }
+
+        let result = if unsafe { self::rcl_parse_yaml_file(file_path.as_ptr(), params) } {
+            let mut params_map = BTreeMap::new();
+            params_to_map(params, node_fqn, &mut params_map);
+            Ok(params_map)
+        } else {
+            Err(RCLError::Error)
         };
 
-        let node_params = unsafe {
-            std::slice::from_raw_parts(
-                (*(*params.as_ref())).params,
-                (*(*params.as_ref())).num_nodes.try_into().unwrap(),
+        unsafe { self::rcl_yaml_node_struct_fini(params) };
+        result
+    }
+}
+
+/// Insert parameters of the node whose name matches `node_fqn` into `params_map`.
+fn params_to_map(
+    params: *const rcl_params_t,
+    node_fqn: &str,
+    params_map: &mut BTreeMap<String, Value>,
+) {
+    let node_names = unsafe {
+        std::slice::from_raw_parts(
+            (*params).node_names,
+            (*params).num_nodes.try_into().unwrap(),
+        )
+    };
+
+    let node_params = unsafe {
+        std::slice::from_raw_parts((*params).params, (*params).num_nodes.try_into().unwrap())
+    };
+
+    for (nn, np) in node_names.iter().zip(node_params) {
+        let c_node_name = unsafe { CStr::from_ptr(*nn) };
+        let Ok(node_name) = c_node_name.to_str() else {
+            continue;
+        };
+        let fqn = if node_name.chars().next().unwrap_or('/').eq(&'/') {
+            node_name.to_owned()
+        } else {
+            format!("/{}", node_name)
+        };
+        if !is_node_name_matched(&fqn, node_fqn) {
+            continue;
+        }
+        let (param_names, param_values) = unsafe {
+            (
+                std::slice::from_raw_parts(np.parameter_names, np.num_params.try_into().unwrap()),
+                std::slice::from_raw_parts(np.parameter_values, np.num_params.try_into().unwrap()),
             )
         };
-
-        for (nn, np) in node_names.iter().zip(node_params) {
-            let c_node_name = unsafe { CStr::from_ptr(*nn) };
-            let Ok(node_name) = c_node_name.to_str() else {
-                continue;
-            };
-            let fqn = if node_name.chars().next().unwrap_or('/').eq(&'/') {
-                node_name.to_owned()
-            } else {
-                format!("/{}", node_name)
-            };
-            if !is_node_name_matched(&fqn, node_fqn) {
-                continue;
-            }
-            let (param_names, param_values) = unsafe {
-                (
-                    std::slice::from_raw_parts(
-                        np.parameter_names,
-                        np.num_params.try_into().unwrap(),
-                    ),
-                    std::slice::from_raw_parts(
-                        np.parameter_values,
-                        np.num_params.try_into().unwrap(),
-                    ),
-                )
-            };
-            for (s, v) in param_names.iter().zip(param_values) {
-                let s = unsafe { CStr::from_ptr(*s) };
-                if let Ok(key) = s.to_str() {
-                    params_map.insert(key.to_owned(), v.into());
-                }
+        for (s, v) in param_names.iter().zip(param_values) {
+            let s = unsafe { CStr::from_ptr(*s) };
+            if let Ok(key) = s.to_str() {
+                params_map.insert(key.to_owned(), v.into());
             }
         }
-        Ok(params_map)
     }
 }
 
@@ -864,6 +1201,29 @@ impl MTUnsafeLogFn {
 pub(crate) struct MTSafeFn;
 
 impl MTSafeFn {
+    pub fn rcutils_get_zero_initialized_string_array() -> rcutils_string_array_t {
+        unsafe { self::rcutils_get_zero_initialized_string_array() }
+    }
+
+    pub fn rmw_get_zero_initialized_names_and_types() -> rmw_names_and_types_t {
+        unsafe { self::rmw_get_zero_initialized_names_and_types() }
+    }
+
+    pub fn rmw_get_zero_initialized_topic_endpoint_info_array() -> rmw_topic_endpoint_info_array_t {
+        unsafe { self::rmw_get_zero_initialized_topic_endpoint_info_array() }
+    }
+
+    pub fn rcl_node_get_graph_guard_condition(
+        node: *const rcl_node_t,
+    ) -> RCLResult<*const rcl_guard_condition_t> {
+        let cond = unsafe { self::rcl_node_get_graph_guard_condition(node) };
+        if cond.is_null() {
+            Err(RCLError::NodeInvalid)
+        } else {
+            Ok(cond)
+        }
+    }
+
     pub fn rcl_get_zero_initialized_context() -> rcl_context_t {
         unsafe { self::rcl_get_zero_initialized_context() }
     }
@@ -888,6 +1248,29 @@ impl MTSafeFn {
         unsafe { self::rcl_get_zero_initialized_node() }
     }
 
+    pub fn rcl_context_get_domain_id(context: *mut rcl_context_t) -> RCLResult<usize> {
+        let mut domain_id = 0;
+        ret_val_to_err(unsafe {
+            self::rcl_context_get_domain_id(context, &mut domain_id as *mut _ as _)
+        })?;
+        Ok(domain_id)
+    }
+
+    pub fn rcl_node_get_options(node: *const rcl_node_t) -> RCLResult<*const rcl_node_options_t> {
+        let options = unsafe { self::rcl_node_get_options(node) };
+        if options.is_null() {
+            Err(RCLError::NodeInvalid)
+        } else {
+            Ok(options)
+        }
+    }
+
+    pub fn rcl_arguments_get_count_unparsed_ros(
+        args: *const rcl_arguments_t,
+    ) -> ::std::os::raw::c_int {
+        unsafe { self::rcl_arguments_get_count_unparsed_ros(args) }
+    }
+
     pub fn rcl_node_get_default_options() -> rcl_node_options_t {
         unsafe { self::rcl_node_get_default_options() }
     }
@@ -922,6 +1305,58 @@ impl MTSafeFn {
         })
     }
 
+    pub fn rcl_publisher_get_topic_name(
+        publisher: *const rcl_publisher_t,
+    ) -> *const ::std::os::raw::c_char {
+        unsafe { self::rcl_publisher_get_topic_name(publisher) }
+    }
+
+    pub fn rcl_publisher_get_subscription_count(
+        publisher: *const rcl_publisher_t,
+    ) -> RCLResult<usize> {
+        let mut count = 0;
+        ret_val_to_err(unsafe {
+            self::rcl_publisher_get_subscription_count(publisher, &mut count as *mut _ as _)
+        })?;
+        Ok(count)
+    }
+
+    pub fn rcl_subscription_get_topic_name(
+        subscription: *const rcl_subscription_t,
+    ) -> *const ::std::os::raw::c_char {
+        unsafe { self::rcl_subscription_get_topic_name(subscription) }
+    }
+
+    pub fn rcl_publisher_get_actual_qos(
+        publisher: *const rcl_publisher_t,
+    ) -> *const rmw_qos_profile_t {
+        unsafe { self::rcl_publisher_get_actual_qos(publisher) }
+    }
+
+    pub fn rcl_subscription_get_actual_qos(
+        subscription: *const rcl_subscription_t,
+    ) -> *const rmw_qos_profile_t {
+        unsafe { self::rcl_subscription_get_actual_qos(subscription) }
+    }
+
+    pub fn rmw_qos_profile_check_compatible(
+        publisher_profile: rmw_qos_profile_t,
+        subscription_profile: rmw_qos_profile_t,
+        compatibility: *mut rmw_qos_compatibility_type_t,
+        reason: *mut ::std::os::raw::c_char,
+        reason_size: usize,
+    ) -> RCLResult<()> {
+        ret_val_to_err(unsafe {
+            self::rmw_qos_profile_check_compatible(
+                publisher_profile,
+                subscription_profile,
+                compatibility,
+                reason,
+                reason_size as _,
+            )
+        })
+    }
+
     pub fn rmw_get_default_publisher_options() -> rmw_publisher_options_t {
         unsafe { self::rmw_get_default_publisher_options() }
     }
@@ -1069,6 +1504,94 @@ impl MTSafeFn {
         unsafe { self::rcl_action_get_zero_initialized_cancel_response() }
     }
 
+    /// Validate a topic name which may be relative or contain substitutions.
+    /// Return the reason and the index of the invalid character if invalid.
+    pub fn rcl_validate_topic_name(topic_name: &CStr) -> RCLResult<Option<(String, usize)>> {
+        validate_name(
+            topic_name,
+            RCL_TOPIC_NAME_VALID,
+            self::rcl_validate_topic_name,
+            self::rcl_topic_name_validation_result_string,
+        )
+    }
+
+    /// Validate a fully qualified topic name.
+    /// Return the reason and the index of the invalid character if invalid.
+    pub fn rmw_validate_full_topic_name(topic_name: &CStr) -> RCLResult<Option<(String, usize)>> {
+        validate_name(
+            topic_name,
+            RMW_TOPIC_VALID,
+            self::rmw_validate_full_topic_name,
+            self::rmw_full_topic_name_validation_result_string,
+        )
+    }
+
+    /// Validate a namespace.
+    /// Return the reason and the index of the invalid character if invalid.
+    pub fn rmw_validate_namespace(namespace: &CStr) -> RCLResult<Option<(String, usize)>> {
+        validate_name(
+            namespace,
+            RMW_NAMESPACE_VALID,
+            self::rmw_validate_namespace,
+            self::rmw_namespace_validation_result_string,
+        )
+    }
+
+    /// Validate a node name.
+    /// Return the reason and the index of the invalid character if invalid.
+    pub fn rmw_validate_node_name(node_name: &CStr) -> RCLResult<Option<(String, usize)>> {
+        validate_name(
+            node_name,
+            RMW_NODE_NAME_VALID,
+            self::rmw_validate_node_name,
+            self::rmw_node_name_validation_result_string,
+        )
+    }
+
+    /// Expand a topic or service name to the fully qualified name
+    /// by using the default substitutions.
+    /// Remap rules are not applied.
+    pub fn rcl_expand_topic_name(
+        input_name: &CStr,
+        node_name: &CStr,
+        node_namespace: &CStr,
+    ) -> RCLResult<String> {
+        let allocator = crate::get_allocator();
+
+        let mut substitutions = unsafe { self::rcutils_get_zero_initialized_string_map() };
+        ret_val_to_err(unsafe { self::rcutils_string_map_init(&mut substitutions, 0, allocator) })?;
+
+        let mut output_name: *mut ::std::os::raw::c_char = std::ptr::null_mut();
+        let result = ret_val_to_err(unsafe {
+            self::rcl_get_default_topic_name_substitutions(&mut substitutions)
+        })
+        .and_then(|_| {
+            ret_val_to_err(unsafe {
+                self::rcl_expand_topic_name(
+                    input_name.as_ptr(),
+                    node_name.as_ptr(),
+                    node_namespace.as_ptr(),
+                    &substitutions,
+                    allocator,
+                    &mut output_name,
+                )
+            })
+        });
+
+        unsafe { self::rcutils_string_map_fini(&mut substitutions) };
+        result?;
+
+        let expanded = unsafe { CStr::from_ptr(output_name) }
+            .to_string_lossy()
+            .into_owned();
+
+        if let Some(deallocate) = allocator.deallocate {
+            unsafe { deallocate(output_name as *mut _, allocator.state) };
+        }
+
+        Ok(expanded)
+    }
+
     pub fn rcl_node_get_name(node: *const rcl_node_t) -> RCLResult<String> {
         let name_c = unsafe { self::rcl_node_get_name(node) };
         if name_c.is_null() {
@@ -1103,3 +1626,44 @@ impl MTSafeFn {
             .to_owned())
     }
 }
+
+type ValidateFn = unsafe extern "C" fn(
+    *const ::std::os::raw::c_char,
+    *mut ::std::os::raw::c_int,
+    *mut size_t,
+) -> rcl_ret_t;
+
+type ValidationResultStringFn =
+    unsafe extern "C" fn(::std::os::raw::c_int) -> *const ::std::os::raw::c_char;
+
+fn validate_name(
+    name: &CStr,
+    valid: u32,
+    validate: ValidateFn,
+    result_string: ValidationResultStringFn,
+) -> RCLResult<Option<(String, usize)>> {
+    let mut result = 0;
+    let mut invalid_index = 0;
+    ret_val_to_err(unsafe {
+        validate(
+            name.as_ptr(),
+            &mut result,
+            &mut invalid_index as *mut usize as *mut _,
+        )
+    })?;
+
+    if result == valid as _ {
+        return Ok(None);
+    }
+
+    let reason = unsafe { result_string(result) };
+    let reason = if reason.is_null() {
+        "unknown reason".to_string()
+    } else {
+        unsafe { CStr::from_ptr(reason) }
+            .to_string_lossy()
+            .into_owned()
+    };
+
+    Ok(Some((reason, invalid_index)))
+}
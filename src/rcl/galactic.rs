@@ -28072,3 +28072,82 @@ fn bindgen_test_layout___va_list_tag() {
 pub struct __locale_data {
     pub _address: u8,
 }
+pub const RCL_TOPIC_NAME_VALID: u32 = 0;
+pub const RMW_TOPIC_VALID: u32 = 0;
+pub const RMW_NAMESPACE_VALID: u32 = 0;
+pub const RMW_NODE_NAME_VALID: u32 = 0;
+extern "C" {
+    pub fn rcl_expand_topic_name(
+        input_topic_name: *const ::std::os::raw::c_char,
+        node_name: *const ::std::os::raw::c_char,
+        node_namespace: *const ::std::os::raw::c_char,
+        substitutions: *const rcutils_string_map_t,
+        allocator: rcl_allocator_t,
+        output_topic_name: *mut *mut ::std::os::raw::c_char,
+    ) -> rcl_ret_t;
+}
+extern "C" {
+    pub fn rcl_get_default_topic_name_substitutions(
+        string_map: *mut rcutils_string_map_t,
+    ) -> rcl_ret_t;
+}
+extern "C" {
+    pub fn rcl_validate_topic_name(
+        topic_name: *const ::std::os::raw::c_char,
+        validation_result: *mut ::std::os::raw::c_int,
+        invalid_index: *mut size_t,
+    ) -> rcl_ret_t;
+}
+extern "C" {
+    pub fn rcl_topic_name_validation_result_string(
+        validation_result: ::std::os::raw::c_int,
+    ) -> *const ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn rmw_validate_full_topic_name(
+        topic_name: *const ::std::os::raw::c_char,
+        validation_result: *mut ::std::os::raw::c_int,
+        invalid_index: *mut size_t,
+    ) -> rmw_ret_t;
+}
+extern "C" {
+    pub fn rmw_full_topic_name_validation_result_string(
+        validation_result: ::std::os::raw::c_int,
+    ) -> *const ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn rmw_validate_namespace(
+        namespace_: *const ::std::os::raw::c_char,
+        validation_result: *mut ::std::os::raw::c_int,
+        invalid_index: *mut size_t,
+    ) -> rmw_ret_t;
+}
+extern "C" {
+    pub fn rmw_namespace_validation_result_string(
+        validation_result: ::std::os::raw::c_int,
+    ) -> *const ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn rmw_validate_node_name(
+        node_name: *const ::std::os::raw::c_char,
+        validation_result: *mut ::std::os::raw::c_int,
+        invalid_index: *mut size_t,
+    ) -> rmw_ret_t;
+}
+extern "C" {
+    pub fn rmw_node_name_validation_result_string(
+        validation_result: ::std::os::raw::c_int,
+    ) -> *const ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn rcl_yaml_node_struct_init(allocator: rcutils_allocator_t) -> *mut rcl_params_t;
+}
+extern "C" {
+    pub fn rcl_yaml_node_struct_fini(params_st: *mut rcl_params_t);
+}
+extern "C" {
+    pub fn rcl_parse_yaml_file(
+        file_path: *const ::std::os::raw::c_char,
+        params_st: *mut rcl_params_t,
+    ) -> bool;
+}
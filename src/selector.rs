@@ -53,13 +53,18 @@
 //! }
 //! ```
 
-use self::guard_condition::{GuardCondition, RCLGuardCondition};
+use self::{
+    clock_timer::ClockTimers,
+    guard_condition::{GuardCondition, RCLGuardCondition},
+};
 use crate::{
     action::{self, handle::GoalHandle, update_goal_status, GoalStatus, SendGoalServiceRequest},
+    clock::Clock,
     context::Context,
     delta_list::DeltaList,
     error::{DynError, RCLActionResult, RCLError, RCLResult},
     get_allocator,
+    graph::GraphWatcher,
     logger::{pr_error_in, pr_fatal_in, Logger},
     msg::{
         interfaces::action_msgs::{
@@ -68,7 +73,11 @@ use crate::{
         },
         ActionMsg, GetUUID, ServiceMsg, TypeSupport,
     },
-    parameter::{ParameterServer, Parameters},
+    node::Node,
+    parameter::{
+        event::{Callbacks, ParameterEventHandler},
+        ParameterServer, Parameters,
+    },
     rcl::{
         self, bindgen_action_msgs__msg__GoalInfo, bindgen_action_msgs__msg__GoalInfo__Sequence,
         rcl_action_client_t, rcl_action_server_t,
@@ -79,7 +88,11 @@ use crate::{
         Header,
     },
     signal_handler::{self, Signaled},
-    topic::subscriber::{RCLSubscription, Subscriber, TakenMsg},
+    topic::{
+        intra_process::{IntraProcessMsg, IntraProcessSubscriber},
+        subscriber::{RCLSubscription, Subscriber, TakenMsg},
+        synchronizer::{Subscribers, Synchronizer},
+    },
     PhantomUnsend, PhantomUnsync, RecvResult, ST,
 };
 use std::{
@@ -101,6 +114,8 @@ use crate::helper::statistics::{SerializableTimeStat, TimeStatistics};
 pub(crate) mod async_selector;
 pub(crate) mod guard_condition;
 
+mod clock_timer;
+
 type ServerCallback<T> =
     Box<dyn FnMut(<T as ServiceMsg>::Request, Header) -> <T as ServiceMsg>::Response>;
 type ParameterCallback = Box<dyn FnMut(&mut Parameters, BTreeSet<String>)>;
@@ -174,6 +189,39 @@ struct EntitySize {
     events: rcl::size_t,
 }
 
+/// Keys of entities registered in a selector.
+/// This is used to remove entities registered in a period,
+/// such as entities of a component.
+#[derive(Default)]
+pub(crate) struct EntityKeys {
+    subscriptions: BTreeSet<*const rcl::rcl_subscription_t>,
+    services: BTreeSet<*const rcl::rcl_service_t>,
+    clients: BTreeSet<*const rcl::rcl_client_t>,
+    action_servers: BTreeSet<*const rcl::rcl_action_server_t>,
+    action_clients: BTreeSet<*const rcl::rcl_action_client_t>,
+    cond: BTreeSet<*const RCLGuardCondition>,
+    timers: BTreeSet<u64>,
+}
+
+impl EntityKeys {
+    /// Keys which are in `self` but not in `other`.
+    pub(crate) fn difference(&self, other: &EntityKeys) -> EntityKeys {
+        fn diff<K: Ord + Copy>(a: &BTreeSet<K>, b: &BTreeSet<K>) -> BTreeSet<K> {
+            a.difference(b).copied().collect()
+        }
+
+        EntityKeys {
+            subscriptions: diff(&self.subscriptions, &other.subscriptions),
+            services: diff(&self.services, &other.services),
+            clients: diff(&self.clients, &other.clients),
+            action_servers: diff(&self.action_servers, &other.action_servers),
+            action_clients: diff(&self.action_clients, &other.action_clients),
+            cond: diff(&self.cond, &other.cond),
+            timers: diff(&self.timers, &other.timers),
+        }
+    }
+}
+
 /// Selector invokes callback functions associated with subscribers, services, timers, or condition variables.
 /// Selector cannot send to another thread and shared by multiple threads.
 /// So, use this for single threaded execution.
@@ -190,6 +238,7 @@ pub struct Selector {
     param_server: Option<ParameterServer>,
     timer: DeltaList<(ConditionHandler<TimerType>, u64)>,
     base_time: SystemTime,
+    clock_timers: BTreeMap<*const Mutex<Clock>, ClockTimers>,
     signal_cond: GuardCondition,
     wait_set: rcl::rcl_wait_set_t,
     services: BTreeMap<*const rcl::rcl_service_t, ConditionHandler<Arc<ServerData>>>,
@@ -197,7 +246,9 @@ pub struct Selector {
     subscriptions: BTreeMap<*const rcl::rcl_subscription_t, ConditionHandler<Arc<RCLSubscription>>>,
     action_servers: BTreeMap<*const rcl::rcl_action_server_t, ActionServerConditionHandler>,
     action_clients: BTreeMap<*const rcl::rcl_action_client_t, ActionClientConditionHandler>,
-    cond: BTreeMap<*const rcl::rcl_guard_condition_t, ConditionHandler<Arc<RCLGuardCondition>>>,
+    /// Guard conditions are identified by `GuardCondition` instead of `rcl_guard_condition_t`,
+    /// because the graph guard condition of a node is shared by graph watchers.
+    cond: BTreeMap<*const RCLGuardCondition, ConditionHandler<Arc<RCLGuardCondition>>>,
     timer_ids: BTreeSet<u64>,
     timer_id: u64,
     context: Arc<Context>,
@@ -241,6 +292,7 @@ impl Selector {
             param_server: None,
             timer: DeltaList::Nil,
             base_time: SystemTime::now(),
+            clock_timers: Default::default(),
             signal_cond: signal_cond.clone(),
             wait_set,
             subscriptions: Default::default(),
@@ -261,6 +313,7 @@ impl Selector {
         };
 
         selector.add_guard_condition(&signal_cond, None, false);
+        selector.context.register_selector(&signal_cond);
         signal_handler::register_guard_condition(signal_cond);
 
         Ok(selector)
@@ -306,6 +359,8 @@ impl Selector {
 
     /// Register a subscriber with callback function.
     /// The callback function will be invoked when arriving data.
+    /// If topic statistics of the subscriber are enabled,
+    /// they are published by a wall timer of the selector.
     ///
     /// # Error
     ///
@@ -337,6 +392,7 @@ impl Selector {
     ) -> bool {
         let sub = subscriber.subscription.clone();
         let context_ptr = subscriber.subscription.node.context.as_ptr();
+        let statistics = subscriber.statistics.as_ref().map(|s| s.publisher());
 
         #[cfg(feature = "statistics")]
         let symbol = {
@@ -387,6 +443,24 @@ impl Selector {
                 );
             }
 
+            // publish topic statistics while the subscriber is alive
+            if let Some(statistics) = statistics {
+                let period = statistics.period();
+                let statistics = Arc::downgrade(&statistics);
+                self.add_timer_inner(
+                    period,
+                    Box::new(move || {
+                        if let Some(statistics) = statistics.upgrade() {
+                            statistics.publish();
+                            CallbackResult::Ok
+                        } else {
+                            CallbackResult::Remove
+                        }
+                    }),
+                    TimerType::WallTimer(Rc::new("topic_statistics".to_string()), period),
+                );
+            }
+
             self.add_rcl_subscription(sub, Some(Box::new(f)), false);
             true
         } else {
@@ -394,6 +468,171 @@ impl Selector {
         }
     }
 
+    /// Register an intra-process subscriber with callback function.
+    /// The callback function will be invoked when arriving data
+    /// via either `rcl` or the intra-process queue.
+    ///
+    /// # Error
+    ///
+    /// If a selector takes a subscriber created by a different context,
+    /// `add_intra_process_subscriber()` must fail.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::{
+    ///     msg::common_interfaces::std_msgs, node::Node, selector::Selector,
+    ///     topic::intra_process::IntraProcessMsg,
+    /// };
+    /// use std::sync::Arc;
+    ///
+    /// fn add_new_subscriber(selector: &mut Selector, node: Arc<Node>) {
+    ///     // Create an intra-process subscriber.
+    ///     let subscriber = node.create_subscriber_intra_process("topic_name", None,
+    ///     ).unwrap();
+    ///
+    ///     // Add the subscriber with a callback function.
+    ///     selector.add_intra_process_subscriber(
+    ///         subscriber,
+    ///         Box::new(|msg: IntraProcessMsg<std_msgs::msg::Bool>| /* some tasks */ ()), // Callback function.
+    ///     );
+    /// }
+    /// ```
+    pub fn add_intra_process_subscriber<T: TypeSupport + Send + Sync + 'static>(
+        &mut self,
+        subscriber: IntraProcessSubscriber<T>,
+        mut handler: Box<dyn FnMut(IntraProcessMsg<T>)>,
+    ) -> bool {
+        let sub = subscriber.subscriber.subscription.clone();
+        let cond = subscriber.buffer.cond.clone();
+
+        if self.context.as_ptr() != sub.node.context.as_ptr() {
+            return false;
+        }
+
+        let f = move || {
+            let start = SystemTime::now();
+            let dur = Duration::from_millis(1);
+
+            loop {
+                match subscriber.try_recv() {
+                    RecvResult::Ok(n) => {
+                        handler(n);
+                    }
+                    RecvResult::RetryLater(()) => return CallbackResult::Ok,
+                    RecvResult::Err(e) => {
+                        let logger = Logger::new("safe_drive");
+                        pr_error_in!(logger, "failed try_recv() of subscriber: {}", e);
+                        return CallbackResult::Remove;
+                    }
+                }
+
+                if let Ok(t) = start.elapsed() {
+                    if t > dur {
+                        return CallbackResult::Ok;
+                    }
+                } else {
+                    return CallbackResult::Ok;
+                }
+            }
+        };
+
+        // Messages arrive via both rcl and the intra-process queue.
+        let f = Rc::new(RefCell::new(f));
+        let g = f.clone();
+        self.add_guard_condition(&cond, Some(Box::new(move || (g.borrow_mut())())), false);
+        self.add_rcl_subscription(sub, Some(Box::new(move || (f.borrow_mut())())), false);
+
+        true
+    }
+
+    /// Register a synchronizer with callback function.
+    /// The callback function will be invoked when a tuple of messages is matched.
+    ///
+    /// # Error
+    ///
+    /// If a selector takes a synchronizer whose subscribers are created by a different context,
+    /// `add_synchronizer()` must fail.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::{
+    ///     msg::common_interfaces::sensor_msgs,
+    ///     node::Node,
+    ///     selector::Selector,
+    ///     topic::synchronizer::{Policy, Synchronizer},
+    /// };
+    /// use std::{sync::Arc, time::Duration};
+    ///
+    /// fn add_new_synchronizer(selector: &mut Selector, node: Arc<Node>) {
+    ///     // Create subscribers.
+    ///     let image = node
+    ///         .create_subscriber::<sensor_msgs::msg::Image>("image", None)
+    ///         .unwrap();
+    ///     let info = node
+    ///         .create_subscriber::<sensor_msgs::msg::CameraInfo>("camera_info", None)
+    ///         .unwrap();
+    ///
+    ///     // Create a synchronizer.
+    ///     let sync = Synchronizer::new(
+    ///         (image, info),
+    ///         Policy::ApproximateTime(Duration::from_millis(10)),
+    ///         10,
+    ///     );
+    ///
+    ///     // Add the synchronizer with a callback function.
+    ///     selector.add_synchronizer(
+    ///         sync,
+    ///         Box::new(|(image, info)| /* some tasks */ ()), // Callback function.
+    ///     );
+    /// }
+    /// ```
+    pub fn add_synchronizer<S: Subscribers + 'static>(
+        &mut self,
+        synchronizer: Synchronizer<S>,
+        handler: Box<dyn FnMut(S::Output)>,
+    ) -> bool {
+        let subscriptions: Vec<_> = synchronizer
+            .inputs
+            .iter()
+            .map(|input| input.subscription().clone())
+            .collect();
+
+        if subscriptions
+            .iter()
+            .any(|sub| self.context.as_ptr() != sub.node.context.as_ptr())
+        {
+            return false;
+        }
+
+        let shared = Rc::new(RefCell::new((synchronizer, handler)));
+
+        for sub in subscriptions {
+            let shared = shared.clone();
+            let f = move || {
+                let mut guard = shared.borrow_mut();
+                let (synchronizer, handler) = &mut *guard;
+
+                loop {
+                    match synchronizer.try_recv() {
+                        RecvResult::Ok(msgs) => handler(msgs),
+                        RecvResult::RetryLater(()) => return CallbackResult::Ok,
+                        RecvResult::Err(e) => {
+                            let logger = Logger::new("safe_drive");
+                            pr_error_in!(logger, "failed try_recv() of synchronizer: {}", e);
+                            return CallbackResult::Remove;
+                        }
+                    }
+                }
+            };
+
+            self.add_rcl_subscription(sub, Some(Box::new(f)), false);
+        }
+
+        true
+    }
+
     pub(crate) fn add_rcl_subscription(
         &mut self,
         subscription: Arc<RCLSubscription>,
@@ -430,6 +669,79 @@ impl Selector {
         self.param_server = Some(param_server);
     }
 
+    /// Register a handler of `/parameter_events`.
+    /// Callbacks of the handler will be invoked when receiving events.
+    /// See `safe_drive::parameter::event`.
+    ///
+    /// # Error
+    ///
+    /// If a selector takes a handler created by a different context or
+    /// the handler has been already added, `add_parameter_event_handler()` must fail.
+    pub fn add_parameter_event_handler(&mut self, handler: &mut ParameterEventHandler) -> bool {
+        if self.context.as_ptr() != handler.node.context.as_ptr() {
+            return false;
+        }
+
+        let Some(subscriber) = handler.take_subscriber() else {
+            return false;
+        };
+
+        let callbacks = handler.get_callbacks();
+        self.add_subscriber(
+            subscriber,
+            Box::new(move |event| Callbacks::dispatch(&callbacks, &event)),
+        )
+    }
+
+    /// Register a graph watcher with callback function.
+    /// The callback function will be invoked with the watched node
+    /// when the ROS graph changes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::{logger::Logger, node::Node, pr_info, selector::Selector};
+    /// use std::sync::Arc;
+    ///
+    /// fn add_graph_watcher(selector: &mut Selector, node: Arc<Node>) {
+    ///     let watcher = node.create_graph_watcher().unwrap();
+    ///     let logger = Logger::new("graph_watcher_rs");
+    ///
+    ///     selector.add_graph_watcher(
+    ///         watcher,
+    ///         Box::new(move |node| {
+    ///             let names = node.get_node_names().unwrap_or_default();
+    ///             pr_info!(logger, "nodes: {:?}", names);
+    ///         }),
+    ///     );
+    /// }
+    /// ```
+    ///
+    /// # Error
+    ///
+    /// If a selector takes a watcher created by a different context,
+    /// `add_graph_watcher()` must fail.
+    pub fn add_graph_watcher(
+        &mut self,
+        watcher: GraphWatcher,
+        mut handler: Box<dyn FnMut(&Node)>,
+    ) -> bool {
+        if self.context.as_ptr() != watcher.get_node().context.as_ptr() {
+            return false;
+        }
+
+        let cond = watcher.cond.clone();
+        self.add_guard_condition(
+            &cond,
+            Some(Box::new(move || {
+                handler(watcher.get_node());
+                CallbackResult::Ok
+            })),
+            false,
+        );
+        true
+    }
+
     /// Register a subscriber with callback function.
     /// The callback function will be invoked when arriving data.
     ///
@@ -892,7 +1204,7 @@ impl Selector {
         is_once: bool,
     ) {
         self.cond.insert(
-            cond.cond.cond.as_ref(),
+            Arc::as_ptr(&cond.cond),
             ConditionHandler {
                 event: cond.cond.clone(),
                 handler,
@@ -902,7 +1214,7 @@ impl Selector {
     }
 
     pub(crate) fn remove_guard_condition(&mut self, cond: &GuardCondition) {
-        self.cond.remove(&(cond.cond.cond.as_ref() as *const _));
+        self.cond.remove(&Arc::as_ptr(&cond.cond));
     }
 
     pub(crate) fn remove_rcl_subscription(&mut self, subscription: &Arc<RCLSubscription>) {
@@ -918,6 +1230,44 @@ impl Selector {
         self.clients.remove(&(&client.client as *const _));
     }
 
+    /// Get the keys of all registered entities.
+    pub(crate) fn entity_keys(&self) -> EntityKeys {
+        EntityKeys {
+            subscriptions: self.subscriptions.keys().copied().collect(),
+            services: self.services.keys().copied().collect(),
+            clients: self.clients.keys().copied().collect(),
+            action_servers: self.action_servers.keys().copied().collect(),
+            action_clients: self.action_clients.keys().copied().collect(),
+            cond: self.cond.keys().copied().collect(),
+            timers: self.timer_ids.clone(),
+        }
+    }
+
+    /// Remove entities by their keys.
+    pub(crate) fn remove_entities(&mut self, keys: &EntityKeys) {
+        for k in keys.subscriptions.iter() {
+            self.subscriptions.remove(k);
+        }
+        for k in keys.services.iter() {
+            self.services.remove(k);
+        }
+        for k in keys.clients.iter() {
+            self.clients.remove(k);
+        }
+        for k in keys.action_servers.iter() {
+            self.action_servers.remove(k);
+        }
+        for k in keys.action_clients.iter() {
+            self.action_clients.remove(k);
+        }
+        for k in keys.cond.iter() {
+            self.cond.remove(k);
+        }
+        for id in keys.timers.iter() {
+            self.remove_timer(*id);
+        }
+    }
+
     /// Add a timer.
     /// The `handler` is called after `t` seconds later.
     /// The `handler` is called just once.
@@ -996,12 +1346,80 @@ impl Selector {
         )
     }
 
+    /// Add a timer driven by the time of `clock`, such as the clock got by `Node::get_clock`.
+    /// The `handler` is called every `t` of the clock.
+    ///
+    /// While the ROS time is overridden by `/clock`, see `safe_drive::time_source`,
+    /// the timer follows the simulated time and pauses when `/clock` stops.
+    /// If the time jumps forward, missed periods are skipped and the `handler` is called once.
+    /// If the time jumps backward or the override is enabled or disabled,
+    /// the remaining time of the timer is kept.
+    ///
+    /// # Return Value
+    ///
+    /// The identifier of the timer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::{error::DynError, node::Node, selector::Selector};
+    /// use std::{sync::Arc, time::Duration};
+    ///
+    /// fn add_new_ros_timer(selector: &mut Selector, node: Arc<Node>) -> Result<(), DynError> {
+    ///     // Add a timer of the ROS time.
+    ///     selector.add_ros_timer(
+    ///         &node.get_clock(),
+    ///         Duration::from_millis(100),
+    ///         Box::new(|| /* some tasks */ ()), // Callback function.
+    ///     )?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn add_ros_timer(
+        &mut self,
+        clock: &Arc<Mutex<Clock>>,
+        t: Duration,
+        handler: Box<dyn FnMut()>,
+    ) -> Result<u64, DynError> {
+        let key = Arc::as_ptr(clock);
+        if !self.clock_timers.contains_key(&key) {
+            let timers = ClockTimers::new(clock.clone(), self.context.clone())?;
+            self.clock_timers.insert(key, timers);
+        }
+
+        let timer_id = self.new_timer_id();
+        let result = self
+            .clock_timers
+            .get_mut(&key)
+            .unwrap()
+            .insert(timer_id, t, handler);
+
+        if let Err(e) = result {
+            self.remove_timer(timer_id);
+            return Err(e.into());
+        }
+
+        Ok(timer_id)
+    }
+
     fn add_timer_inner(
         &mut self,
         t: Duration,
         handler: Box<dyn FnMut() -> CallbackResult>,
         timer_type: TimerType,
     ) -> u64 {
+        let timer_id = self.new_timer_id();
+        self.add_timer_with_id(t, handler, timer_type, timer_id);
+        timer_id
+    }
+
+    fn add_timer_with_id(
+        &mut self,
+        t: Duration,
+        handler: Box<dyn FnMut() -> CallbackResult>,
+        timer_type: TimerType,
+        timer_id: u64,
+    ) {
         let now_time = SystemTime::now();
 
         if self.timer.is_empty() {
@@ -1024,8 +1442,6 @@ impl Selector {
             t
         };
 
-        let timer_id = self.new_timer_id();
-
         self.timer.insert(
             delta,
             (
@@ -1037,8 +1453,6 @@ impl Selector {
                 timer_id,
             ),
         );
-
-        timer_id
     }
 
     /// Wait events and invoke registered callback functions.
@@ -1085,6 +1499,11 @@ impl Selector {
 
     pub fn remove_timer(&mut self, id: u64) {
         self.timer.filter(|e| e.1 != id);
+        self.clock_timers.retain(|_, timers| {
+            timers.remove(id);
+            !timers.is_empty()
+        });
+        self.timer_ids.remove(&id);
     }
 
     fn new_timer_id(&mut self) -> u64 {
@@ -1139,11 +1558,17 @@ impl Selector {
                 )?;
             }
 
-            // set guard conditions
-            for (_, h) in self.cond.iter() {
+            // set guard conditions, each of which is added only once
+            let conds: BTreeSet<_> = self.cond.values().map(|h| h.event.as_ptr()).collect();
+            for cond in conds {
+                guard.rcl_wait_set_add_guard_condition(&mut self.wait_set, cond, null_mut())?;
+            }
+
+            // set guard conditions of clocks
+            for (_, h) in self.clock_timers.iter() {
                 guard.rcl_wait_set_add_guard_condition(
                     &mut self.wait_set,
-                    h.event.cond.as_ref(),
+                    h.cond().cond.as_ptr(),
                     null_mut(),
                 )?;
             }
@@ -1184,6 +1609,11 @@ impl Selector {
         // notify timers
         self.notify_timer();
 
+        // notify timers of clocks
+        for (_, h) in self.clock_timers.iter_mut() {
+            h.notify()?;
+        }
+
         #[cfg(feature = "statistics")]
         {
             // notify subscriptions
@@ -1199,8 +1629,7 @@ impl Selector {
             notify(target, self.wait_set.clients, time_stat);
 
             // notify guard conditions
-            let (target, time_stat) = (&mut self.cond, &mut self.time_stat);
-            notify(target, self.wait_set.guard_conditions, time_stat);
+            notify_guard_conditions(&mut self.cond, &self.wait_set);
         }
 
         #[cfg(not(feature = "statistics"))]
@@ -1215,7 +1644,7 @@ impl Selector {
             notify(&mut self.clients, self.wait_set.clients);
 
             // notify guard conditions
-            notify(&mut self.cond, self.wait_set.guard_conditions);
+            notify_guard_conditions(&mut self.cond, &self.wait_set);
 
             notify_action_server(&mut self.action_servers, &self.wait_set)?;
             notify_action_client(&mut self.action_clients, &self.wait_set)?;
@@ -1228,35 +1657,30 @@ impl Selector {
         if signal_handler::is_halt() {
             return Err(Signaled.into());
         }
+        self.context.check_ok()?;
 
-        if self.timer.is_empty() {
-            #[cfg(feature = "rcl_stat")]
-            let wait_start = SystemTime::now();
-
-            // wait forever until arriving events
-            rcl::MTSafeFn::rcl_wait(&mut self.wait_set, -1)?;
-
-            #[cfg(feature = "rcl_stat")]
-            {
-                if let Ok(wait_time) = wait_start.elapsed() {
-                    self.time_stat.rcl_wait.add(wait_time);
-                }
-            }
-        } else {
-            // insert timer
+        // the nearest deadline of timers
+        let mut timeout = self.timer.front().map(|(head_delta, _)| {
             let now_time = SystemTime::now();
-            let head_delta = *self.timer.front().unwrap().0;
-            let timeout = if self.base_time <= now_time {
+            if self.base_time <= now_time {
                 let diff = now_time.duration_since(self.base_time).unwrap();
-                if diff < head_delta {
-                    head_delta - diff
+                if diff < *head_delta {
+                    *head_delta - diff
                 } else {
                     Duration::ZERO
                 }
             } else {
-                head_delta + self.base_time.duration_since(now_time).unwrap()
-            };
+                *head_delta + self.base_time.duration_since(now_time).unwrap()
+            }
+        });
+
+        for (_, h) in self.clock_timers.iter() {
+            if let Some(t) = h.timeout()? {
+                timeout = Some(timeout.map_or(t, |timeout| timeout.min(t)));
+            }
+        }
 
+        if let Some(timeout) = timeout {
             let timeout_nanos = timeout.as_nanos();
             let timeout_nanos = if timeout_nanos > i64::max_value() as u128 {
                 let logger = Logger::new("safe_drive");
@@ -1284,11 +1708,25 @@ impl Selector {
                     }
                 }
             }
+        } else {
+            #[cfg(feature = "rcl_stat")]
+            let wait_start = SystemTime::now();
+
+            // wait forever until arriving events
+            rcl::MTSafeFn::rcl_wait(&mut self.wait_set, -1)?;
+
+            #[cfg(feature = "rcl_stat")]
+            {
+                if let Ok(wait_time) = wait_start.elapsed() {
+                    self.time_stat.rcl_wait.add(wait_time);
+                }
+            }
         }
 
         if signal_handler::is_halt() {
             return Err(Signaled.into());
         }
+        self.context.check_ok()?;
 
         Ok(())
     }
@@ -1310,16 +1748,19 @@ impl Selector {
                         #[cfg(feature = "statistics")]
                         let start = std::time::SystemTime::now();
 
-                        handler(); // invoke the callback function
+                        let result = handler(); // invoke the callback function
 
-                        // register the wall timer again.
-                        if let TimerType::WallTimer(name, dur) = &head.1 .0.event {
+                        // register the wall timer again unless it is removed.
+                        if let (TimerType::WallTimer(name, dur), CallbackResult::Ok) =
+                            (&head.1 .0.event, result)
+                        {
                             let elapsed = now_time.elapsed().unwrap();
 
+                            let timer_id = head.1 .1;
                             if let Some(dur) = dur.checked_sub(elapsed) {
-                                reload.push((name.clone(), dur, handler));
+                                reload.push((name.clone(), dur, handler, timer_id));
                             } else {
-                                reload.push((name.clone(), Duration::ZERO, handler));
+                                reload.push((name.clone(), Duration::ZERO, handler, timer_id));
                             }
 
                             #[cfg(feature = "statistics")]
@@ -1338,9 +1779,9 @@ impl Selector {
             }
         }
 
-        // reload wall timers
-        for (name, dur, handler) in reload {
-            self.add_timer_inner(dur, handler, TimerType::WallTimer(name, dur));
+        // reload wall timers, which keep their identifiers
+        for (name, dur, handler, timer_id) in reload {
+            self.add_timer_with_id(dur, handler, TimerType::WallTimer(name, dur), timer_id);
         }
     }
 
@@ -1390,6 +1831,7 @@ impl Selector {
                 + action_server_subscriptions_size * n_servers
                 + action_client_subscriptions_size * n_clients,
             guard_condititons: self.cond.len() as rcl::size_t
+                + self.clock_timers.len() as rcl::size_t
                 + action_server_guard_conditions_size * n_servers
                 + action_client_guard_conditions_size * n_clients,
             timers: action_server_timers_size * n_servers + action_client_timers_size * n_clients,
@@ -1474,6 +1916,35 @@ fn notify<K, V>(m: &mut BTreeMap<*const K, ConditionHandler<V>>, array: *const *
     }
 }
 
+/// Invoke handlers of guard conditions triggered in the waitset.
+/// A guard condition may be shared by several handlers.
+fn notify_guard_conditions(
+    m: &mut BTreeMap<*const RCLGuardCondition, ConditionHandler<Arc<RCLGuardCondition>>>,
+    wait_set: &rcl::rcl_wait_set_t,
+) {
+    let mut triggered = BTreeSet::new();
+    for i in 0..wait_set.size_of_guard_conditions {
+        let p = unsafe { *wait_set.guard_conditions.add(i) };
+        if !p.is_null() {
+            triggered.insert(p);
+        }
+    }
+
+    m.retain(|_, h| {
+        if !triggered.contains(&h.event.as_ptr()) {
+            return true;
+        }
+
+        let mut is_rm = false;
+        if let Some(hdl) = &mut h.handler {
+            if hdl() == CallbackResult::Remove {
+                is_rm = true;
+            }
+        }
+        !(h.is_once || is_rm)
+    });
+}
+
 /// Scan the waitset to see if there are any updates for action servers.
 fn notify_action_server(
     m: &mut BTreeMap<*const rcl_action_server_t, ActionServerConditionHandler>,
@@ -1596,7 +2067,7 @@ fn notify_action_client(
 #[cfg(test)]
 mod test {
     use crate::{context::Context, error::DynError, selector::CallbackResult};
-    use std::thread;
+    use std::{cell::Cell, rc::Rc, thread, time::Duration};
 
     #[test]
     fn test_guard_condition() -> Result<(), DynError> {
@@ -1624,4 +2095,32 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_remove_wall_timer() -> Result<(), DynError> {
+        let ctx = Context::new()?;
+        let mut selector = super::Selector::new(ctx)?;
+
+        // a wall timer returning `Remove` is not reloaded
+        let count = Rc::new(Cell::new(0));
+        let count_cloned = count.clone();
+        let dur = Duration::from_millis(10);
+        selector.add_timer_inner(
+            dur,
+            Box::new(move || {
+                count_cloned.set(count_cloned.get() + 1);
+                CallbackResult::Remove
+            }),
+            super::TimerType::WallTimer(Rc::new("test_remove_wall_timer".to_string()), dur),
+        );
+
+        selector.wait()?;
+        assert_eq!(count.get(), 1);
+        assert!(selector.timer_ids.is_empty());
+
+        selector.wait_timeout(Duration::from_millis(50))?;
+        assert_eq!(count.get(), 1);
+
+        Ok(())
+    }
 }
@@ -2,6 +2,7 @@ use super::{guard_condition::GuardCondition, CallbackResult};
 use crate::{
     context::Context,
     error::DynError,
+    rcl,
     service::{client::ClientData, server::ServerData},
     signal_handler,
     topic::subscriber::RCLSubscription,
@@ -10,10 +11,16 @@ use crossbeam_channel::{Receiver, Sender};
 use once_cell::sync::Lazy;
 use parking_lot::Mutex;
 use std::{
+    collections::BTreeMap,
     sync::Arc,
     thread::{self, yield_now, JoinHandle},
+    time::Duration,
 };
 
+/// A selector without waiting entities terminates after this duration,
+/// so that it does not keep the context alive.
+const IDLE_TIMEOUT: Duration = Duration::from_secs(1);
+
 pub(crate) static SELECTOR: Lazy<Mutex<AsyncSelector>> =
     Lazy::new(|| Mutex::new(AsyncSelector::new()));
 
@@ -45,11 +52,29 @@ struct SelectorData {
     tx: Sender<Command>,
     th: JoinHandle<Result<(), DynError>>,
     cond: GuardCondition,
-    _context: Arc<Context>,
 }
 
+impl SelectorData {
+    fn halt(self) -> Result<(), DynError> {
+        self.tx.send(Command::Halt)?;
+        self.cond.trigger()?;
+
+        yield_now();
+        let _ = self.th.join();
+
+        Ok(())
+    }
+}
+
+/// Selectors running in threads.
+/// A selector is created for each context,
+/// because a wait set cannot wait entities of other contexts.
+///
+/// A selector holds its context while running,
+/// and it terminates when it has nothing to wait for `IDLE_TIMEOUT`.
+/// It is created again by the next command.
 pub(crate) struct AsyncSelector {
-    data: Option<SelectorData>,
+    data: BTreeMap<*const rcl::rcl_context_t, SelectorData>,
 }
 
 unsafe impl Sync for AsyncSelector {}
@@ -57,28 +82,37 @@ unsafe impl Send for AsyncSelector {}
 
 impl AsyncSelector {
     fn new() -> Self {
-        AsyncSelector { data: None }
+        AsyncSelector {
+            data: BTreeMap::new(),
+        }
     }
 
-    pub(crate) fn halt(&mut self) -> Result<(), DynError> {
-        if let Some(SelectorData { tx, cond, th, .. }) = self.data.take() {
-            tx.send(Command::Halt)?;
-            cond.trigger()?;
-
-            yield_now();
-            let _ = th.join();
+    /// Halt all selectors.
+    /// The threads are joined without locking `SELECTOR`, because they lock it to terminate.
+    pub(crate) fn halt() -> Result<(), DynError> {
+        let data = std::mem::take(&mut SELECTOR.lock().data);
+        for (_, data) in data {
+            data.halt()?;
         }
 
         Ok(())
     }
 
+    /// Remove the selector of a context which has been shut down.
+    /// The thread is not joined, because this may be called in the thread.
+    pub(crate) fn remove_context(&mut self, context: &Context) {
+        if let Some(data) = self.data.remove(&context.as_ptr()) {
+            let _ = data.cond.trigger();
+        }
+    }
+
     pub(crate) fn send_command(
         &mut self,
         context: &Arc<Context>,
         cmd: Command,
     ) -> Result<(), DynError> {
         loop {
-            if let Some(SelectorData { tx, cond, .. }) = &self.data {
+            if let Some(SelectorData { tx, cond, .. }) = self.data.get(&context.as_ptr()) {
                 tx.send(cmd)?;
                 cond.trigger()?;
                 return Ok(());
@@ -92,12 +126,14 @@ impl AsyncSelector {
                 let ctx = context.clone();
                 let guard2 = guard.clone();
                 let th = thread::spawn(move || select(ctx, guard2, rx));
-                self.data = Some(SelectorData {
-                    tx,
-                    th,
-                    _context: context.clone(),
-                    cond: guard,
-                });
+                self.data.insert(
+                    context.as_ptr(),
+                    SelectorData {
+                        tx,
+                        th,
+                        cond: guard,
+                    },
+                );
             }
         }
     }
@@ -127,8 +163,23 @@ fn select(
             }
         }
 
-        if let Err(_e) = selector.wait() {
-            if signal_handler::is_halt() {
+        let result = if is_idle(&selector, &guard) {
+            match selector.wait_timeout(IDLE_TIMEOUT) {
+                Ok(false) => {
+                    if remove_if_idle(&selector, &guard, &rx) {
+                        return Ok(());
+                    }
+                    continue;
+                }
+                result => result.map(|_| ()),
+            }
+        } else {
+            selector.wait()
+        };
+
+        if let Err(_e) = result {
+            if signal_handler::is_halt() || !selector.context.ok() {
+                // wake up all futures, which return an error
                 for (_, h) in selector.subscriptions.iter_mut() {
                     if let Some(handler) = &mut h.handler {
                         (*handler)();
@@ -158,3 +209,39 @@ fn select(
         }
     }
 }
+
+/// Return `true` if no entity except the guard conditions of the selector is waited.
+fn is_idle(selector: &super::Selector, guard: &GuardCondition) -> bool {
+    let own = [
+        Arc::as_ptr(&guard.cond),
+        Arc::as_ptr(&selector.signal_cond.cond),
+    ];
+
+    selector.subscriptions.is_empty()
+        && selector.services.is_empty()
+        && selector.clients.is_empty()
+        && selector.cond.keys().all(|k| own.contains(k))
+}
+
+/// Remove the selector from `SELECTOR` if it is still idle.
+/// Commands are sent while locking `SELECTOR`, so no command arrives after this check.
+fn remove_if_idle(
+    selector: &super::Selector,
+    guard: &GuardCondition,
+    rx: &Receiver<Command>,
+) -> bool {
+    let mut async_selector = SELECTOR.lock();
+    if !rx.is_empty() || !is_idle(selector, guard) {
+        return false;
+    }
+
+    // the context may have been shut down and removed already
+    let key = selector.context.as_ptr();
+    if let Some(data) = async_selector.data.get(&key) {
+        if Arc::ptr_eq(&data.cond.cond, &guard.cond) {
+            async_selector.data.remove(&key);
+        }
+    }
+
+    true
+}
@@ -0,0 +1,215 @@
+//! Timers driven by the time of a clock, such as the ROS time overridden by `/clock`.
+//!
+//! While the ROS time is overridden, the time does not advance by itself,
+//! so a jump callback of the clock triggers a guard condition to wake up selectors
+//! whenever the time is set.
+//! If the time jumps backward or the override is enabled or disabled,
+//! the remaining time of each timer is kept.
+
+use super::guard_condition::GuardCondition;
+use crate::{
+    clock::{Clock, ClockType},
+    context::Context,
+    error::RCLResult,
+    rcl,
+};
+use parking_lot::Mutex;
+use std::{collections::BTreeMap, ffi::c_void, sync::Arc, time::Duration};
+
+/// Timers of a clock registered in a selector.
+pub(super) struct ClockTimers {
+    clock: Arc<Mutex<Clock>>,
+
+    /// Triggered when the time of the clock jumps.
+    /// This is boxed because the address is passed to the jump callback.
+    cond: Box<GuardCondition>,
+
+    /// Timers sorted by their deadlines and identifiers.
+    timers: BTreeMap<(i64, u64), Timer>,
+
+    /// The time when the timers were checked last.
+    now: i64,
+    is_overridden: bool,
+}
+
+struct Timer {
+    period: i64,
+    handler: Box<dyn FnMut()>,
+}
+
+impl ClockTimers {
+    pub(super) fn new(clock: Arc<Mutex<Clock>>, context: Arc<Context>) -> RCLResult<Self> {
+        let cond = Box::new(GuardCondition::new(context)?);
+
+        let (now, is_overridden) = {
+            let mut guard = clock.lock();
+            let now = guard.get_now()?;
+            let is_overridden = is_overridden(&mut guard)?;
+
+            // Wake up when the time is set or the override is enabled or disabled.
+            let threshold = rcl::rcl_jump_threshold_t {
+                on_clock_change: true,
+                min_forward: rcl::rcl_duration_t { nanoseconds: 1 },
+                min_backward: rcl::rcl_duration_t { nanoseconds: -1 },
+            };
+
+            let rcl_guard = rcl::MT_UNSAFE_FN.lock();
+            rcl_guard.rcl_clock_add_jump_callback(
+                guard.as_ptr_mut(),
+                threshold,
+                Some(on_time_jump),
+                &*cond as *const GuardCondition as *mut c_void,
+            )?;
+
+            (now, is_overridden)
+        };
+
+        Ok(Self {
+            clock,
+            cond,
+            timers: BTreeMap::new(),
+            now,
+            is_overridden,
+        })
+    }
+
+    pub(super) fn cond(&self) -> &GuardCondition {
+        &self.cond
+    }
+
+    pub(super) fn is_empty(&self) -> bool {
+        self.timers.is_empty()
+    }
+
+    /// Add a timer, which is called every `period` of the clock.
+    pub(super) fn insert(
+        &mut self,
+        timer_id: u64,
+        period: Duration,
+        handler: Box<dyn FnMut()>,
+    ) -> RCLResult<()> {
+        self.update()?;
+
+        let period = i64::try_from(period.as_nanos()).unwrap_or(i64::MAX);
+        let deadline = self.now.saturating_add(period);
+        self.timers
+            .insert((deadline, timer_id), Timer { period, handler });
+
+        Ok(())
+    }
+
+    /// Remove a timer, and return whether it was registered.
+    pub(super) fn remove(&mut self, timer_id: u64) -> bool {
+        let len = self.timers.len();
+        self.timers.retain(|(_, id), _| *id != timer_id);
+        len != self.timers.len()
+    }
+
+    /// Duration until the nearest deadline.
+    /// `None` means waiting until the time is set, or no timer.
+    pub(super) fn timeout(&self) -> RCLResult<Option<Duration>> {
+        let Some(((deadline, _), _)) = self.timers.first_key_value() else {
+            return Ok(None);
+        };
+
+        let (now, is_overridden) = {
+            let mut guard = self.clock.lock();
+            (guard.get_now()?, is_overridden(&mut guard)?)
+        };
+
+        if *deadline <= now {
+            Ok(Some(Duration::ZERO))
+        } else if is_overridden {
+            Ok(None)
+        } else {
+            Ok(Some(Duration::from_nanos((*deadline - now) as u64)))
+        }
+    }
+
+    /// Invoke handlers of expired timers, and reload them.
+    /// Periods missed by a jump of the time are skipped.
+    pub(super) fn notify(&mut self) -> RCLResult<()> {
+        self.update()?;
+
+        let mut expired = Vec::new();
+        while let Some(entry) = self.timers.first_entry() {
+            if entry.key().0 > self.now {
+                break;
+            }
+            let ((deadline, timer_id), timer) = entry.remove_entry();
+            expired.push((deadline, timer_id, timer));
+        }
+
+        for (deadline, timer_id, mut timer) in expired {
+            (timer.handler)();
+
+            let deadline = if timer.period > 0 {
+                let missed = (self.now - deadline) / timer.period;
+                deadline.saturating_add(timer.period.saturating_mul(missed.saturating_add(1)))
+            } else {
+                self.now
+            };
+            self.timers.insert((deadline, timer_id), timer);
+        }
+
+        Ok(())
+    }
+
+    /// Get the current time, and shift the deadlines
+    /// if the time jumped backward or the override was enabled or disabled.
+    fn update(&mut self) -> RCLResult<()> {
+        let (now, is_overridden) = {
+            let mut guard = self.clock.lock();
+            (guard.get_now()?, is_overridden(&mut guard)?)
+        };
+
+        if now < self.now || is_overridden != self.is_overridden {
+            let timers = std::mem::take(&mut self.timers);
+            for ((deadline, timer_id), timer) in timers {
+                let remaining = (deadline - self.now).clamp(0, timer.period);
+                self.timers
+                    .insert((now.saturating_add(remaining), timer_id), timer);
+            }
+        }
+
+        self.now = now;
+        self.is_overridden = is_overridden;
+
+        Ok(())
+    }
+}
+
+impl Drop for ClockTimers {
+    fn drop(&mut self) {
+        let guard = self.clock.lock();
+        let rcl_guard = rcl::MT_UNSAFE_FN.lock();
+        let _ = rcl_guard.rcl_clock_remove_jump_callback(
+            guard.as_ptr_mut(),
+            Some(on_time_jump),
+            &*self.cond as *const GuardCondition as *mut c_void,
+        );
+    }
+}
+
+/// Only the ROS time can be overridden.
+fn is_overridden(clock: &mut Clock) -> RCLResult<bool> {
+    if clock.get_clock_type() == ClockType::Ros {
+        clock.is_ros_time_override_enabled()
+    } else {
+        Ok(false)
+    }
+}
+
+/// Jump callbacks are invoked by functions such as `rcl_set_ros_time_override`,
+/// which are called while `rcl::MT_UNSAFE_FN` is locked,
+/// so the guard condition is triggered without locking it.
+unsafe extern "C" fn on_time_jump(
+    _time_jump: *const rcl::rcl_time_jump_t,
+    before_jump: bool,
+    user_data: *mut c_void,
+) {
+    if !before_jump {
+        let cond = &*(user_data as *const GuardCondition);
+        rcl::rcl_trigger_guard_condition(cond.cond.as_ptr_mut());
+    }
+}
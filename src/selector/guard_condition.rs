@@ -1,25 +1,32 @@
-use crate::{context::Context, error::RCLResult, get_allocator, rcl};
+use crate::{context::Context, error::RCLResult, get_allocator, node::Node, rcl};
 use std::sync::Arc;
 
 pub(crate) struct RCLGuardCondition {
-    pub(crate) cond: Box<rcl::rcl_guard_condition_t>,
+    cond: *mut rcl::rcl_guard_condition_t,
     _context: Arc<Context>,
+
+    /// `Some` if this is the graph guard condition of a node,
+    /// which is finalized by the node.
+    node: Option<Arc<Node>>,
 }
 
 impl RCLGuardCondition {
     pub(crate) fn as_ptr(&self) -> *const rcl::rcl_guard_condition_t {
-        self.cond.as_ref() as *const _
+        self.cond
     }
 
     pub(crate) unsafe fn as_ptr_mut(&self) -> *mut rcl::rcl_guard_condition_t {
-        self.cond.as_ref() as *const _ as *mut _
+        self.cond
     }
 }
 
 impl Drop for RCLGuardCondition {
     fn drop(&mut self) {
-        let guard = rcl::MT_UNSAFE_FN.lock();
-        guard.rcl_guard_condition_fini(self.cond.as_mut()).unwrap();
+        if self.node.is_none() {
+            let mut cond = unsafe { Box::from_raw(self.cond) };
+            let guard = rcl::MT_UNSAFE_FN.lock();
+            guard.rcl_guard_condition_fini(cond.as_mut()).unwrap();
+        }
     }
 }
 
@@ -42,11 +49,23 @@ impl GuardCondition {
             )?;
         }
 
-        let cond = Arc::new(RCLGuardCondition {
-            cond: Box::new(guard_condition),
+        Ok(RCLGuardCondition {
+            cond: Box::into_raw(Box::new(guard_condition)),
             _context: context,
-        });
-        Ok(GuardCondition { cond })
+            node: None,
+        }
+        .into())
+    }
+
+    /// Get the guard condition which is triggered when the ROS graph changes.
+    pub(crate) fn graph(node: Arc<Node>) -> RCLResult<Self> {
+        let cond = rcl::MTSafeFn::rcl_node_get_graph_guard_condition(node.as_ptr())?;
+        Ok(RCLGuardCondition {
+            cond: cond as *mut _,
+            _context: node.context.clone(),
+            node: Some(node),
+        }
+        .into())
     }
 
     pub(crate) fn trigger(&self) -> RCLResult<()> {
@@ -55,5 +74,13 @@ impl GuardCondition {
     }
 }
 
+impl From<RCLGuardCondition> for GuardCondition {
+    fn from(cond: RCLGuardCondition) -> Self {
+        GuardCondition {
+            cond: Arc::new(cond),
+        }
+    }
+}
+
 unsafe impl Sync for GuardCondition {}
 unsafe impl Send for GuardCondition {}
@@ -104,7 +104,7 @@ impl<T: ServiceMsg> Client<T> {
         qos: Option<Profile>,
     ) -> RCLResult<Self> {
         let mut client = rcl::MTSafeFn::rcl_get_zero_initialized_client();
-        let service_name = CString::new(service_name).unwrap_or_default();
+        let service_name = CString::new(service_name).map_err(|_| RCLError::ServiceNameInvalid)?;
         let profile = qos.unwrap_or_else(Profile::services_default);
         let options = rcl::rcl_client_options_t {
             qos: (&profile).into(),
@@ -478,6 +478,7 @@ impl<T: ServiceMsg> Future for AsyncReceiver<T> {
         if is_halt() {
             return Poll::Ready(Err(Signaled.into()));
         }
+        self.client.data.node.context.check_ok()?;
 
         let this = self.project();
 
@@ -133,13 +133,22 @@ pub struct Server<T> {
 }
 
 impl<T: ServiceMsg> Server<T> {
+    /// Recreate a server from its data, such as after `try_recv` failed.
+    pub(crate) fn from_data(data: Arc<ServerData>) -> Self {
+        Server {
+            data,
+            _phantom: Default::default(),
+            _unsync: Default::default(),
+        }
+    }
+
     pub(crate) fn new(
         node: Arc<Node>,
         service_name: &str,
         qos: Option<Profile>,
     ) -> RCLResult<Self> {
         let mut service = rcl::MTSafeFn::rcl_get_zero_initialized_service();
-        let service_name = CString::new(service_name).unwrap_or_default();
+        let service_name = CString::new(service_name).map_err(|_| RCLError::ServiceNameInvalid)?;
         let profile = qos.unwrap_or_else(Profile::services_default);
         let options = rcl::rcl_service_options_t {
             qos: (&profile).into(),
@@ -361,11 +370,7 @@ impl<T: ServiceMsg> ServerSend<T> {
     }
 
     pub fn give_up(self) -> Server<T> {
-        Server {
-            data: self.data,
-            _phantom: Default::default(),
-            _unsync: Default::default(),
-        }
+        Server::from_data(self.data)
     }
 }
 
@@ -403,6 +408,7 @@ impl<T: ServiceMsg> Future for AsyncReceiver<T> {
         if is_halt() {
             return Poll::Ready(Err(Signaled.into()));
         }
+        self.server.data.node.context.check_ok()?;
 
         let this = self.project();
 
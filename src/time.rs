@@ -1,9 +1,204 @@
+//! Time of clocks, which is safe from the year-2038 problem.
+//!
+//! `Time` holds nanoseconds in `i64` with the type of the clock,
+//! and it can be converted from and to `builtin_interfaces::UnsafeTime` of messages.
+//! Because `UnsafeTime` holds seconds in `i32`,
+//! the conversion to it saturates explicitly by `Time::to_stamp_saturating`.
+//!
+//! # Example
+//!
+//! ```
+//! use safe_drive::{
+//!     clock::{Clock, ClockType},
+//!     time::Time,
+//! };
+//! use std::time::Duration;
+//!
+//! let mut clock = Clock::with_type(ClockType::Steady).unwrap();
+//! let start = clock.now().unwrap();
+//! let deadline = start + Duration::from_millis(100);
+//! assert!(start < deadline);
+//!
+//! // Times of different clocks cannot be compared.
+//! let ros_time = Time::new(start.nanoseconds(), ClockType::Ros);
+//! assert_eq!(start.partial_cmp(&ros_time), None);
+//! assert!(deadline.duration_since(&ros_time).is_err());
+//!
+//! // The conversion to message stamps saturates in 2038.
+//! let far_future = Time::new(i64::MAX, ClockType::Ros);
+//! let stamp = far_future.to_stamp_saturating();
+//! assert_eq!(stamp.sec, i32::MAX);
+//! ```
+
 use crate::{
+    clock::ClockType,
+    error::DynError,
     logger::{pr_fatal_in, Logger},
     msg::builtin_interfaces,
     rcl,
 };
-use std::time::{Duration, SystemTime};
+use std::{
+    cmp::Ordering,
+    ops::{Add, AddAssign, Sub, SubAssign},
+    time::{Duration, SystemTime},
+};
+
+const NANOS_PER_SEC: i64 = 1_000_000_000;
+
+/// A point in time of a clock, which is nanoseconds since the epoch of the clock.
+///
+/// Times of different types of clocks are not comparable,
+/// so `partial_cmp` returns `None` for them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Time {
+    nanoseconds: i64,
+    clock_type: ClockType,
+}
+
+impl Time {
+    pub const fn new(nanoseconds: i64, clock_type: ClockType) -> Self {
+        Self {
+            nanoseconds,
+            clock_type,
+        }
+    }
+
+    /// Convert a stamp of a message to `Time`.
+    /// This never loses precision.
+    pub fn from_stamp(stamp: &builtin_interfaces::UnsafeTime, clock_type: ClockType) -> Self {
+        let nanoseconds = stamp.sec as i64 * NANOS_PER_SEC + stamp.nanosec as i64;
+        Self::new(nanoseconds, clock_type)
+    }
+
+    /// Convert to a stamp of a message.
+    ///
+    /// Because a stamp holds seconds in `i32`,
+    /// times after 2038-01-19 saturate to the maximum stamp,
+    /// and times before 1901-12-13 saturate to the minimum stamp.
+    pub fn to_stamp_saturating(&self) -> builtin_interfaces::UnsafeTime {
+        let sec = self.nanoseconds.div_euclid(NANOS_PER_SEC);
+        let nanosec = self.nanoseconds.rem_euclid(NANOS_PER_SEC);
+
+        if sec > i32::MAX as i64 {
+            builtin_interfaces::UnsafeTime {
+                sec: i32::MAX,
+                nanosec: (NANOS_PER_SEC - 1) as u32,
+            }
+        } else if sec < i32::MIN as i64 {
+            builtin_interfaces::UnsafeTime {
+                sec: i32::MIN,
+                nanosec: 0,
+            }
+        } else {
+            builtin_interfaces::UnsafeTime {
+                sec: sec as i32,
+                nanosec: nanosec as u32,
+            }
+        }
+    }
+
+    pub fn nanoseconds(&self) -> i64 {
+        self.nanoseconds
+    }
+
+    pub fn clock_type(&self) -> ClockType {
+        self.clock_type
+    }
+
+    /// Return `None` if overflowed.
+    pub fn checked_add(&self, duration: Duration) -> Option<Self> {
+        let nanoseconds = i64::try_from(duration.as_nanos()).ok()?;
+        Some(Self::new(
+            self.nanoseconds.checked_add(nanoseconds)?,
+            self.clock_type,
+        ))
+    }
+
+    /// Return `None` if overflowed.
+    pub fn checked_sub(&self, duration: Duration) -> Option<Self> {
+        let nanoseconds = i64::try_from(duration.as_nanos()).ok()?;
+        Some(Self::new(
+            self.nanoseconds.checked_sub(nanoseconds)?,
+            self.clock_type,
+        ))
+    }
+
+    /// Return the duration elapsed from `earlier` to `self`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the types of the clocks are different,
+    /// or if `earlier` is later than `self`.
+    pub fn duration_since(&self, earlier: &Time) -> Result<Duration, DynError> {
+        if self.clock_type != earlier.clock_type {
+            let msg = format!(
+                "cannot compare times of different clocks: {:?} and {:?}",
+                self.clock_type, earlier.clock_type
+            );
+            return Err(msg.into());
+        }
+
+        let nanoseconds = self.nanoseconds as i128 - earlier.nanoseconds as i128;
+        if nanoseconds < 0 {
+            return Err("the earlier time is later than the time".into());
+        }
+
+        Ok(Duration::from_nanos(nanoseconds as u64))
+    }
+}
+
+impl PartialOrd for Time {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self.clock_type == other.clock_type {
+            Some(self.nanoseconds.cmp(&other.nanoseconds))
+        } else {
+            None
+        }
+    }
+}
+
+impl Add<Duration> for Time {
+    type Output = Time;
+
+    /// # Panics
+    ///
+    /// Panics if overflowed. Use `checked_add` instead to avoid it.
+    fn add(self, rhs: Duration) -> Self::Output {
+        self.checked_add(rhs)
+            .expect("overflow when adding duration to time")
+    }
+}
+
+impl AddAssign<Duration> for Time {
+    fn add_assign(&mut self, rhs: Duration) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub<Duration> for Time {
+    type Output = Time;
+
+    /// # Panics
+    ///
+    /// Panics if overflowed. Use `checked_sub` instead to avoid it.
+    fn sub(self, rhs: Duration) -> Self::Output {
+        self.checked_sub(rhs)
+            .expect("overflow when subtracting duration from time")
+    }
+}
+
+impl SubAssign<Duration> for Time {
+    fn sub_assign(&mut self, rhs: Duration) {
+        *self = *self - rhs;
+    }
+}
+
+/// A stamp of a message is regarded as the ROS time.
+impl From<&builtin_interfaces::UnsafeTime> for Time {
+    fn from(stamp: &builtin_interfaces::UnsafeTime) -> Self {
+        Time::from_stamp(stamp, ClockType::Ros)
+    }
+}
 
 impl From<rcl::rmw_time_t> for Duration {
     fn from(t: rcl::rmw_time_t) -> Self {
@@ -0,0 +1,252 @@
+//! Time source, which drives the clock of a node by `/clock` when `use_sim_time` is true.
+//!
+//! A time source subscribes `/clock` published by simulators,
+//! and overrides the ROS time of the clock of the node, which is got by `Node::get_clock`.
+//! The initial value of `use_sim_time` is given by parameter overrides such as
+//! `--ros-args -p use_sim_time:=true`,
+//! and it can be changed by `set_use_sim_time` or by the parameter `use_sim_time`
+//! after `attach_parameter_server`.
+//! Timers following the clock can be added by `Selector::add_ros_timer`.
+//!
+//! # Example
+//!
+//! ```
+//! use safe_drive::{context::Context, parameter::Value};
+//!
+//! let ctx = Context::new().unwrap();
+//! let node = ctx.create_node("time_source_rs", None, Default::default()).unwrap();
+//!
+//! let time_source = node.create_time_source().unwrap();
+//!
+//! // `use_sim_time` can be set by remote nodes.
+//! let param_server = node.create_parameter_server().unwrap();
+//! time_source.attach_parameter_server(&param_server).unwrap();
+//!
+//! param_server
+//!     .params
+//!     .write()
+//!     .set_parameter("use_sim_time".to_string(), Value::Bool(true), false, None)
+//!     .unwrap();
+//! assert!(time_source.get_use_sim_time());
+//!
+//! // The time is 0 until `/clock` is received.
+//! let clock = node.get_clock();
+//! assert_eq!(clock.lock().get_now().unwrap(), 0);
+//! ```
+
+use crate::{
+    clock::Clock,
+    error::DynError,
+    logger::{pr_error_in, Logger},
+    msg::interfaces::rosgraph_msgs,
+    node::Node,
+    parameter::{typed::Binding, ParameterServer, Value},
+    qos::Profile,
+    selector::{guard_condition::GuardCondition, CallbackResult},
+    time::Time,
+};
+use parking_lot::Mutex;
+use std::{
+    cell::Cell,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+const USE_SIM_TIME: &str = "use_sim_time";
+
+/// Time source of a node.
+/// See `safe_drive::time_source`.
+pub struct TimeSource {
+    state: Arc<State>,
+    handler: Option<std::thread::JoinHandle<Result<(), DynError>>>,
+    cond_halt: GuardCondition,
+}
+
+struct State {
+    use_sim_time: AtomicBool,
+    cond_changed: GuardCondition,
+    clock: Arc<Mutex<Clock>>,
+}
+
+impl TimeSource {
+    pub(crate) fn new(node: Arc<Node>) -> Result<Self, DynError> {
+        let use_sim_time = matches!(
+            node.parameter_overrides()?.get(USE_SIM_TIME),
+            Some(Value::Bool(true))
+        );
+
+        let state = Arc::new(State {
+            use_sim_time: AtomicBool::new(false),
+            cond_changed: GuardCondition::new(node.context.clone())?,
+            clock: node.get_clock(),
+        });
+        state.set_use_sim_time(use_sim_time)?;
+
+        let cond_halt = GuardCondition::new(node.context.clone())?;
+        let cond_halt_cloned = cond_halt.clone();
+        let s = state.clone();
+
+        let handler = std::thread::spawn(move || time_source(node, s, cond_halt_cloned));
+
+        Ok(Self {
+            state,
+            handler: Some(handler),
+            cond_halt,
+        })
+    }
+
+    /// Enable or disable the simulated time.
+    /// If enabled, the time of the clock is set by `/clock`,
+    /// and otherwise the system time is used.
+    pub fn set_use_sim_time(&self, use_sim_time: bool) -> Result<(), DynError> {
+        self.state.set_use_sim_time(use_sim_time)
+    }
+
+    pub fn get_use_sim_time(&self) -> bool {
+        self.state.use_sim_time.load(Ordering::Relaxed)
+    }
+
+    /// Declare the parameter `use_sim_time`, and follow it.
+    /// If the parameter has been declared by parameter overrides,
+    /// the value is used.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `use_sim_time` is not a boolean value.
+    pub fn attach_parameter_server(&self, param_server: &ParameterServer) -> Result<(), DynError> {
+        let mut params = param_server.params.write();
+
+        if params.get_parameter(USE_SIM_TIME).is_none() {
+            params.set_parameter(
+                USE_SIM_TIME.to_string(),
+                Value::Bool(self.get_use_sim_time()),
+                false,
+                Some("use the time published to /clock".to_string()),
+            )?;
+        }
+
+        let Some(Value::Bool(use_sim_time)) = params.get_parameter(USE_SIM_TIME).map(|p| &p.value)
+        else {
+            return Err("use_sim_time must be a boolean value".into());
+        };
+
+        self.state.set_use_sim_time(*use_sim_time)?;
+        params.add_binding(Box::new(self.state.clone()));
+
+        Ok(())
+    }
+}
+
+impl Drop for TimeSource {
+    fn drop(&mut self) {
+        if self.cond_halt.trigger().is_ok() {
+            if let Some(handler) = self.handler.take() {
+                let _ = handler.join();
+            }
+        }
+    }
+}
+
+impl State {
+    fn set_use_sim_time(&self, use_sim_time: bool) -> Result<(), DynError> {
+        {
+            let mut clock = self.clock.lock();
+            if use_sim_time {
+                clock.enable_ros_time_override()?;
+            } else {
+                clock.disable_ros_time_override()?;
+            }
+        }
+
+        if self.use_sim_time.swap(use_sim_time, Ordering::Relaxed) != use_sim_time {
+            self.cond_changed.trigger()?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Binding for Arc<State> {
+    fn check(&self, proposed: &[(String, Value)]) -> Result<(), String> {
+        for (name, value) in proposed {
+            if name == USE_SIM_TIME && !matches!(value, Value::Bool(_)) {
+                return Err("use_sim_time must be a boolean value".to_string());
+            }
+        }
+        Ok(())
+    }
+
+    fn update(&self, name: &str, value: &Value) {
+        if let (USE_SIM_TIME, Value::Bool(use_sim_time)) = (name, value) {
+            if let Err(e) = self.set_use_sim_time(*use_sim_time) {
+                let logger = Logger::new("safe_drive");
+                pr_error_in!(logger, "failed to set use_sim_time: {e}");
+            }
+        }
+    }
+}
+
+/// Subscribe `/clock` while `use_sim_time` is true.
+fn time_source(
+    node: Arc<Node>,
+    state: Arc<State>,
+    cond_halt: GuardCondition,
+) -> Result<(), DynError> {
+    let is_halt = Rc::new(Cell::new(false));
+
+    while !is_halt.get() {
+        let mut selector = node.context.create_selector()?;
+
+        let is_halt_cloned = is_halt.clone();
+        selector.add_guard_condition(
+            &cond_halt,
+            Some(Box::new(move || {
+                is_halt_cloned.set(true);
+                CallbackResult::Remove
+            })),
+            false,
+        );
+
+        let is_changed = Rc::new(Cell::new(false));
+        let is_changed_cloned = is_changed.clone();
+        selector.add_guard_condition(
+            &state.cond_changed,
+            Some(Box::new(move || {
+                is_changed_cloned.set(true);
+                CallbackResult::Remove
+            })),
+            false,
+        );
+
+        let use_sim_time = state.use_sim_time.load(Ordering::Relaxed);
+        if use_sim_time {
+            let subscriber = node
+                .create_subscriber::<rosgraph_msgs::msg::Clock>("/clock", Some(Profile::clock()))?;
+
+            let clock = state.clock.clone();
+            selector.add_subscriber(
+                subscriber,
+                Box::new(move |msg| {
+                    let time = Time::from(&msg.clock);
+                    if let Err(e) = clock.lock().set_ros_time_override(time.nanoseconds()) {
+                        let logger = Logger::new("safe_drive");
+                        pr_error_in!(logger, "failed to set the time of /clock: {e}");
+                    }
+                }),
+            );
+        }
+
+        // Re-create the selector when `use_sim_time` is changed.
+        while !is_halt.get()
+            && !is_changed.get()
+            && use_sim_time == state.use_sim_time.load(Ordering::Relaxed)
+        {
+            selector.wait()?;
+        }
+    }
+
+    Ok(())
+}
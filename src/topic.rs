@@ -1,4 +1,7 @@
 //! Publish and subscribe messages.
 
+pub mod intra_process;
 pub mod publisher;
+pub mod statistics;
 pub mod subscriber;
+pub mod synchronizer;
@@ -0,0 +1,461 @@
+//! Intra-process communication.
+//!
+//! Publishers and subscribers created by
+//! `Node::create_publisher_intra_process` and `Node::create_subscriber_intra_process`
+//! deliver messages within a process without serialization.
+//! A message is transferred by moving its ownership if there is only one subscriber,
+//! and it is shared by `Arc` if there are several subscribers.
+//! Messages are still delivered via `rcl` to subscribers of other processes.
+//!
+//! Intra-process delivery requires the ownership of messages,
+//! so use `Publisher::send_owned` or `Publisher::send_shared` to send messages.
+//! An `IntraProcessSubscriber` receives `IntraProcessMsg`,
+//! which is `IntraProcessMsg::Shared` if the message is shared with other subscribers.
+//!
+//! # Limitations
+//!
+//! - Intra-process subscribers ignore messages sent via `rcl` by the same context,
+//!   so they do not receive messages from publishers of the same context
+//!   created without `create_publisher_intra_process`.
+//! - Only `DurabilityPolicy::Volatile` is supported.
+//!
+//! # Example
+//!
+//! ```
+//! use safe_drive::{
+//!     context::Context, logger::Logger, msg::common_interfaces::std_msgs, pr_info,
+//! };
+//!
+//! let ctx = Context::new().unwrap();
+//! let node_pub = ctx
+//!     .create_node("intra_process_rs_pub", None, Default::default())
+//!     .unwrap();
+//! let node_sub = ctx
+//!     .create_node("intra_process_rs_sub", None, Default::default())
+//!     .unwrap();
+//!
+//! // Create an intra-process publisher and subscriber.
+//! let publisher = node_pub
+//!     .create_publisher_intra_process::<std_msgs::msg::UInt64>("intra_process_rs_topic", None)
+//!     .unwrap();
+//! let subscriber = node_sub
+//!     .create_subscriber_intra_process::<std_msgs::msg::UInt64>("intra_process_rs_topic", None)
+//!     .unwrap();
+//!
+//! // Send a message by moving its ownership.
+//! let mut msg = std_msgs::msg::UInt64::new().unwrap();
+//! msg.data = 100;
+//! publisher.send_owned(msg).unwrap();
+//!
+//! let logger = Logger::new("intra_process_rs");
+//! let mut selector = ctx.create_selector().unwrap();
+//! selector.add_intra_process_subscriber(
+//!     subscriber,
+//!     Box::new(move |msg| pr_info!(logger, "received: {}", msg.data)),
+//! );
+//! selector.wait().unwrap();
+//! ```
+
+use crate::{
+    context::Context,
+    error::{DynError, RCLError, RCLResult},
+    is_halt,
+    msg::TypeSupport,
+    node::Node,
+    qos::{
+        policy::{DurabilityPolicy, HistoryPolicy},
+        Compatibility, Profile,
+    },
+    rcl,
+    selector::{
+        async_selector::{self, SELECTOR},
+        guard_condition::GuardCondition,
+        CallbackResult,
+    },
+    signal_handler::Signaled,
+    topic::{
+        statistics::TopicStatisticsOptions,
+        subscriber::{Subscriber, TakenMsg},
+        synchronizer::HasHeader,
+    },
+    RecvResult,
+};
+use parking_lot::Mutex;
+use pin_project::{pin_project, pinned_drop};
+use std::{
+    any::Any,
+    collections::{BTreeMap, VecDeque},
+    ffi::CStr,
+    future::Future,
+    ops::Deref,
+    os::raw::c_char,
+    pin::Pin,
+    sync::{Arc, Weak},
+    task::{self, Poll},
+};
+
+/// Manager of intra-process subscriptions of a context.
+/// Subscriptions are registered by fully qualified topic names.
+pub(crate) struct IntraProcessManager {
+    subscriptions: Mutex<BTreeMap<String, Vec<Entry>>>,
+}
+
+struct Entry {
+    buffer: Weak<dyn Any + Send + Sync>,
+    qos: Profile,
+}
+
+impl IntraProcessManager {
+    pub(crate) fn new() -> Self {
+        IntraProcessManager {
+            subscriptions: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    pub(crate) fn add_subscription<T: Send + Sync + 'static>(
+        &self,
+        topic_name: &str,
+        buffer: &Arc<IntraProcessBuffer<T>>,
+    ) {
+        let qos = buffer.qos.clone();
+        let buffer: Arc<dyn Any + Send + Sync> = buffer.clone();
+        let mut guard = self.subscriptions.lock();
+        let entries = guard.entry(topic_name.to_string()).or_default();
+        entries.retain(|e| e.buffer.strong_count() > 0);
+        entries.push(Entry {
+            buffer: Arc::downgrade(&buffer),
+            qos,
+        });
+    }
+
+    /// Return subscriptions of `topic_name` whose types are `T` and
+    /// whose QoS profiles are compatible with `qos`.
+    pub(crate) fn get_subscriptions<T: Send + Sync + 'static>(
+        &self,
+        topic_name: &str,
+        qos: &Profile,
+    ) -> Vec<Arc<IntraProcessBuffer<T>>> {
+        let mut guard = self.subscriptions.lock();
+        let Some(entries) = guard.get_mut(topic_name) else {
+            return Vec::new();
+        };
+
+        entries.retain(|e| e.buffer.strong_count() > 0);
+        if entries.is_empty() {
+            guard.remove(topic_name);
+            return Vec::new();
+        }
+
+        entries
+            .iter()
+            .filter(|e| is_compatible(qos, &e.qos))
+            .filter_map(|e| e.buffer.upgrade()?.downcast::<IntraProcessBuffer<T>>().ok())
+            .collect()
+    }
+
+    /// Check whether there are subscriptions of `topic_name`
+    /// whose QoS profiles are compatible with `qos`.
+    pub(crate) fn has_subscriptions(&self, topic_name: &str, qos: &Profile) -> bool {
+        let guard = self.subscriptions.lock();
+        guard.get(topic_name).is_some_and(|entries| {
+            entries
+                .iter()
+                .any(|e| e.buffer.strong_count() > 0 && is_compatible(qos, &e.qos))
+        })
+    }
+}
+
+/// Message queue of an intra-process subscriber.
+pub(crate) struct IntraProcessBuffer<T> {
+    queue: Mutex<VecDeque<Arc<T>>>,
+    qos: Profile,
+    pub(crate) cond: GuardCondition,
+}
+
+impl<T> IntraProcessBuffer<T> {
+    pub(crate) fn new(context: Arc<Context>, qos: Profile) -> RCLResult<Self> {
+        Ok(IntraProcessBuffer {
+            queue: Mutex::new(VecDeque::new()),
+            qos,
+            cond: GuardCondition::new(context)?,
+        })
+    }
+
+    /// Enqueue a message and wake up the subscriber.
+    /// If the queue is full, the oldest message is dropped.
+    pub(crate) fn push(&self, msg: Arc<T>) -> RCLResult<()> {
+        {
+            let mut guard = self.queue.lock();
+            guard.push_back(msg);
+
+            if let HistoryPolicy::KeepLast | HistoryPolicy::SystemDefault = self.qos.history {
+                let depth = self.qos.depth.max(1);
+                while guard.len() > depth {
+                    guard.pop_front();
+                }
+            }
+        }
+
+        self.cond.trigger()
+    }
+
+    pub(crate) fn pop(&self) -> Option<Arc<T>> {
+        self.queue.lock().pop_front()
+    }
+}
+
+/// Subscriber created by `Node::create_subscriber_intra_process`.
+///
+/// Messages of intra-process publishers are received without serialization,
+/// and messages of the other publishers are received via `rcl`.
+pub struct IntraProcessSubscriber<T> {
+    pub(crate) subscriber: Subscriber<T>,
+    pub(crate) buffer: Arc<IntraProcessBuffer<T>>,
+}
+
+impl<T: TypeSupport + Send + Sync + 'static> IntraProcessSubscriber<T> {
+    pub(crate) fn new(node: Arc<Node>, topic_name: &str, qos: Option<Profile>) -> RCLResult<Self> {
+        let qos = qos.unwrap_or_default();
+        check_qos(&qos)?;
+
+        // Messages from intra-process publishers are delivered without rcl.
+        let subscriber = Subscriber::new_ignore_local_publications(node, topic_name, &qos)?;
+
+        let name = subscription_topic_name(subscriber.subscription.subscription.as_ref());
+        let context = subscriber.subscription.node.context.clone();
+
+        let buffer = Arc::new(IntraProcessBuffer::new(context.clone(), qos)?);
+        context.intra_process.add_subscription(&name, &buffer);
+
+        Ok(IntraProcessSubscriber { subscriber, buffer })
+    }
+
+    pub fn get_topic_name(&self) -> &str {
+        self.subscriber.get_topic_name()
+    }
+
+    /// See `Subscriber::actual_qos`.
+    pub fn actual_qos(&self) -> RCLResult<Profile> {
+        self.subscriber.actual_qos()
+    }
+
+    /// See `Subscriber::enable_statistics`.
+    pub fn enable_statistics(&mut self, options: TopicStatisticsOptions) -> Result<(), DynError> {
+        self.subscriber.enable_statistics(options)
+    }
+
+    /// See `Subscriber::enable_statistics_with_message_age`.
+    pub fn enable_statistics_with_message_age(
+        &mut self,
+        options: TopicStatisticsOptions,
+    ) -> Result<(), DynError>
+    where
+        T: HasHeader,
+    {
+        self.subscriber.enable_statistics_with_message_age(options)
+    }
+
+    /// Non-blocking receive.
+    /// Messages of intra-process publishers are received first.
+    /// See `Subscriber::try_recv`.
+    #[must_use]
+    pub fn try_recv(&self) -> RecvResult<IntraProcessMsg<T>, ()> {
+        if let Some(msg) = self.buffer.pop() {
+            if let Some(statistics) = &self.subscriber.statistics {
+                statistics.record(&msg);
+            }
+            return RecvResult::Ok(IntraProcessMsg::from_shared(msg));
+        }
+
+        match self.subscriber.try_recv() {
+            RecvResult::Ok(msg) => RecvResult::Ok(IntraProcessMsg::Taken(msg)),
+            RecvResult::RetryLater(()) => RecvResult::RetryLater(()),
+            RecvResult::Err(e) => RecvResult::Err(e),
+        }
+    }
+
+    /// Receive a message asynchronously.
+    /// See `Subscriber::recv`.
+    pub async fn recv(&mut self) -> Result<IntraProcessMsg<T>, DynError> {
+        AsyncReceiver {
+            subscriber: self,
+            is_waiting: false,
+        }
+        .await
+    }
+}
+
+/// A message received by an intra-process subscriber.
+pub enum IntraProcessMsg<T> {
+    /// A message taken via `rcl`, or moved from an intra-process publisher.
+    Taken(TakenMsg<T>),
+
+    /// A message shared with other intra-process subscribers.
+    Shared(Arc<T>),
+}
+
+impl<T> IntraProcessMsg<T> {
+    /// The message is moved if no other subscriber shares it.
+    fn from_shared(msg: Arc<T>) -> Self {
+        match Arc::try_unwrap(msg) {
+            Ok(msg) => IntraProcessMsg::Taken(TakenMsg::Copied(msg)),
+            Err(msg) => IntraProcessMsg::Shared(msg),
+        }
+    }
+
+    /// Get a mutable reference to the message.
+    /// `None` is returned if the message is still shared with other subscribers.
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        match self {
+            IntraProcessMsg::Taken(taken) => Some(taken),
+            IntraProcessMsg::Shared(shared) => Arc::get_mut(shared),
+        }
+    }
+
+    /// Get a mutable reference to the message.
+    /// The message is cloned if it is shared with other subscribers.
+    pub fn make_mut(&mut self) -> &mut T
+    where
+        T: Clone,
+    {
+        match self {
+            IntraProcessMsg::Taken(taken) => taken,
+            IntraProcessMsg::Shared(shared) => Arc::make_mut(shared),
+        }
+    }
+
+    /// Returns the owned message without cloning if the subscriber owns it.
+    /// See `TakenMsg::get_owned`.
+    pub fn get_owned(self) -> Option<T> {
+        match self {
+            IntraProcessMsg::Taken(taken) => taken.get_owned(),
+            IntraProcessMsg::Shared(shared) => Arc::try_unwrap(shared).ok(),
+        }
+    }
+}
+
+impl<T> Deref for IntraProcessMsg<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            IntraProcessMsg::Taken(taken) => taken,
+            IntraProcessMsg::Shared(shared) => shared,
+        }
+    }
+}
+
+/// Asynchronous receiver of intra-process subscribers,
+/// which waits for both the subscription and the intra-process queue.
+#[pin_project(PinnedDrop)]
+struct AsyncReceiver<'a, T> {
+    subscriber: &'a IntraProcessSubscriber<T>,
+    is_waiting: bool,
+}
+
+impl<'a, T: TypeSupport + Send + Sync + 'static> Future for AsyncReceiver<'a, T> {
+    type Output = Result<IntraProcessMsg<T>, DynError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Self::Output> {
+        if is_halt() {
+            return Poll::Ready(Err(Signaled.into()));
+        }
+
+        let this = self.project();
+        let subscription = &this.subscriber.subscriber.subscription;
+        subscription.node.context.check_ok()?;
+
+        *this.is_waiting = false;
+
+        match this.subscriber.try_recv() {
+            RecvResult::Ok(msg) => Poll::Ready(Ok(msg)),
+            RecvResult::RetryLater(()) => {
+                let mut guard = SELECTOR.lock();
+
+                let mut waker = Some(cx.waker().clone());
+                guard.send_command(
+                    &subscription.node.context,
+                    async_selector::Command::Subscription(
+                        subscription.clone(),
+                        Box::new(move || {
+                            if let Some(w) = waker.take() {
+                                w.wake();
+                            }
+                            CallbackResult::Ok
+                        }),
+                    ),
+                )?;
+
+                let mut waker = Some(cx.waker().clone());
+                guard.send_command(
+                    &subscription.node.context,
+                    async_selector::Command::ConditionVar(
+                        this.subscriber.buffer.cond.clone(),
+                        Box::new(move || {
+                            if let Some(w) = waker.take() {
+                                w.wake();
+                            }
+                            CallbackResult::Ok
+                        }),
+                    ),
+                )?;
+
+                *this.is_waiting = true;
+                Poll::Pending
+            }
+            RecvResult::Err(e) => Poll::Ready(Err(e)),
+        }
+    }
+}
+
+#[pinned_drop]
+impl<T> PinnedDrop for AsyncReceiver<'_, T> {
+    fn drop(self: Pin<&mut Self>) {
+        if self.is_waiting {
+            let subscription = &self.subscriber.subscriber.subscription;
+            let mut guard = SELECTOR.lock();
+            let _ = guard.send_command(
+                &subscription.node.context,
+                async_selector::Command::RemoveSubscription(subscription.clone()),
+            );
+            let _ = guard.send_command(
+                &subscription.node.context,
+                async_selector::Command::RemoveConditionVar(self.subscriber.buffer.cond.clone()),
+            );
+        }
+    }
+}
+
+/// Intra-process communication supports only volatile durability.
+pub(crate) fn check_qos(qos: &Profile) -> RCLResult<()> {
+    if let DurabilityPolicy::TransientLocal = qos.durability {
+        Err(RCLError::InvalidArgument)
+    } else {
+        Ok(())
+    }
+}
+
+fn is_compatible(publisher: &Profile, subscription: &Profile) -> bool {
+    !matches!(
+        publisher.check_compatible(subscription),
+        Ok(Compatibility::Error(_))
+    )
+}
+
+pub(crate) fn publisher_topic_name(publisher: &rcl::rcl_publisher_t) -> String {
+    to_string(rcl::MTSafeFn::rcl_publisher_get_topic_name(publisher))
+}
+
+pub(crate) fn subscription_topic_name(subscription: &rcl::rcl_subscription_t) -> String {
+    to_string(rcl::MTSafeFn::rcl_subscription_get_topic_name(subscription))
+}
+
+fn to_string(name: *const c_char) -> String {
+    if name.is_null() {
+        String::new()
+    } else {
+        unsafe { CStr::from_ptr(name) }
+            .to_string_lossy()
+            .into_owned()
+    }
+}
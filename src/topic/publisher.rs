@@ -48,13 +48,14 @@
 //! `None` of the 2nd argument of `create_publisher` is equivalent to `Some(Profile::default())`.
 
 use crate::{
-    error::{DynError, RCLResult},
+    error::{DynError, RCLError, RCLResult},
     get_allocator,
     msg::TypeSupport,
     node::Node,
     publisher_loaned_message::PublisherLoanedMessage,
     qos, rcl,
     signal_handler::Signaled,
+    topic::intra_process,
 };
 use std::{ffi::CString, marker::PhantomData, ptr::null_mut, sync::Arc};
 
@@ -93,6 +94,9 @@ pub struct Publisher<T> {
     #[cfg(feature = "rcl_stat")]
     latency_publish: Mutex<TimeStatistics<4096>>,
 
+    /// Fully qualified topic name and QoS profile for intra-process communication.
+    intra_process: Option<(String, qos::Profile)>,
+
     _phantom: PhantomData<T>,
     node: Arc<Node>,
 }
@@ -105,7 +109,7 @@ impl<T: TypeSupport> Publisher<T> {
     ) -> RCLResult<Self> {
         let mut publisher = rcl::MTSafeFn::rcl_get_zero_initialized_publisher();
 
-        let topic_name_c = CString::new(topic_name).unwrap_or_default();
+        let topic_name_c = CString::new(topic_name).map_err(|_| RCLError::TopicNameInvalid)?;
 
         let options = Options::new(&qos.unwrap_or_default());
 
@@ -128,6 +132,7 @@ impl<T: TypeSupport> Publisher<T> {
             #[cfg(feature = "rcl_stat")]
             latency_publish: Mutex::new(TimeStatistics::new()),
 
+            intra_process: None,
             _phantom: Default::default(),
         })
     }
@@ -139,7 +144,7 @@ impl<T: TypeSupport> Publisher<T> {
     ) -> RCLResult<Self> {
         let mut publisher = rcl::MTSafeFn::rcl_get_zero_initialized_publisher();
 
-        let topic_name_c = CString::new(topic_name).unwrap_or_default();
+        let topic_name_c = CString::new(topic_name).map_err(|_| RCLError::TopicNameInvalid)?;
 
         let mut options = Options::new(&qos.unwrap_or_default());
         options.disable_loaned_message();
@@ -163,14 +168,45 @@ impl<T: TypeSupport> Publisher<T> {
             #[cfg(feature = "rcl_stat")]
             latency_publish: Mutex::new(TimeStatistics::new()),
 
+            intra_process: None,
             _phantom: Default::default(),
         })
     }
 
+    pub(crate) fn new_intra_process(
+        node: Arc<Node>,
+        topic_name: &str,
+        qos: Option<qos::Profile>,
+    ) -> RCLResult<Self> {
+        let qos = qos.unwrap_or_default();
+        intra_process::check_qos(&qos)?;
+
+        let mut publisher = Self::new(node, topic_name, Some(qos.clone()))?;
+        let name = intra_process::publisher_topic_name(publisher.publisher.as_ref());
+        publisher.intra_process = Some((name, qos));
+
+        Ok(publisher)
+    }
+
     pub fn get_topic_name(&self) -> &str {
         &self.topic_name
     }
 
+    /// Get the actual QoS profile of the publisher negotiated by the RMW implementation.
+    /// Policies specified as system default are resolved to the actual values.
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::PublisherInvalid` if the publisher is invalid.
+    pub fn actual_qos(&self) -> RCLResult<qos::Profile> {
+        let qos = rcl::MTSafeFn::rcl_publisher_get_actual_qos(self.publisher.as_ref());
+        if qos.is_null() {
+            Err(RCLError::PublisherInvalid)
+        } else {
+            Ok(unsafe { &*qos }.into())
+        }
+    }
+
     pub fn can_loan_messages(&self) -> bool {
         rcl::MTSafeFn::rcl_publisher_can_loan_messages(self.publisher.as_ref())
     }
@@ -205,19 +241,125 @@ impl<T: TypeSupport> Publisher<T> {
     /// - `RCLError::InvalidArgument` if any arguments are invalid, or
     /// - `RCLError::PublisherInvalid` if the publisher is invalid, or
     /// - `RCLError::Error` if an unspecified error occurs.
+    ///
+    /// If the publisher is created by `Node::create_publisher_intra_process`
+    /// and there are intra-process subscribers, this fails because
+    /// a borrowed message cannot be delivered to them.
+    /// Use `send_owned()` or `send_shared()` instead.
     pub fn send(&self, msg: &T) -> Result<(), DynError> {
         if crate::is_halt() {
             return Err(Signaled.into());
         }
 
+        if let Some((topic_name, qos)) = &self.intra_process {
+            if self
+                .node
+                .context
+                .intra_process
+                .has_subscriptions(topic_name, qos)
+            {
+                return Err(
+                    "send() cannot deliver messages to intra-process subscribers, use send_owned() or send_shared()"
+                        .into(),
+                );
+            }
+        }
+
+        self.publish(msg)
+    }
+
+    /// Send a message by moving its ownership.
+    ///
+    /// If the publisher is created by `Node::create_publisher_intra_process`,
+    /// the message is delivered to intra-process subscribers without serialization.
+    /// If there is only one intra-process subscriber, the subscriber receives
+    /// the message itself.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::{context::Context, msg::common_interfaces::std_msgs};
+    ///
+    /// let ctx = Context::new().unwrap();
+    /// let node = ctx
+    ///     .create_node("publish_rs_send_owned", None, Default::default())
+    ///     .unwrap();
+    ///
+    /// // Create a publisher.
+    /// let publisher = node
+    ///     .create_publisher_intra_process("publish_rs_send_owned_topic", None)
+    ///     .unwrap();
+    ///
+    /// // Send a message.
+    /// let msg = std_msgs::msg::Empty::new().unwrap();
+    /// publisher.send_owned(msg).unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::InvalidArgument` if any arguments are invalid, or
+    /// - `RCLError::PublisherInvalid` if the publisher is invalid, or
+    /// - `RCLError::Error` if an unspecified error occurs.
+    pub fn send_owned(&self, msg: T) -> Result<(), DynError>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.send_shared(Arc::new(msg))
+    }
+
+    /// Send a message shared by `Arc`.
+    ///
+    /// If the publisher is created by `Node::create_publisher_intra_process`,
+    /// intra-process subscribers receive the shared message without copying.
+    /// Messages are delivered via `rcl` only if there are subscribers
+    /// of other processes or of other contexts.
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::InvalidArgument` if any arguments are invalid, or
+    /// - `RCLError::PublisherInvalid` if the publisher is invalid, or
+    /// - `RCLError::Error` if an unspecified error occurs.
+    pub fn send_shared(&self, msg: Arc<T>) -> Result<(), DynError>
+    where
+        T: Send + Sync + 'static,
+    {
+        if crate::is_halt() {
+            return Err(Signaled.into());
+        }
+
+        let Some((topic_name, qos)) = &self.intra_process else {
+            return self.publish(msg.as_ref());
+        };
+
+        let mut subscriptions = self
+            .node
+            .context
+            .intra_process
+            .get_subscriptions::<T>(topic_name, qos);
+
+        // Intra-process subscribers ignore messages of this context sent via rcl,
+        // so publish via rcl only if there are any other subscribers.
+        let count = rcl::MTSafeFn::rcl_publisher_get_subscription_count(self.publisher.as_ref())?;
+        if count > subscriptions.len() {
+            self.publish(msg.as_ref())?;
+        }
+
+        // The last subscriber takes the message itself.
+        if let Some(last) = subscriptions.pop() {
+            for subscription in subscriptions {
+                subscription.push(msg.clone())?;
+            }
+            last.push(msg)?;
+        }
+
+        Ok(())
+    }
+
+    fn publish(&self, msg: &T) -> Result<(), DynError> {
         #[cfg(feature = "rcl_stat")]
         let start = std::time::SystemTime::now();
 
-        if let Err(e) =
-            rcl::MTSafeFn::rcl_publish(self.publisher.as_ref(), msg as *const T as _, null_mut())
-        {
-            return Err(e.into());
-        }
+        rcl::MTSafeFn::rcl_publish(self.publisher.as_ref(), msg as *const T as _, null_mut())?;
 
         #[cfg(feature = "rcl_stat")]
         {
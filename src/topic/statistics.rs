@@ -0,0 +1,304 @@
+//! Topic statistics of subscribers.
+//!
+//! A subscriber can collect the statistics of received messages,
+//! and publish them periodically as `statistics_msgs::msg::MetricsMessage`,
+//! which is compatible with topic statistics of `rclcpp`.
+//!
+//! The following metrics are collected in milliseconds.
+//!
+//! - `message_period`: the period between arrivals of messages.
+//! - `message_age`: the difference between the time of arrival and the timestamp of the header.
+//!   This is collected only by `Subscriber::enable_statistics_with_message_age`.
+//!
+//! Each metric contains the average, minimum, maximum, standard deviation, and sample count
+//! of a window, which is the duration between publications.
+//! If there is no sample in a window, the average, minimum, maximum, and standard deviation are NaN.
+//!
+//! No thread is spawned to publish statistics.
+//! If the subscriber is added to a selector by `Selector::add_subscriber`,
+//! statistics are published by a wall timer of the selector.
+//! Otherwise, they are published when receiving messages after the period has elapsed,
+//! so no statistics are published while no message arrives.
+//!
+//! # Example
+//!
+//! ```
+//! use safe_drive::{
+//!     context::Context, msg::common_interfaces::sensor_msgs,
+//!     topic::statistics::TopicStatisticsOptions,
+//! };
+//! use std::time::Duration;
+//!
+//! let ctx = Context::new().unwrap();
+//! let node = ctx
+//!     .create_node("statistics_rs", None, Default::default())
+//!     .unwrap();
+//!
+//! let mut subscriber = node
+//!     .create_subscriber::<sensor_msgs::msg::Imu>("statistics_rs_topic", None)
+//!     .unwrap();
+//!
+//! // Publish statistics to "/statistics" every 5 seconds.
+//! let options = TopicStatisticsOptions {
+//!     publish_period: Duration::from_secs(5),
+//!     ..Default::default()
+//! };
+//! subscriber.enable_statistics_with_message_age(options).unwrap();
+//! ```
+
+use crate::{
+    error::DynError,
+    logger::{pr_error_in, Logger},
+    msg::{
+        builtin_interfaces::UnsafeTime,
+        interfaces::statistics_msgs::msg::{
+            MetricsMessage, StatisticDataPointSeq, STATISTICS_DATA_TYPE_AVERAGE,
+            STATISTICS_DATA_TYPE_MAXIMUM, STATISTICS_DATA_TYPE_MINIMUM,
+            STATISTICS_DATA_TYPE_SAMPLE_COUNT, STATISTICS_DATA_TYPE_STDDEV,
+        },
+    },
+    node::Node,
+    topic::publisher::Publisher,
+};
+use parking_lot::Mutex;
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, SystemTime},
+};
+
+/// Options of topic statistics.
+#[derive(Debug, Clone)]
+pub struct TopicStatisticsOptions {
+    /// Topic name to publish statistics. The default is `/statistics`.
+    pub publish_topic: String,
+
+    /// Period to publish statistics. The default is 1 second.
+    pub publish_period: Duration,
+}
+
+impl Default for TopicStatisticsOptions {
+    fn default() -> Self {
+        TopicStatisticsOptions {
+            publish_topic: "/statistics".to_string(),
+            publish_period: Duration::from_secs(1),
+        }
+    }
+}
+
+/// Collector of topic statistics held by a subscriber.
+pub(crate) struct SubscriberStatistics<T> {
+    publisher: Arc<StatisticsPublisher>,
+    stamp: Option<fn(&T) -> &UnsafeTime>,
+}
+
+impl<T> SubscriberStatistics<T> {
+    pub(crate) fn new(
+        node: &Arc<Node>,
+        options: TopicStatisticsOptions,
+        stamp: Option<fn(&T) -> &UnsafeTime>,
+    ) -> Result<Self, DynError> {
+        let publisher = node.create_publisher::<MetricsMessage>(&options.publish_topic, None)?;
+
+        let publisher = Arc::new(StatisticsPublisher {
+            publisher,
+            node_name: node.get_name()?,
+            period: options.publish_period,
+            window: Mutex::new(Window::new(stamp.is_some())),
+            has_timer: AtomicBool::new(false),
+        });
+
+        Ok(SubscriberStatistics { publisher, stamp })
+    }
+
+    /// Get the publisher of statistics to be driven by a wall timer.
+    /// After calling this, statistics are not published when receiving messages.
+    pub(crate) fn publisher(&self) -> Arc<StatisticsPublisher> {
+        self.publisher.has_timer.store(true, Ordering::Relaxed);
+        self.publisher.clone()
+    }
+
+    /// Record the arrival of a message.
+    pub(crate) fn record(&self, msg: &T) {
+        let now = SystemTime::now();
+
+        {
+            let mut guard = self.publisher.window.lock();
+
+            if let Some(last) = guard.last_arrival.replace(now) {
+                if let Ok(period) = now.duration_since(last) {
+                    guard.period.add(to_millis(period));
+                }
+            }
+
+            if let Some(stamp) = self.stamp {
+                let stamp = stamp(msg);
+
+                // a zero stamp means the header is not set
+                if stamp.sec != 0 || stamp.nanosec != 0 {
+                    let stamp: SystemTime = stamp.into();
+                    if let Ok(age) = now.duration_since(stamp) {
+                        if let Some(age_stat) = guard.age.as_mut() {
+                            age_stat.add(to_millis(age));
+                        }
+                    }
+                }
+            }
+        }
+
+        self.publish_if_elapsed();
+    }
+
+    /// Publish statistics if the period has elapsed and no wall timer drives the publication.
+    pub(crate) fn publish_if_elapsed(&self) {
+        if self.publisher.has_timer.load(Ordering::Relaxed) {
+            return;
+        }
+
+        let start = self.publisher.window.lock().start;
+        if let Ok(elapsed) = start.elapsed() {
+            if elapsed >= self.publisher.period {
+                self.publisher.publish();
+            }
+        }
+    }
+}
+
+/// Publisher of statistics shared by a subscriber and a wall timer.
+pub(crate) struct StatisticsPublisher {
+    publisher: Publisher<MetricsMessage>,
+    node_name: String,
+    period: Duration,
+    window: Mutex<Window>,
+
+    /// Whether a wall timer of a selector publishes statistics.
+    has_timer: AtomicBool,
+}
+
+impl StatisticsPublisher {
+    pub(crate) fn period(&self) -> Duration {
+        self.period
+    }
+
+    /// Publish the statistics of the current window, and start a new window.
+    pub(crate) fn publish(&self) {
+        let (start, stop, period_stat, age_stat) = {
+            let mut guard = self.window.lock();
+            let stop = SystemTime::now();
+            let start = std::mem::replace(&mut guard.start, stop);
+            let period_stat = std::mem::take(&mut guard.period);
+            let age_stat = guard.age.as_mut().map(std::mem::take);
+            (start, stop, period_stat, age_stat)
+        };
+
+        let mut metrics = vec![("message_period", period_stat)];
+        if let Some(age_stat) = age_stat {
+            metrics.push(("message_age", age_stat));
+        }
+
+        for (source, stat) in metrics {
+            let result = to_message(&self.node_name, source, start, stop, &stat)
+                .and_then(|msg| self.publisher.send(&msg));
+            if let Err(e) = result {
+                let logger = Logger::new("safe_drive");
+                pr_error_in!(logger, "failed to publish topic statistics: {}", e);
+            }
+        }
+    }
+}
+
+fn to_millis(dur: Duration) -> f64 {
+    dur.as_secs_f64() * 1000.0
+}
+
+/// Statistics of a window.
+struct Window {
+    start: SystemTime,
+    last_arrival: Option<SystemTime>,
+    period: Moments,
+    age: Option<Moments>,
+}
+
+impl Window {
+    fn new(collect_age: bool) -> Self {
+        Window {
+            start: SystemTime::now(),
+            last_arrival: None,
+            period: Moments::default(),
+            age: collect_age.then(Moments::default),
+        }
+    }
+}
+
+/// Online computation of mean and variance by Welford's algorithm.
+#[derive(Default)]
+struct Moments {
+    count: u64,
+    mean: f64,
+    m2: f64,
+    min: f64,
+    max: f64,
+}
+
+impl Moments {
+    fn add(&mut self, x: f64) {
+        if self.count == 0 {
+            self.min = x;
+            self.max = x;
+        } else {
+            self.min = self.min.min(x);
+            self.max = self.max.max(x);
+        }
+
+        self.count += 1;
+        let delta = x - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (x - self.mean);
+    }
+
+    /// Return average, minimum, maximum, standard deviation, and sample count.
+    fn get(&self) -> [(u8, f64); 5] {
+        let (avg, min, max, stddev) = if self.count == 0 {
+            (f64::NAN, f64::NAN, f64::NAN, f64::NAN)
+        } else {
+            let stddev = (self.m2 / self.count as f64).sqrt();
+            (self.mean, self.min, self.max, stddev)
+        };
+
+        [
+            (STATISTICS_DATA_TYPE_AVERAGE, avg),
+            (STATISTICS_DATA_TYPE_MINIMUM, min),
+            (STATISTICS_DATA_TYPE_MAXIMUM, max),
+            (STATISTICS_DATA_TYPE_STDDEV, stddev),
+            (STATISTICS_DATA_TYPE_SAMPLE_COUNT, self.count as f64),
+        ]
+    }
+}
+
+fn to_message(
+    node_name: &str,
+    metrics_source: &str,
+    window_start: SystemTime,
+    window_stop: SystemTime,
+    stat: &Moments,
+) -> Result<MetricsMessage, DynError> {
+    let mut msg = MetricsMessage::new().ok_or("failed to allocate MetricsMessage")?;
+    msg.measurement_source_name.assign(node_name);
+    msg.metrics_source.assign(metrics_source);
+    msg.unit.assign("ms");
+    msg.window_start = window_start.into();
+    msg.window_stop = window_stop.into();
+
+    let values = stat.get();
+    let mut statistics =
+        StatisticDataPointSeq::new(values.len()).ok_or("failed to allocate StatisticDataPoint")?;
+    for (point, (data_type, data)) in statistics.iter_mut().zip(values) {
+        point.data_type = data_type;
+        point.data = data;
+    }
+    msg.statistics = statistics;
+
+    Ok(msg)
+}
@@ -154,7 +154,7 @@
 use crate::{
     error::{DynError, RCLError, RCLResult},
     get_allocator, is_halt,
-    msg::TypeSupport,
+    msg::{builtin_interfaces::UnsafeTime, TypeSupport},
     node::Node,
     qos, rcl,
     selector::{
@@ -163,6 +163,10 @@ use crate::{
     },
     signal_handler::Signaled,
     subscriber_loaned_message::SubscriberLoanedMessage,
+    topic::{
+        statistics::{SubscriberStatistics, TopicStatisticsOptions},
+        synchronizer::HasHeader,
+    },
     PhantomUnsync, RecvResult,
 };
 use pin_project::{pin_project, pinned_drop};
@@ -218,6 +222,7 @@ unsafe impl Send for RCLSubscription {}
 /// Subscriber.
 pub struct Subscriber<T> {
     pub(crate) subscription: Arc<RCLSubscription>,
+    pub(crate) statistics: Option<SubscriberStatistics<T>>,
     _phantom: PhantomData<T>,
     _unsync: PhantomUnsync,
 }
@@ -228,36 +233,8 @@ impl<T: TypeSupport> Subscriber<T> {
         topic_name: &str,
         qos: Option<qos::Profile>,
     ) -> RCLResult<Self> {
-        let mut subscription = Box::new(rcl::MTSafeFn::rcl_get_zero_initialized_subscription());
-
-        let topic_name_c = CString::new(topic_name).unwrap_or_default();
-
         let options = Options::new(&qos.unwrap_or_default());
-
-        {
-            let guard = rcl::MT_UNSAFE_FN.lock();
-
-            guard.rcl_subscription_init(
-                subscription.as_mut(),
-                node.as_ptr(),
-                T::type_support(),
-                topic_name_c.as_ptr(),
-                options.as_ptr(),
-            )?;
-        }
-
-        Ok(Subscriber {
-            subscription: Arc::new(RCLSubscription {
-                subscription,
-                node,
-                topic_name: topic_name.to_string(),
-
-                #[cfg(feature = "rcl_stat")]
-                latency_take: Mutex::new(TimeStatistics::new()),
-            }),
-            _phantom: Default::default(),
-            _unsync: Default::default(),
-        })
+        Self::with_options(node, topic_name, &options)
     }
 
     pub(crate) fn new_disable_loaned_message(
@@ -265,12 +242,27 @@ impl<T: TypeSupport> Subscriber<T> {
         topic_name: &str,
         qos: Option<qos::Profile>,
     ) -> RCLResult<Self> {
-        let mut subscription = Box::new(rcl::MTSafeFn::rcl_get_zero_initialized_subscription());
-
-        let topic_name_c = CString::new(topic_name).unwrap_or_default();
-
         let mut options = Options::new(&qos.unwrap_or_default());
         options.disable_loaned_message();
+        Self::with_options(node, topic_name, &options)
+    }
+
+    /// Create a subscriber ignoring messages sent by publishers of the same context,
+    /// which are delivered by intra-process communication instead.
+    pub(crate) fn new_ignore_local_publications(
+        node: Arc<Node>,
+        topic_name: &str,
+        qos: &qos::Profile,
+    ) -> RCLResult<Self> {
+        let mut options = Options::new(qos);
+        options.ignore_local_publications();
+        Self::with_options(node, topic_name, &options)
+    }
+
+    fn with_options(node: Arc<Node>, topic_name: &str, options: &Options) -> RCLResult<Self> {
+        let mut subscription = Box::new(rcl::MTSafeFn::rcl_get_zero_initialized_subscription());
+
+        let topic_name_c = CString::new(topic_name).map_err(|_| RCLError::TopicNameInvalid)?;
 
         {
             let guard = rcl::MT_UNSAFE_FN.lock();
@@ -293,6 +285,7 @@ impl<T: TypeSupport> Subscriber<T> {
                 #[cfg(feature = "rcl_stat")]
                 latency_take: Mutex::new(TimeStatistics::new()),
             }),
+            statistics: None,
             _phantom: Default::default(),
             _unsync: Default::default(),
         })
@@ -302,6 +295,22 @@ impl<T: TypeSupport> Subscriber<T> {
         &self.subscription.topic_name
     }
 
+    /// Get the actual QoS profile of the subscriber negotiated by the RMW implementation.
+    /// Policies specified as system default are resolved to the actual values.
+    ///
+    /// # Errors
+    ///
+    /// - `RCLError::SubscriptionInvalid` if the subscriber is invalid.
+    pub fn actual_qos(&self) -> RCLResult<qos::Profile> {
+        let qos =
+            rcl::MTSafeFn::rcl_subscription_get_actual_qos(self.subscription.subscription.as_ref());
+        if qos.is_null() {
+            Err(RCLError::SubscriptionInvalid)
+        } else {
+            Ok(unsafe { &*qos }.into())
+        }
+    }
+
     /// Non-blocking receive.
     ///
     /// Because `rcl::rcl_take` is non-blocking,
@@ -343,12 +352,20 @@ impl<T: TypeSupport> Subscriber<T> {
                 #[cfg(feature = "rcl_stat")]
                 self.subscription.measure_latency(start);
 
+                if let Some(statistics) = &self.statistics {
+                    statistics.record(&n);
+                }
+
                 RecvResult::Ok(n)
             }
             Err(RCLError::SubscriptionTakeFailed) => {
                 #[cfg(feature = "rcl_stat")]
                 self.subscription.measure_latency(start);
 
+                if let Some(statistics) = &self.statistics {
+                    statistics.publish_if_elapsed();
+                }
+
                 RecvResult::RetryLater(())
             }
             Err(e) => RecvResult::Err(e.into()),
@@ -403,12 +420,52 @@ impl<T: TypeSupport> Subscriber<T> {
     pub async fn recv(&mut self) -> Result<TakenMsg<T>, DynError> {
         AsyncReceiver {
             subscription: &mut self.subscription,
+            statistics: self.statistics.as_ref(),
             is_waiting: false,
             _phantom: Default::default(),
         }
         .await
     }
 
+    /// Enable topic statistics.
+    /// The period between arrivals of messages is published periodically.
+    /// See `safe_drive::topic::statistics`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_drive::{msg::common_interfaces::std_msgs, topic::subscriber::Subscriber};
+    ///
+    /// fn enable_statistics(subscriber: &mut Subscriber<std_msgs::msg::UInt32>) {
+    ///     subscriber.enable_statistics(Default::default()).unwrap();
+    /// }
+    /// ```
+    pub fn enable_statistics(&mut self, options: TopicStatisticsOptions) -> Result<(), DynError> {
+        let statistics = SubscriberStatistics::new(&self.subscription.node, options, None)?;
+        self.statistics = Some(statistics);
+        Ok(())
+    }
+
+    /// Enable topic statistics including the age of messages,
+    /// which is computed from the timestamp of the header.
+    /// See `safe_drive::topic::statistics`.
+    pub fn enable_statistics_with_message_age(
+        &mut self,
+        options: TopicStatisticsOptions,
+    ) -> Result<(), DynError>
+    where
+        T: HasHeader,
+    {
+        fn stamp<T: HasHeader>(msg: &T) -> &UnsafeTime {
+            &msg.get_header().stamp
+        }
+
+        let statistics =
+            SubscriberStatistics::new(&self.subscription.node, options, Some(stamp::<T>))?;
+        self.statistics = Some(statistics);
+        Ok(())
+    }
+
     /// Get latency statistics information of `Mutex` and `rcl_take()`.
     /// Because `rcl_take()` is MT-UNSAFE, a latency includes not only `rcl_take` but also `Mutex`.
     #[cfg(feature = "rcl_stat")]
@@ -422,6 +479,7 @@ impl<T: TypeSupport> Subscriber<T> {
 #[pin_project(PinnedDrop)]
 pub struct AsyncReceiver<'a, T> {
     subscription: &'a mut Arc<RCLSubscription>,
+    statistics: Option<&'a SubscriberStatistics<T>>,
     is_waiting: bool,
     _phantom: PhantomData<T>,
 }
@@ -433,6 +491,7 @@ impl<'a, T> Future for AsyncReceiver<'a, T> {
         if is_halt() {
             return Poll::Ready(Err(Signaled.into()));
         }
+        self.subscription.node.context.check_ok()?;
 
         let this = self.project();
 
@@ -451,12 +510,20 @@ impl<'a, T> Future for AsyncReceiver<'a, T> {
                 #[cfg(feature = "rcl_stat")]
                 subscription.measure_latency(start);
 
+                if let Some(statistics) = this.statistics {
+                    statistics.record(&value);
+                }
+
                 Poll::Ready(Ok(value))
             } // got
             Err(RCLError::SubscriptionTakeFailed) => {
                 #[cfg(feature = "rcl_stat")]
                 subscription.measure_latency(start);
 
+                if let Some(statistics) = this.statistics {
+                    statistics.publish_if_elapsed();
+                }
+
                 let mut guard = SELECTOR.lock();
                 let mut waker = Some(cx.waker().clone());
 
@@ -518,6 +585,12 @@ impl Options {
         }
     }
 
+    fn ignore_local_publications(&mut self) {
+        self.options
+            .rmw_subscription_options
+            .ignore_local_publications = true;
+    }
+
     pub(crate) fn as_ptr(&self) -> *const rcl::rcl_subscription_options_t {
         &self.options
     }
@@ -562,7 +635,7 @@ impl<T> std::ops::DerefMut for TakenMsg<T> {
 unsafe impl<T> Sync for TakenMsg<T> {}
 unsafe impl<T> Send for TakenMsg<T> {}
 
-fn take<T>(subscription: &Arc<RCLSubscription>) -> RCLResult<TakenMsg<T>> {
+pub(crate) fn take<T>(subscription: &Arc<RCLSubscription>) -> RCLResult<TakenMsg<T>> {
     if rcl::MTSafeFn::rcl_subscription_can_loan_messages(subscription.subscription.as_ref()) {
         take_loaned_message(subscription.clone()).map(TakenMsg::Loaned)
     } else {
@@ -0,0 +1,512 @@
+//! Synchronize messages of several subscribers by the timestamps of their headers.
+//!
+//! This is a counterpart of `message_filters` of ROS2.
+//! A `Synchronizer` takes a tuple of subscribers whose messages have `std_msgs::msg::Header`,
+//! and it delivers a tuple of messages matched by one of the following policies.
+//!
+//! - `Policy::ExactTime`: messages must have exactly the same timestamp.
+//! - `Policy::ApproximateTime(max_interval)`: messages are matched by the nearest timestamps,
+//!   and the difference between the oldest and the newest timestamp must not exceed `max_interval`.
+//!
+//! # Examples
+//!
+//! ## Single Threaded Execution
+//!
+//! ```
+//! use safe_drive::{
+//!     context::Context, logger::Logger, msg::common_interfaces::sensor_msgs, pr_info,
+//!     topic::synchronizer::{Policy, Synchronizer},
+//! };
+//! use std::time::Duration;
+//!
+//! let ctx = Context::new().unwrap();
+//! let node = ctx
+//!     .create_node("synchronizer_rs", None, Default::default())
+//!     .unwrap();
+//!
+//! // Create subscribers.
+//! let sub_image = node
+//!     .create_subscriber::<sensor_msgs::msg::Image>("synchronizer_rs_image", None)
+//!     .unwrap();
+//! let sub_info = node
+//!     .create_subscriber::<sensor_msgs::msg::CameraInfo>("synchronizer_rs_info", None)
+//!     .unwrap();
+//!
+//! // Create a synchronizer.
+//! // The 2nd argument is the policy, and the 3rd argument is the queue size of each subscriber.
+//! let sync = Synchronizer::new(
+//!     (sub_image, sub_info),
+//!     Policy::ApproximateTime(Duration::from_millis(10)),
+//!     10,
+//! );
+//!
+//! let logger = Logger::new("synchronizer_rs");
+//! let mut selector = ctx.create_selector().unwrap();
+//!
+//! // The callback is invoked when a pair of messages is matched.
+//! selector.add_synchronizer(
+//!     sync,
+//!     Box::new(move |(image, info)| {
+//!         pr_info!(logger, "image = {}x{}, info = {}x{}", image.width, image.height, info.width, info.height);
+//!     }),
+//! );
+//! ```
+//!
+//! ## Multi Threaded Execution
+//!
+//! ```
+//! use safe_drive::{
+//!     logger::Logger, msg::common_interfaces::sensor_msgs, pr_info,
+//!     topic::{subscriber::Subscriber, synchronizer::{Policy, Synchronizer}},
+//! };
+//!
+//! async fn run_synchronizer(
+//!     sub_image: Subscriber<sensor_msgs::msg::Image>,
+//!     sub_cloud: Subscriber<sensor_msgs::msg::PointCloud2>,
+//! ) {
+//!     let mut sync = Synchronizer::new((sub_image, sub_cloud), Policy::ExactTime, 10);
+//!     let logger = Logger::new("synchronizer_rs_async");
+//!
+//!     loop {
+//!         let (image, cloud) = sync.recv().await.unwrap();
+//!         pr_info!(logger, "image = {}, cloud = {}", image.data.len(), cloud.data.len());
+//!     }
+//! }
+//! ```
+
+use crate::{
+    error::DynError,
+    is_halt,
+    msg::{builtin_interfaces::UnsafeTime, common_interfaces::*, TypeSupport},
+    selector::{
+        async_selector::{self, SELECTOR},
+        CallbackResult,
+    },
+    signal_handler::Signaled,
+    topic::subscriber::{RCLSubscription, Subscriber, TakenMsg},
+    RecvResult,
+};
+use std::{
+    any::Any,
+    collections::VecDeque,
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    sync::Arc,
+    task::{self, Poll},
+    time::Duration,
+};
+
+/// Messages which have `std_msgs::msg::Header`.
+pub trait HasHeader {
+    fn get_header(&self) -> &std_msgs::msg::Header;
+}
+
+impl HasHeader for std_msgs::msg::Header {
+    fn get_header(&self) -> &std_msgs::msg::Header {
+        self
+    }
+}
+
+macro_rules! impl_has_header {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl HasHeader for $ty {
+                fn get_header(&self) -> &std_msgs::msg::Header {
+                    &self.header
+                }
+            }
+        )*
+    };
+}
+
+impl_has_header!(
+    geometry_msgs::msg::AccelStamped,
+    geometry_msgs::msg::AccelWithCovarianceStamped,
+    geometry_msgs::msg::InertiaStamped,
+    geometry_msgs::msg::PointStamped,
+    geometry_msgs::msg::PolygonStamped,
+    geometry_msgs::msg::PoseArray,
+    geometry_msgs::msg::PoseStamped,
+    geometry_msgs::msg::PoseWithCovarianceStamped,
+    geometry_msgs::msg::QuaternionStamped,
+    geometry_msgs::msg::TransformStamped,
+    geometry_msgs::msg::TwistStamped,
+    geometry_msgs::msg::TwistWithCovarianceStamped,
+    geometry_msgs::msg::Vector3Stamped,
+    geometry_msgs::msg::WrenchStamped,
+    nav_msgs::msg::GridCells,
+    nav_msgs::msg::OccupancyGrid,
+    nav_msgs::msg::Odometry,
+    nav_msgs::msg::Path,
+    sensor_msgs::msg::BatteryState,
+    sensor_msgs::msg::CameraInfo,
+    sensor_msgs::msg::CompressedImage,
+    sensor_msgs::msg::FluidPressure,
+    sensor_msgs::msg::Illuminance,
+    sensor_msgs::msg::Image,
+    sensor_msgs::msg::Imu,
+    sensor_msgs::msg::JointState,
+    sensor_msgs::msg::Joy,
+    sensor_msgs::msg::LaserScan,
+    sensor_msgs::msg::MagneticField,
+    sensor_msgs::msg::MultiDOFJointState,
+    sensor_msgs::msg::MultiEchoLaserScan,
+    sensor_msgs::msg::NavSatFix,
+    sensor_msgs::msg::PointCloud,
+    sensor_msgs::msg::PointCloud2,
+    sensor_msgs::msg::Range,
+    sensor_msgs::msg::RelativeHumidity,
+    sensor_msgs::msg::Temperature,
+    sensor_msgs::msg::TimeReference,
+    stereo_msgs::msg::DisparityImage,
+    trajectory_msgs::msg::JointTrajectory,
+    trajectory_msgs::msg::MultiDOFJointTrajectory,
+    visualization_msgs::msg::ImageMarker,
+    visualization_msgs::msg::Marker,
+);
+
+/// Policy to match messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Policy {
+    /// Messages are matched only if their timestamps are exactly the same.
+    ExactTime,
+
+    /// Messages are matched by the nearest timestamps.
+    /// The difference between the oldest and the newest timestamp of a matched tuple
+    /// must be less than or equal to the maximum interval.
+    ApproximateTime(Duration),
+}
+
+fn stamp_to_nanos(stamp: &UnsafeTime) -> i64 {
+    stamp.sec as i64 * 1_000_000_000 + stamp.nanosec as i64
+}
+
+mod private {
+    use super::*;
+
+    /// A queue of messages received by a subscriber.
+    /// Messages are sorted by their timestamps.
+    pub trait Input {
+        /// Take all available messages from the subscriber.
+        fn fetch(&mut self, queue_size: usize) -> Result<(), DynError>;
+        fn len(&self) -> usize;
+        fn stamp(&self, idx: usize) -> i64;
+        fn drop_front(&mut self, n: usize);
+        fn pop_front(&mut self) -> Box<dyn Any>;
+
+        // This trait is sealed, so `RCLSubscription` never leaks out of the crate.
+        #[allow(private_interfaces)]
+        fn subscription(&self) -> &Arc<RCLSubscription>;
+    }
+
+    pub trait Sealed {
+        fn into_inputs(self) -> Vec<Box<dyn Input + Send>>;
+        fn from_matched(matched: Vec<Box<dyn Any>>) -> <Self as Subscribers>::Output
+        where
+            Self: Subscribers;
+    }
+}
+
+use private::Input;
+
+struct SubscriberInput<T> {
+    subscriber: Subscriber<T>,
+    queue: VecDeque<(i64, TakenMsg<T>)>,
+}
+
+impl<T: TypeSupport + HasHeader + 'static> Input for SubscriberInput<T> {
+    fn fetch(&mut self, queue_size: usize) -> Result<(), DynError> {
+        loop {
+            match self.subscriber.try_recv() {
+                RecvResult::Ok(msg) => {
+                    let stamp = stamp_to_nanos(&msg.get_header().stamp);
+                    let idx = self.queue.partition_point(|(t, _)| *t <= stamp);
+                    self.queue.insert(idx, (stamp, msg));
+
+                    // drop the oldest message
+                    if self.queue.len() > queue_size {
+                        self.queue.pop_front();
+                    }
+                }
+                RecvResult::RetryLater(()) => return Ok(()),
+                RecvResult::Err(e) => return Err(e),
+            }
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    fn stamp(&self, idx: usize) -> i64 {
+        self.queue[idx].0
+    }
+
+    fn drop_front(&mut self, n: usize) {
+        self.queue.drain(..n);
+    }
+
+    fn pop_front(&mut self) -> Box<dyn Any> {
+        Box::new(self.queue.pop_front().unwrap().1)
+    }
+
+    fn subscription(&self) -> &Arc<RCLSubscription> {
+        &self.subscriber.subscription
+    }
+}
+
+/// A tuple of subscribers which can be synchronized.
+///
+/// This is implemented for tuples of 2 to 9 `Subscriber<T>`,
+/// where `T` implements `HasHeader`.
+pub trait Subscribers: private::Sealed {
+    /// A tuple of matched messages.
+    type Output;
+}
+
+macro_rules! impl_subscribers {
+    ($($t:ident $idx:tt),+) => {
+        impl<$($t: TypeSupport + HasHeader + Send + 'static),+> private::Sealed for ($(Subscriber<$t>,)+) {
+            fn into_inputs(self) -> Vec<Box<dyn Input + Send>> {
+                vec![$(Box::new(SubscriberInput {
+                    subscriber: self.$idx,
+                    queue: VecDeque::new(),
+                })),+]
+            }
+
+            fn from_matched(matched: Vec<Box<dyn Any>>) -> <Self as Subscribers>::Output {
+                let mut it = matched.into_iter();
+                ($(*it.next().unwrap().downcast::<TakenMsg<$t>>().unwrap(),)+)
+            }
+        }
+
+        impl<$($t: TypeSupport + HasHeader + Send + 'static),+> Subscribers for ($(Subscriber<$t>,)+) {
+            type Output = ($(TakenMsg<$t>,)+);
+        }
+    };
+}
+
+impl_subscribers!(A 0, B 1);
+impl_subscribers!(A 0, B 1, C 2);
+impl_subscribers!(A 0, B 1, C 2, D 3);
+impl_subscribers!(A 0, B 1, C 2, D 3, E 4);
+impl_subscribers!(A 0, B 1, C 2, D 3, E 4, F 5);
+impl_subscribers!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_subscribers!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+impl_subscribers!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8);
+
+/// Synchronizer of subscribers.
+///
+/// See the module-level documentation for examples.
+pub struct Synchronizer<S> {
+    pub(crate) inputs: Vec<Box<dyn Input + Send>>,
+    policy: Policy,
+    queue_size: usize,
+    _phantom: PhantomData<fn() -> S>,
+}
+
+impl<S: Subscribers> Synchronizer<S> {
+    /// Create a synchronizer.
+    /// `queue_size` is the maximum number of messages kept for each subscriber.
+    /// If a queue exceeds `queue_size`, the oldest message is dropped.
+    pub fn new(subscribers: S, policy: Policy, queue_size: usize) -> Self {
+        Synchronizer {
+            inputs: subscribers.into_inputs(),
+            policy,
+            queue_size: queue_size.max(1),
+            _phantom: Default::default(),
+        }
+    }
+
+    pub fn get_policy(&self) -> Policy {
+        self.policy
+    }
+
+    /// Non-blocking receive.
+    ///
+    /// This takes all available messages from the subscribers,
+    /// and returns `RecvResult::RetryLater` if no tuple of messages is matched.
+    #[must_use]
+    pub fn try_recv(&mut self) -> RecvResult<S::Output, ()> {
+        for input in self.inputs.iter_mut() {
+            if let Err(e) = input.fetch(self.queue_size) {
+                return RecvResult::Err(e);
+            }
+        }
+
+        let positions = match self.policy {
+            Policy::ExactTime => match_exact(&self.inputs),
+            Policy::ApproximateTime(max_interval) => {
+                let max_interval = max_interval.as_nanos().min(i64::MAX as u128) as i64;
+                match_approximate(&mut self.inputs, max_interval)
+            }
+        };
+
+        if let Some(positions) = positions {
+            let matched = self
+                .inputs
+                .iter_mut()
+                .zip(positions)
+                .map(|(input, pos)| {
+                    input.drop_front(pos);
+                    input.pop_front()
+                })
+                .collect();
+            RecvResult::Ok(S::from_matched(matched))
+        } else {
+            RecvResult::RetryLater(())
+        }
+    }
+
+    /// Receive a tuple of matched messages asynchronously.
+    ///
+    /// This waits and blocks forever until messages are matched.
+    /// In order to call `recv()` with timeout,
+    /// use mechanisms provided by asynchronous libraries,
+    /// such as `async_std::future::timeout`.
+    pub async fn recv(&mut self) -> Result<S::Output, DynError> {
+        AsyncReceiver {
+            sync: self,
+            is_waiting: false,
+        }
+        .await
+    }
+}
+
+/// Find messages which have the same timestamp.
+/// Return the positions of the matched messages in each queue.
+fn match_exact(inputs: &[Box<dyn Input + Send>]) -> Option<Vec<usize>> {
+    let (first, rest) = inputs.split_first()?;
+    for i in 0..first.len() {
+        let stamp = first.stamp(i);
+        let positions: Option<Vec<usize>> = rest
+            .iter()
+            .map(|input| (0..input.len()).find(|j| input.stamp(*j) == stamp))
+            .collect();
+
+        if let Some(positions) = positions {
+            let mut result = vec![i];
+            result.extend(positions);
+            return Some(result);
+        }
+    }
+
+    None
+}
+
+/// Find messages which have the nearest timestamps.
+/// Return the positions of the matched messages in each queue.
+///
+/// The newest one of the oldest messages in the queues is used as a pivot.
+/// For each queue, the message nearest to the pivot is chosen,
+/// but it is decided only after a message not older than the pivot has arrived,
+/// because a newer message may be nearer.
+/// If the chosen messages are not within `max_interval`,
+/// the oldest message is dropped and this is retried.
+fn match_approximate(
+    inputs: &mut [Box<dyn Input + Send>],
+    max_interval: i64,
+) -> Option<Vec<usize>> {
+    loop {
+        if inputs.iter().any(|input| input.len() == 0) {
+            return None;
+        }
+
+        let pivot = inputs.iter().map(|input| input.stamp(0)).max()?;
+
+        let mut positions = Vec::with_capacity(inputs.len());
+        for input in inputs.iter() {
+            let after = (0..input.len()).find(|j| input.stamp(*j) >= pivot)?;
+            let pos = if after > 0 && pivot - input.stamp(after - 1) < input.stamp(after) - pivot {
+                after - 1
+            } else {
+                after
+            };
+            positions.push(pos);
+        }
+
+        let stamps = inputs
+            .iter()
+            .zip(positions.iter())
+            .map(|(input, pos)| input.stamp(*pos));
+        let min = stamps.clone().min()?;
+        let max = stamps.max()?;
+
+        if max - min <= max_interval {
+            return Some(positions);
+        }
+
+        // the oldest message cannot be matched
+        let (_, oldest) = inputs
+            .iter_mut()
+            .map(|input| (input.stamp(0), input))
+            .min_by_key(|(stamp, _)| *stamp)?;
+        oldest.drop_front(1);
+    }
+}
+
+/// Asynchronous receiver of synchronizers.
+pub struct AsyncReceiver<'a, S> {
+    sync: &'a mut Synchronizer<S>,
+    is_waiting: bool,
+}
+
+impl<'a, S: Subscribers> Future for AsyncReceiver<'a, S> {
+    type Output = Result<S::Output, DynError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Self::Output> {
+        if is_halt() {
+            return Poll::Ready(Err(Signaled.into()));
+        }
+        for input in self.sync.inputs.iter() {
+            input.subscription().node.context.check_ok()?;
+        }
+
+        let this = self.get_mut();
+        this.is_waiting = false;
+
+        match this.sync.try_recv() {
+            RecvResult::Ok(msgs) => Poll::Ready(Ok(msgs)),
+            RecvResult::RetryLater(()) => {
+                let mut guard = SELECTOR.lock();
+                for input in this.sync.inputs.iter() {
+                    let subscription = input.subscription();
+                    let mut waker = Some(cx.waker().clone());
+
+                    guard.send_command(
+                        &subscription.node.context,
+                        async_selector::Command::Subscription(
+                            subscription.clone(),
+                            Box::new(move || {
+                                if let Some(w) = waker.take() {
+                                    w.wake();
+                                }
+                                CallbackResult::Ok
+                            }),
+                        ),
+                    )?;
+                }
+
+                this.is_waiting = true;
+                Poll::Pending
+            }
+            RecvResult::Err(e) => Poll::Ready(Err(e)),
+        }
+    }
+}
+
+impl<'a, S> Drop for AsyncReceiver<'a, S> {
+    fn drop(&mut self) {
+        if self.is_waiting {
+            let mut guard = SELECTOR.lock();
+            for input in self.sync.inputs.iter() {
+                let subscription = input.subscription();
+                let _ = guard.send_command(
+                    &subscription.node.context,
+                    async_selector::Command::RemoveSubscription(subscription.clone()),
+                );
+            }
+        }
+    }
+}
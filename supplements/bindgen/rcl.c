@@ -1,3 +1,9 @@
 #include <rcl/rcl.h>
 #include <rcl/logging.h>
 #include <rcl_action/rcl_action.h>
+#include <rcl/expand_topic_name.h>
+#include <rcl/validate_topic_name.h>
+#include <rmw/validate_full_topic_name.h>
+#include <rmw/validate_namespace.h>
+#include <rmw/validate_node_name.h>
+#include <rcl_yaml_param_parser/parser.h>
@@ -0,0 +1,185 @@
+use safe_drive::{
+    component::{Component, ComponentArgs, ComponentContainer, LoadOptions},
+    context::{Context, ContextBuilder},
+    error::DynError,
+    msg::interfaces::composition_interfaces::srv::{LoadNode, LoadNodeRequest},
+    node::Node,
+    parameter::Value,
+    RecvResult,
+};
+use std::{
+    error::Error,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+static COUNT: AtomicUsize = AtomicUsize::new(0);
+
+struct Counter {
+    node: Arc<Node>,
+}
+
+impl Component for Counter {
+    fn get_node(&self) -> &Arc<Node> {
+        &self.node
+    }
+}
+
+fn create_counter(args: &mut ComponentArgs) -> Result<Box<dyn Component>, DynError> {
+    let node = args.create_node("test_component_counter", None)?;
+    args.selector().add_wall_timer(
+        "test_component_counter",
+        Duration::from_millis(10),
+        Box::new(|| {
+            COUNT.fetch_add(1, Ordering::Relaxed);
+        }),
+    );
+    Ok(Box::new(Counter { node }))
+}
+
+fn create_failure(args: &mut ComponentArgs) -> Result<Box<dyn Component>, DynError> {
+    args.selector().add_wall_timer(
+        "test_component_failure",
+        Duration::from_millis(10),
+        Box::new(|| ()),
+    );
+    Err("failure".into())
+}
+
+#[test]
+fn test_component_container() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let mut container = ComponentContainer::new(ctx, "test_component_container", None)?;
+    container
+        .registry_mut()
+        .register("test_component::Counter", create_counter);
+    container
+        .registry_mut()
+        .register("test_component::Failure", create_failure);
+
+    let options = LoadOptions {
+        node_name: Some("test_component_renamed".to_string()),
+        parameters: vec![("test_component_param".to_string(), Value::I64(10))],
+        ..Default::default()
+    };
+    let id = container.load("test_component", "test_component::Counter", options)?;
+    assert_eq!(
+        container.list(),
+        vec![("/test_component_renamed".to_string(), id)]
+    );
+
+    // a failed component is not loaded
+    assert!(container
+        .load(
+            "test_component",
+            "test_component::Failure",
+            Default::default()
+        )
+        .is_err());
+    assert_eq!(container.list().len(), 1);
+
+    for _ in 0..5 {
+        container.spin_once()?;
+    }
+    assert!(COUNT.load(Ordering::Relaxed) > 0);
+
+    // the timer of the component is removed
+    container.unload(id)?;
+    assert!(container.list().is_empty());
+    assert!(container.unload(id).is_err());
+
+    let count = COUNT.load(Ordering::Relaxed);
+    container
+        .selector()
+        .wait_timeout(Duration::from_millis(50))?;
+    assert_eq!(COUNT.load(Ordering::Relaxed), count);
+
+    Ok(())
+}
+
+#[test]
+fn test_component_container_service() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let mut container = ComponentContainer::new(ctx.clone(), "test_component_srv", None)?;
+    container
+        .registry_mut()
+        .register("test_component::Counter", create_counter);
+
+    let node = ctx.create_node("test_component_client", None, Default::default())?;
+    let client =
+        node.create_client::<LoadNode>("/test_component_srv/_container/load_node", None)?;
+
+    let mut req = LoadNodeRequest::new().unwrap();
+    req.package_name.assign("test_component");
+    req.plugin_name.assign("test_component::Counter");
+    req.node_name.assign("test_component_loaded");
+
+    let mut receiver = client.send(&req)?;
+    let mut response = None;
+    for _ in 0..300 {
+        container.spin_once_timeout(Duration::from_millis(10))?;
+        match receiver.try_recv() {
+            RecvResult::Ok((_, res, _)) => {
+                response = Some(res);
+                break;
+            }
+            RecvResult::RetryLater(r) => receiver = r,
+            RecvResult::Err(e) => return Err(e),
+        }
+    }
+
+    let response = response.ok_or("no response")?;
+    assert!(response.success);
+    assert_eq!(
+        response.full_node_name.get_string(),
+        "/test_component_loaded"
+    );
+
+    let list = container.list();
+    assert_eq!(
+        list,
+        vec![("/test_component_loaded".to_string(), response.unique_id)]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_component_container_missing_package() -> Result<(), Box<dyn Error + Sync + Send + 'static>>
+{
+    let ctx = Context::new()?;
+    let mut container = ComponentContainer::new(ctx, "test_component_missing", None)?;
+
+    // the failure of the last candidate, the bare file name, is reported
+    let err = container
+        .load(
+            "test_component_missing",
+            "test_component_missing::Node",
+            Default::default(),
+        )
+        .unwrap_err();
+    assert!(err.to_string().contains("libtest_component_missing.so"));
+    assert!(container.list().is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_component_container_spin_shutdown() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = ContextBuilder::new().independent().build()?;
+    let mut container = ComponentContainer::new(ctx.clone(), "test_component_shutdown", None)?;
+
+    let th = std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(100));
+        ctx.shutdown("finished")
+    });
+
+    // spin() returns after the context is shut down
+    container.spin()?;
+    th.join().unwrap()?;
+
+    Ok(())
+}
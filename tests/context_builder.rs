@@ -0,0 +1,97 @@
+use safe_drive::{context::ContextBuilder, msg::common_interfaces::std_msgs};
+use std::{env, error::Error, sync::Arc, thread, time::Duration};
+
+#[test]
+fn test_context_builder() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx1 = ContextBuilder::new()
+        .arguments([
+            "test_context_builder",
+            "--ros-args",
+            "-r",
+            "test_context_builder_topic:=test_context_builder_remapped",
+        ])
+        .domain_id(11)
+        .independent()
+        .build()?;
+
+    let ctx2 = ContextBuilder::new().domain_id(12).independent().build()?;
+
+    assert!(!Arc::ptr_eq(&ctx1, &ctx2));
+    assert_eq!(ctx1.get_domain_id()?, 11);
+    assert_eq!(ctx2.get_domain_id()?, 12);
+
+    // the global arguments of ctx1 remap the topic
+    let node1 = ctx1.create_node("test_context_builder_node", None, Default::default())?;
+    let _publisher =
+        node1.create_publisher::<std_msgs::msg::UInt32>("test_context_builder_topic", None)?;
+    assert_eq!(node1.count_publishers("/test_context_builder_remapped")?, 1);
+
+    // nodes of different domains cannot see each other
+    let node2 = ctx2.create_node("test_context_builder_node", None, Default::default())?;
+    assert_eq!(node2.count_publishers("/test_context_builder_remapped")?, 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_context_builder_invalid_arguments() {
+    let result = ContextBuilder::new()
+        .arguments(["test_context_builder", "--ros-args", "-r", "invalid"])
+        .independent()
+        .build();
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_context_builder_shared() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = ContextBuilder::new().build()?;
+
+    // the same options return the shared context
+    let domain_id = ctx.get_domain_id()?;
+    let shared = ContextBuilder::new()
+        .domain_id(domain_id)
+        .arguments(env::args())
+        .build()?;
+    assert!(Arc::ptr_eq(&ctx, &shared));
+
+    // different options fail without independent()
+    let result = ContextBuilder::new().domain_id(domain_id + 1).build();
+    assert!(result.is_err());
+
+    let result = ContextBuilder::new()
+        .arguments(["test_context_builder", "--ros-args", "-r", "a:=b"])
+        .build();
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_context_builder_drop_async() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = ContextBuilder::new().independent().build()?;
+    let weak = Arc::downgrade(&ctx);
+
+    {
+        let node = ctx.create_node("test_context_builder_async", None, Default::default())?;
+        let mut subscriber =
+            node.create_subscriber::<std_msgs::msg::UInt32>("test_context_builder_async", None)?;
+
+        // start the asynchronous selector of the context
+        let result = async_std::task::block_on(async_std::future::timeout(
+            Duration::from_millis(100),
+            subscriber.recv(),
+        ));
+        assert!(result.is_err());
+    }
+    drop(ctx);
+
+    // the asynchronous selector terminates, and the context is dropped
+    for _ in 0..50 {
+        if weak.strong_count() == 0 {
+            return Ok(());
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+
+    Err("the context is not dropped".into())
+}
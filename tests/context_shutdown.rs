@@ -0,0 +1,120 @@
+use safe_drive::{
+    context::{Context, ContextBuilder, Shutdown},
+    msg::common_interfaces::std_msgs,
+};
+use std::{
+    error::Error,
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+#[test]
+fn test_context_shutdown_hooks() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = ContextBuilder::new().independent().build()?;
+    assert!(ctx.ok());
+
+    let called = Arc::new(Mutex::new(Vec::new()));
+
+    for i in 0..3 {
+        let called = called.clone();
+        ctx.add_on_shutdown(Box::new(move |reason| {
+            called.lock().unwrap().push((i, reason.to_string()));
+        }));
+    }
+
+    // removed hooks are not invoked
+    let called_removed = called.clone();
+    let id = ctx.add_on_shutdown(Box::new(move |_| {
+        called_removed.lock().unwrap().push((100, String::new()));
+    }));
+    assert!(ctx.remove_on_shutdown(id));
+    assert!(!ctx.remove_on_shutdown(id));
+
+    ctx.shutdown("test_context_shutdown")?;
+    assert!(!ctx.ok());
+
+    // hooks are invoked in reverse order
+    let called = called.lock().unwrap();
+    let expected: Vec<_> = (0..3)
+        .rev()
+        .map(|i| (i, "test_context_shutdown".to_string()))
+        .collect();
+    assert_eq!(*called, expected);
+
+    // a context cannot be shut down twice
+    assert!(ctx.shutdown("test_context_shutdown").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_context_shutdown_selector() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = ContextBuilder::new().independent().build()?;
+    let node = ctx.create_node("test_context_shutdown_sel_node", None, Default::default())?;
+    let subscriber =
+        node.create_subscriber::<std_msgs::msg::UInt32>("test_context_shutdown_sel_topic", None)?;
+
+    let mut selector = ctx.create_selector()?;
+    selector.add_subscriber(subscriber, Box::new(|_| ()));
+
+    let ctx_shutdown = ctx.clone();
+    let th = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(100));
+        ctx_shutdown.shutdown("test_context_shutdown_sel").unwrap();
+    });
+
+    // the pending wait is woken up by the shutdown
+    let err = selector.wait().unwrap_err();
+    let err = err.downcast_ref::<Shutdown>().ok_or("not Shutdown")?;
+    assert_eq!(err.reason, "test_context_shutdown_sel");
+
+    th.join().unwrap();
+
+    Ok(())
+}
+
+#[test]
+fn test_context_shutdown_async() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = ContextBuilder::new().independent().build()?;
+    let node = ctx.create_node("test_context_shutdown_async_node", None, Default::default())?;
+    let mut subscriber =
+        node.create_subscriber::<std_msgs::msg::UInt32>("test_context_shutdown_async_topic", None)?;
+
+    async_std::task::block_on(async {
+        let ctx_shutdown = ctx.clone();
+        async_std::task::spawn(async move {
+            async_std::task::sleep(Duration::from_millis(100)).await;
+            ctx_shutdown
+                .shutdown("test_context_shutdown_async")
+                .unwrap();
+        });
+
+        let result = async_std::future::timeout(Duration::from_secs(3), subscriber.recv())
+            .await
+            .expect("timeout");
+
+        let err = result.err().expect("received a message");
+        assert!(err.downcast_ref::<Shutdown>().is_some());
+    });
+
+    Ok(())
+}
+
+#[test]
+fn test_context_shutdown_shared() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    // only this test uses the shared context
+    let ctx = Context::new()?;
+    ctx.shutdown("restart")?;
+    assert!(!ctx.ok());
+
+    // the shared context is replaced after shutting down
+    let restarted = Context::new()?;
+    assert!(restarted.ok());
+    assert!(!Arc::ptr_eq(&ctx, &restarted));
+    assert!(Arc::ptr_eq(&restarted, &Context::new()?));
+
+    restarted.create_node("test_context_shutdown_shared", None, Default::default())?;
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use safe_drive::{
+    context::Context, graph::EndpointType, msg::common_interfaces::std_msgs, qos::Profile,
+};
+use std::error::Error;
+
+const NODE_NAME: &str = "test_graph_node";
+const TOPIC_NAME: &str = "test_graph_topic";
+
+#[test]
+fn test_graph() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node = ctx.create_node(NODE_NAME, Some("test_graph"), Default::default())?;
+
+    let _publisher =
+        node.create_publisher::<std_msgs::msg::UInt32>(TOPIC_NAME, Some(Profile::keep_last(7)))?;
+    let _subscriber = node.create_subscriber::<std_msgs::msg::UInt32>(TOPIC_NAME, None)?;
+
+    let nodes = node.get_node_names_and_namespaces()?;
+    assert!(nodes
+        .iter()
+        .any(|(name, namespace)| name == NODE_NAME && namespace == "/test_graph"));
+    assert!(node
+        .get_node_names()?
+        .contains(&"/test_graph/test_graph_node".to_string()));
+
+    let topic = "/test_graph/test_graph_topic";
+    let topics = node.get_topic_names_and_types()?;
+    assert_eq!(topics[topic], vec!["std_msgs/msg/UInt32".to_string()]);
+
+    let topics = node.get_publisher_names_and_types_by_node(NODE_NAME, "/test_graph")?;
+    assert!(topics.contains_key(topic));
+
+    let topics = node.get_subscriber_names_and_types_by_node(NODE_NAME, "/test_graph")?;
+    assert!(topics.contains_key(topic));
+
+    // relative names are expanded by the namespace of the node
+    assert_eq!(node.count_publishers(TOPIC_NAME)?, 1);
+    assert_eq!(node.count_subscribers(topic)?, 1);
+
+    let infos = node.get_publishers_info_by_topic(TOPIC_NAME)?;
+    assert_eq!(infos.len(), 1);
+    assert_eq!(infos[0].node_name, NODE_NAME);
+    assert_eq!(infos[0].node_namespace, "/test_graph");
+    assert_eq!(infos[0].topic_type, "std_msgs/msg/UInt32");
+    assert_eq!(infos[0].endpoint_type, EndpointType::Publisher);
+    assert_eq!(infos[0].qos_profile.depth, 7);
+    assert!(infos[0].endpoint_gid.iter().any(|b| *b != 0));
+
+    let infos = node.get_subscriptions_info_by_topic(TOPIC_NAME)?;
+    assert_eq!(infos.len(), 1);
+    assert_eq!(infos[0].endpoint_type, EndpointType::Subscription);
+
+    Ok(())
+}
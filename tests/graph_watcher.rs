@@ -0,0 +1,96 @@
+use safe_drive::{context::Context, msg::common_interfaces::std_msgs};
+use std::{cell::Cell, error::Error, rc::Rc, sync::Arc, time::Duration};
+
+const TOPIC_NAME: &str = "/test_graph_watcher_topic";
+
+#[test]
+fn test_graph_watcher_async() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node_watch = ctx.create_node("test_graph_watcher_node", None, Default::default())?;
+    let node_pub = ctx.create_node("test_graph_watcher_pub_node", None, Default::default())?;
+
+    // several watchers of a node can wait at the same time
+    let mut watcher = node_watch.create_graph_watcher()?;
+    let mut watcher2 = node_watch.create_graph_watcher()?;
+
+    async_std::task::block_on(async {
+        let publisher = async_std::task::spawn(async move {
+            async_std::task::sleep(Duration::from_millis(100)).await;
+            node_pub
+                .create_publisher::<std_msgs::msg::UInt32>(TOPIC_NAME, None)
+                .unwrap()
+        });
+
+        let (r1, r2) = async_std::future::timeout(
+            Duration::from_secs(3),
+            futures::future::join(
+                watcher.wait_for_publisher(TOPIC_NAME),
+                watcher2.wait_for_publisher(TOPIC_NAME),
+            ),
+        )
+        .await
+        .expect("timeout");
+        r1.unwrap();
+        r2.unwrap();
+
+        let publisher = publisher.await;
+        drop(publisher);
+
+        async_std::future::timeout(
+            Duration::from_secs(3),
+            watcher.wait_for_publisher_gone(TOPIC_NAME),
+        )
+        .await
+        .expect("timeout")
+        .unwrap();
+    });
+
+    Ok(())
+}
+
+#[test]
+fn test_graph_watcher_selector() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node_watch = ctx.create_node("test_graph_watcher_sel_node", None, Default::default())?;
+    let node_new = ctx.create_node("test_graph_watcher_new_node", None, Default::default())?;
+
+    let watcher = node_watch.create_graph_watcher()?;
+    assert!(Arc::ptr_eq(watcher.get_node(), &node_watch));
+
+    let found = Rc::new(Cell::new(false));
+    let found_cb = found.clone();
+
+    let mut selector = ctx.create_selector()?;
+    assert!(selector.add_graph_watcher(
+        watcher,
+        Box::new(move |node| {
+            if let Ok(names) = node.get_node_names() {
+                if names.iter().any(|n| n == "/test_graph_watcher_new_node") {
+                    found_cb.set(true);
+                }
+            }
+        }),
+    ));
+
+    // another watcher of the same node in the same selector
+    let count = Rc::new(Cell::new(0));
+    let count_cb = count.clone();
+    assert!(selector.add_graph_watcher(
+        node_watch.create_graph_watcher()?,
+        Box::new(move |_| count_cb.set(count_cb.get() + 1)),
+    ));
+
+    let _subscriber =
+        node_new.create_subscriber::<std_msgs::msg::UInt32>("test_graph_watcher_sel", None)?;
+
+    for _ in 0..10 {
+        if found.get() {
+            break;
+        }
+        selector.wait_timeout(Duration::from_millis(300))?;
+    }
+    assert!(found.get());
+    assert!(count.get() > 0);
+
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use safe_drive::{
+    context::Context,
+    error::RCLError,
+    msg::common_interfaces::std_msgs,
+    qos::{policy::DurabilityPolicy, Profile},
+    topic::{intra_process::IntraProcessMsg, subscriber::TakenMsg},
+    RecvResult,
+};
+use std::{error::Error, sync::Arc};
+
+#[test]
+fn test_intra_process_owned() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node_pub = ctx.create_node("test_intra_process_owned_pub", None, Default::default())?;
+    let node_sub = ctx.create_node("test_intra_process_owned_sub", None, Default::default())?;
+
+    let publisher = node_pub.create_publisher_intra_process::<std_msgs::msg::UInt64>(
+        "test_intra_process_owned",
+        None,
+    )?;
+    let subscriber = node_sub.create_subscriber_intra_process::<std_msgs::msg::UInt64>(
+        "test_intra_process_owned",
+        None,
+    )?;
+
+    // a borrowed message cannot be delivered to intra-process subscribers
+    let mut msg = std_msgs::msg::UInt64::new().unwrap();
+    assert!(publisher.send(&msg).is_err());
+
+    msg.data = 100;
+    publisher.send_owned(msg)?;
+
+    let mut selector = ctx.create_selector()?;
+    selector.add_intra_process_subscriber(
+        subscriber,
+        Box::new(|msg| {
+            // the only subscriber takes the ownership
+            assert!(matches!(msg, IntraProcessMsg::Taken(TakenMsg::Copied(_))));
+            assert_eq!(msg.data, 100);
+        }),
+    );
+    selector.wait()?;
+
+    Ok(())
+}
+
+#[test]
+fn test_intra_process_shared() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node = ctx.create_node("test_intra_process_shared", None, Default::default())?;
+
+    let publisher = node.create_publisher_intra_process::<std_msgs::msg::UInt64>(
+        "test_intra_process_shared",
+        None,
+    )?;
+    let subscriber1 = node.create_subscriber_intra_process::<std_msgs::msg::UInt64>(
+        "test_intra_process_shared",
+        None,
+    )?;
+    let subscriber2 = node.create_subscriber_intra_process::<std_msgs::msg::UInt64>(
+        "test_intra_process_shared",
+        None,
+    )?;
+
+    let mut msg = std_msgs::msg::UInt64::new().unwrap();
+    msg.data = 200;
+    let msg = Arc::new(msg);
+    publisher.send_shared(msg.clone())?;
+
+    for subscriber in [subscriber1, subscriber2] {
+        match subscriber.try_recv() {
+            RecvResult::Ok(mut received) => {
+                // a shared message cannot be mutated without cloning
+                assert!(received.get_mut().is_none());
+
+                let IntraProcessMsg::Shared(received) = received else {
+                    panic!("the message is not shared");
+                };
+                assert!(Arc::ptr_eq(&msg, &received));
+            }
+            RecvResult::RetryLater(()) => panic!("no message is received"),
+            RecvResult::Err(e) => return Err(e),
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_intra_process_transient_local() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node = ctx.create_node(
+        "test_intra_process_transient_local",
+        None,
+        Default::default(),
+    )?;
+
+    let profile = Profile {
+        durability: DurabilityPolicy::TransientLocal,
+        ..Default::default()
+    };
+
+    let result = node.create_publisher_intra_process::<std_msgs::msg::UInt64>(
+        "test_intra_process_transient_local",
+        Some(profile),
+    );
+    assert!(matches!(result, Err(RCLError::InvalidArgument)));
+
+    Ok(())
+}
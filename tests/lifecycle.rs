@@ -0,0 +1,212 @@
+use safe_drive::{
+    context::Context,
+    lifecycle::{CallbackReturn, State, Transition},
+    msg::{
+        common_interfaces::std_msgs,
+        interfaces::lifecycle_msgs::{self, srv::ChangeState, srv::ChangeStateRequest},
+    },
+};
+use std::{
+    error::Error,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+#[test]
+fn test_lifecycle_transitions() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node = ctx.create_lifecycle_node("test_lifecycle_node", None, Default::default())?;
+    assert_eq!(node.get_current_state(), State::Unconfigured);
+    assert_eq!(node.get_available_states().len(), 10);
+    assert_eq!(
+        node.get_available_transitions(),
+        vec![Transition::Configure, Transition::UnconfiguredShutdown]
+    );
+
+    let called = Arc::new(Mutex::new(Vec::new()));
+
+    let c = called.clone();
+    node.set_on_configure(Box::new(move |previous| {
+        c.lock().unwrap().push(("configure", previous));
+        CallbackReturn::Success
+    }));
+
+    let c = called.clone();
+    node.set_on_activate(Box::new(move |previous| {
+        c.lock().unwrap().push(("activate", previous));
+        CallbackReturn::Success
+    }));
+
+    // a deactivation fails once
+    let c = called.clone();
+    let mut is_first = true;
+    node.set_on_deactivate(Box::new(move |previous| {
+        c.lock().unwrap().push(("deactivate", previous));
+        if is_first {
+            is_first = false;
+            CallbackReturn::Failure
+        } else {
+            CallbackReturn::Success
+        }
+    }));
+
+    // invalid transition
+    assert!(node.activate().is_err());
+    assert_eq!(node.get_current_state(), State::Unconfigured);
+
+    assert_eq!(node.configure()?, State::Inactive);
+    assert_eq!(node.activate()?, State::Active);
+    assert_eq!(node.deactivate()?, State::Active);
+    assert_eq!(node.deactivate()?, State::Inactive);
+    assert_eq!(node.shutdown()?, State::Finalized);
+    assert!(node.get_available_transitions().is_empty());
+
+    let called = called.lock().unwrap();
+    assert_eq!(
+        *called,
+        vec![
+            ("configure", State::Unconfigured),
+            ("activate", State::Inactive),
+            ("deactivate", State::Active),
+            ("deactivate", State::Active),
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_lifecycle_error() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node = ctx.create_lifecycle_node("test_lifecycle_error_node", None, Default::default())?;
+
+    node.set_on_configure(Box::new(|_| CallbackReturn::Error));
+
+    // recovered by on_error
+    assert_eq!(node.configure()?, State::Unconfigured);
+
+    // not recovered by on_error
+    node.set_on_error(Box::new(|_| CallbackReturn::Failure));
+    assert_eq!(node.configure()?, State::Finalized);
+
+    Ok(())
+}
+
+#[test]
+fn test_lifecycle_hooks_without_lock() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node = Arc::new(ctx.create_lifecycle_node(
+        "test_lifecycle_hooks_node",
+        None,
+        Default::default(),
+    )?);
+
+    // a hook can replace hooks, and transitions fail while a transition is in progress
+    let results = Arc::new(Mutex::new(Vec::new()));
+    let (n, r) = (Arc::downgrade(&node), results.clone());
+    node.set_on_configure(Box::new(move |_| {
+        let n = n.upgrade().unwrap();
+        r.lock().unwrap().push(n.cleanup().is_err());
+        n.set_on_configure(Box::new(|_| CallbackReturn::Failure));
+        CallbackReturn::Success
+    }));
+
+    assert_eq!(node.configure()?, State::Inactive);
+    assert_eq!(*results.lock().unwrap(), vec![true]);
+
+    // the replaced hook is kept
+    assert_eq!(node.cleanup()?, State::Unconfigured);
+    assert_eq!(node.configure()?, State::Unconfigured);
+
+    Ok(())
+}
+
+#[test]
+fn test_lifecycle_publisher() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node = ctx.create_lifecycle_node("test_lifecycle_pub_node", None, Default::default())?;
+    let node_sub = ctx.create_node("test_lifecycle_sub_node", None, Default::default())?;
+
+    let publisher =
+        node.create_publisher::<std_msgs::msg::UInt32>("test_lifecycle_pub_topic", None)?;
+    let subscriber =
+        node_sub.create_subscriber::<std_msgs::msg::UInt32>("test_lifecycle_pub_topic", None)?;
+
+    let mut msg = std_msgs::msg::UInt32::new().unwrap();
+
+    // dropped because the node is not active
+    msg.data = 1;
+    publisher.send(&msg)?;
+    assert!(!publisher.is_activated());
+
+    node.configure()?;
+    node.activate()?;
+    assert!(publisher.is_activated());
+
+    msg.data = 2;
+
+    let mut selector = ctx.create_selector()?;
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let r = received.clone();
+    selector.add_subscriber(
+        subscriber,
+        Box::new(move |msg| r.lock().unwrap().push(msg.data)),
+    );
+
+    for _ in 0..10 {
+        if !received.lock().unwrap().is_empty() {
+            break;
+        }
+        publisher.send(&msg)?;
+        selector.wait_timeout(Duration::from_millis(100))?;
+    }
+
+    let received = received.lock().unwrap();
+    assert!(!received.is_empty());
+    assert!(received.iter().all(|data| *data == 2));
+
+    Ok(())
+}
+
+#[test]
+fn test_lifecycle_change_state_service() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node = ctx.create_lifecycle_node("test_lifecycle_srv_node", None, Default::default())?;
+    let node_client = ctx.create_node("test_lifecycle_client_node", None, Default::default())?;
+
+    let client =
+        node_client.create_client::<ChangeState>("/test_lifecycle_srv_node/change_state", None)?;
+
+    let mut req = ChangeStateRequest::new().unwrap();
+    req.transition.id = lifecycle_msgs::msg::TRANSITION_CONFIGURE;
+
+    let client = async_std::task::block_on(async {
+        let receiver = client.send(&req).unwrap();
+        let (client, response, _) =
+            async_std::future::timeout(Duration::from_secs(3), receiver.recv())
+                .await
+                .expect("timeout")
+                .unwrap();
+        assert!(response.success);
+        client
+    });
+
+    assert_eq!(node.get_current_state(), State::Inactive);
+
+    // a transition rejected by the hook is not successful
+    node.set_on_activate(Box::new(|_| CallbackReturn::Failure));
+    req.transition.id = lifecycle_msgs::msg::TRANSITION_ACTIVATE;
+
+    async_std::task::block_on(async {
+        let receiver = client.send(&req).unwrap();
+        let (_, response, _) = async_std::future::timeout(Duration::from_secs(3), receiver.recv())
+            .await
+            .expect("timeout")
+            .unwrap();
+        assert!(!response.success);
+    });
+
+    assert_eq!(node.get_current_state(), State::Inactive);
+
+    Ok(())
+}
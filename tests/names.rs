@@ -0,0 +1,112 @@
+use safe_drive::{
+    context::Context,
+    error::RCLError,
+    msg::common_interfaces::{std_msgs, std_srvs},
+    names::{self, NameKind},
+    node::NodeOptions,
+};
+use std::error::Error;
+
+#[test]
+fn test_expand_topic_name() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    assert_eq!(
+        names::expand_topic_name("topic", "node", "/ns", false)?,
+        "/ns/topic"
+    );
+    assert_eq!(
+        names::expand_topic_name("/topic", "node", "/ns", false)?,
+        "/topic"
+    );
+    assert_eq!(
+        names::expand_topic_name("~/topic", "node", "/ns", false)?,
+        "/ns/node/topic"
+    );
+    assert_eq!(
+        names::expand_topic_name("{node}/srv", "node", "/", true)?,
+        "/node/srv"
+    );
+
+    let err = names::expand_topic_name("my topic", "node", "/ns", false).unwrap_err();
+    assert_eq!(err.kind, NameKind::TopicName);
+    assert_eq!(err.invalid_index, Some(2));
+
+    let err = names::expand_topic_name("my_srv\0", "node", "/ns", true).unwrap_err();
+    assert_eq!(err.kind, NameKind::ServiceName);
+    assert_eq!(err.invalid_index, Some(6));
+
+    Ok(())
+}
+
+#[test]
+fn test_validate_names() {
+    assert!(names::validate_full_topic_name("/ns/topic").is_ok());
+    assert!(names::validate_full_topic_name("ns/topic").is_err());
+
+    assert!(names::validate_node_name("node").is_ok());
+    let err = names::validate_node_name("1node").unwrap_err();
+    assert_eq!(err.kind, NameKind::NodeName);
+    assert_eq!(err.invalid_index, Some(0));
+
+    assert!(names::validate_namespace("/ns").is_ok());
+    let err = names::validate_namespace("/ns/").unwrap_err();
+    assert_eq!(err.kind, NameKind::Namespace);
+    assert_eq!(err.invalid_index, Some(3));
+}
+
+#[test]
+fn test_resolve_topic_name() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let options =
+        NodeOptions::new().arguments(["--ros-args", "-r", "test_names_topic:=test_names_remapped"]);
+    let node = ctx.create_node("test_names_node", Some("test_names_ns"), options)?;
+
+    assert_eq!(
+        node.resolve_topic_name("test_names_topic", false)?,
+        "/test_names_ns/test_names_remapped"
+    );
+    assert_eq!(
+        node.resolve_topic_name("~/topic", false)?,
+        "/test_names_ns/test_names_node/topic"
+    );
+
+    // invalid names are reported with the index of the invalid character
+    let err = node
+        .resolve_topic_name("invalid topic", false)
+        .err()
+        .ok_or("must fail")?;
+    assert_eq!(err.invalid_index, Some(7));
+
+    let result = node.create_publisher::<std_msgs::msg::UInt32>("invalid topic", None);
+    assert!(matches!(result, Err(RCLError::TopicNameInvalid)));
+
+    let result = node.create_publisher::<std_msgs::msg::UInt32>("topic\0", None);
+    assert!(matches!(result, Err(RCLError::TopicNameInvalid)));
+
+    let result = node.create_server::<std_srvs::srv::Empty>("invalid service", None);
+    assert!(matches!(result, Err(RCLError::ServiceNameInvalid)));
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_node_name() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+
+    let result = ctx.create_node("invalid-node", None, Default::default());
+    assert!(matches!(result, Err(RCLError::NodeInvalidName)));
+
+    let result = ctx.create_node("test_names_valid", Some("invalid ns"), Default::default());
+    assert!(matches!(result, Err(RCLError::NodeInvalidNamespace)));
+
+    // the reason is reported by `names`
+    let err = names::validate_node_name("invalid-node")
+        .err()
+        .ok_or("must fail")?;
+    assert_eq!(err.kind, NameKind::NodeName);
+    assert_eq!(err.invalid_index, Some(7));
+
+    // `NameError` is converted to the error of `rcl`
+    assert_eq!(RCLError::from(err), RCLError::NodeInvalidName);
+
+    Ok(())
+}
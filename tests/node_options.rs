@@ -0,0 +1,60 @@
+use safe_drive::{
+    context::Context, error::RCLError, msg::common_interfaces::std_msgs, node::NodeOptions,
+    parameter::Value,
+};
+use std::error::Error;
+
+#[test]
+fn test_node_options() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+
+    let options = NodeOptions::new()
+        .arguments([
+            "--ros-args",
+            "-r",
+            "test_node_options_topic:=test_node_options_remapped",
+            "-p",
+            "test_node_options_param:=10",
+        ])
+        .use_global_arguments(false)
+        .enable_rosout(false);
+
+    assert_eq!(options.get_arguments().len(), 5);
+    assert!(!options.get_use_global_arguments());
+    assert!(!options.get_enable_rosout());
+
+    let node = ctx.create_node("test_node_options_node", None, options)?;
+
+    // the topic name is remapped
+    let _publisher =
+        node.create_publisher::<std_msgs::msg::UInt32>("test_node_options_topic", None)?;
+    let infos = node.get_publishers_info_by_topic("/test_node_options_remapped")?;
+    assert_eq!(infos.len(), 1);
+
+    // the parameter is set by the node-local arguments
+    let param_server = node.create_parameter_server()?;
+    let guard = param_server.params.read();
+    let param = guard
+        .get_parameter("test_node_options_param")
+        .ok_or("parameter not found")?;
+    assert!(matches!(param.value, Value::I64(10)));
+
+    Ok(())
+}
+
+#[test]
+fn test_node_options_invalid_arguments() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+
+    // invalid remapping rule
+    let options = NodeOptions::new().arguments(["--ros-args", "-r", "invalid"]);
+    let result = ctx.create_node("test_node_options_invalid_node", None, options);
+    assert!(result.is_err());
+
+    // unknown ROS specific argument
+    let options = NodeOptions::new().arguments(["--ros-args", "--unknown-argument"]);
+    let result = ctx.create_node("test_node_options_invalid_node", None, options);
+    assert!(matches!(result, Err(RCLError::InvalidRosArgs)));
+
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use safe_drive::{context::Context, parameter::Value};
+use std::{error::Error, time::Duration};
+
+#[test]
+fn test_set_parameters_atomically() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node_server = ctx.create_node("test_param_atomic_server", None, Default::default())?;
+    let node_client = ctx.create_node("test_param_atomic_client", None, Default::default())?;
+
+    let param_server = node_server.create_parameter_server()?;
+    {
+        let mut params = param_server.params.write();
+        params.set_parameter("a".to_string(), Value::I64(1), false, None)?;
+        params.set_parameter("b".to_string(), Value::I64(2), true, None)?;
+    }
+
+    let mut client = node_client.create_parameter_client("/test_param_atomic_server")?;
+
+    // wait for discovery
+    let dur = Duration::from_millis(500);
+    let mut result = None;
+    for _ in 0..10 {
+        // "b" is read only, so nothing is set
+        if let Ok(r) = client.set_parameters_atomically_timeout(
+            &[
+                ("a".to_string(), Value::I64(10)),
+                ("b".to_string(), Value::I64(20)),
+            ],
+            dur,
+        ) {
+            result = Some(r);
+            break;
+        }
+    }
+    assert_eq!(
+        result.ok_or("no response")?,
+        Err("b is read only".to_string())
+    );
+    assert_eq!(
+        client.get_parameters_timeout(&["a", "b"], dur)?,
+        vec![Value::I64(1), Value::I64(2)]
+    );
+
+    // type mismatch
+    let result = client.set_parameters_atomically_timeout(
+        &[
+            ("a".to_string(), Value::I64(10)),
+            ("a".to_string(), Value::Bool(true)),
+        ],
+        dur,
+    )?;
+    assert!(result.is_err());
+    assert_eq!(
+        param_server
+            .params
+            .read()
+            .get_parameter("a")
+            .ok_or("not found")?
+            .value,
+        Value::I64(1)
+    );
+
+    let result =
+        client.set_parameters_atomically_timeout(&[("a".to_string(), Value::I64(10))], dur)?;
+    assert!(result.is_ok());
+    assert_eq!(
+        param_server
+            .params
+            .read()
+            .get_parameter("a")
+            .ok_or("not found")?
+            .value,
+        Value::I64(10)
+    );
+
+    Ok(())
+}
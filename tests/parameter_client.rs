@@ -0,0 +1,67 @@
+use safe_drive::{
+    context::Context,
+    parameter::{ParameterType, Value},
+};
+use std::{error::Error, time::Duration};
+
+#[test]
+fn test_parameter_client() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node_server = ctx.create_node("test_param_client_server", None, Default::default())?;
+    let node_client = ctx.create_node("test_param_client", None, Default::default())?;
+
+    let param_server = node_server.create_parameter_server()?;
+    {
+        let mut params = param_server.params.write();
+        params.set_parameter("flag".to_string(), Value::Bool(false), false, None)?;
+        params.set_parameter("rate.hz".to_string(), Value::I64(10), false, None)?;
+        params.set_parameter("name".to_string(), Value::String("a".into()), true, None)?;
+        params.set_integer_range("rate.hz", 0, 100, 1)?;
+    }
+
+    let mut client = node_client.create_parameter_client("/test_param_client_server")?;
+    assert_eq!(client.get_remote_node_name(), "/test_param_client_server");
+
+    // wait for discovery
+    let dur = Duration::from_millis(500);
+    let mut list = None;
+    for _ in 0..10 {
+        if let Ok(result) = client.list_parameters_timeout(&[], 0, dur) {
+            list = Some(result);
+            break;
+        }
+    }
+    let list = list.ok_or("no response")?;
+    assert_eq!(list.names, vec!["flag", "name", "rate.hz"]);
+    assert_eq!(list.prefixes, vec!["rate"]);
+
+    let values = client.get_parameters_timeout(&["flag", "unknown"], dur)?;
+    assert_eq!(values, vec![Value::Bool(false), Value::NotSet]);
+
+    let types = client.get_parameter_types_timeout(&["rate.hz", "name"], dur)?;
+    assert_eq!(types, vec![ParameterType::I64, ParameterType::String]);
+
+    let descriptors = client.describe_parameters_timeout(&["rate.hz"], dur)?;
+    assert_eq!(descriptors.len(), 1);
+    assert_eq!(descriptors[0].0, "rate.hz");
+    let range = descriptors[0].1.integer_range.as_ref().ok_or("no range")?;
+    assert_eq!((range.min, range.max), (0, 100));
+
+    let results = client.set_parameters_timeout(
+        &[
+            ("flag".to_string(), Value::Bool(true)),
+            ("name".to_string(), Value::String("b".into())),
+        ],
+        dur,
+    )?;
+    assert!(results[0].is_ok());
+    assert!(results[1].is_err()); // read only
+
+    // asynchronous version
+    let values = async_std::task::block_on(async {
+        async_std::future::timeout(Duration::from_secs(3), client.get_parameters(&["flag"])).await
+    })??;
+    assert_eq!(values, vec![Value::Bool(true)]);
+
+    Ok(())
+}
@@ -0,0 +1,126 @@
+use safe_drive::{context::Context, parameter::Value};
+use std::{
+    error::Error,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+#[test]
+fn test_parameter_event() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node_server = ctx.create_node("test_param_event_server", None, Default::default())?;
+    let node_handler = ctx.create_node("test_param_event_handler", None, Default::default())?;
+
+    let mut handler = node_handler.create_parameter_event_handler()?;
+
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let r = received.clone();
+    handler.add_parameter_callback(
+        "test_param_event_flag",
+        "/test_param_event_server",
+        Box::new(move |name, value| r.lock().unwrap().push((name.to_string(), value.clone()))),
+    );
+
+    // not invoked because the node name is different
+    let id = handler.add_parameter_callback(
+        "test_param_event_flag",
+        "other_node",
+        Box::new(|_, _| panic!("must not be invoked")),
+    );
+
+    let nodes = Arc::new(Mutex::new(Vec::new()));
+    let n = nodes.clone();
+    handler.add_event_callback(Box::new(move |event| {
+        n.lock().unwrap().push(event.node.get_string())
+    }));
+
+    let mut selector = ctx.create_selector()?;
+    assert!(selector.add_parameter_event_handler(&mut handler));
+    assert!(!selector.add_parameter_event_handler(&mut handler));
+
+    let param_server = node_server.create_parameter_server()?;
+
+    for i in 0..20 {
+        param_server.params.write().set_parameter(
+            "test_param_event_flag".to_string(),
+            Value::I64(i),
+            false,
+            None,
+        )?;
+        selector.wait_timeout(Duration::from_millis(100))?;
+        if !received.lock().unwrap().is_empty() {
+            break;
+        }
+    }
+
+    let received = received.lock().unwrap();
+    assert!(!received.is_empty());
+    assert!(received
+        .iter()
+        .all(|(name, value)| name == "test_param_event_flag" && matches!(value, Value::I64(_))));
+
+    assert!(nodes
+        .lock()
+        .unwrap()
+        .iter()
+        .any(|node| node == "/test_param_event_server"));
+
+    assert!(handler.remove_parameter_callback(id));
+    assert!(!handler.remove_parameter_callback(id));
+
+    Ok(())
+}
+
+#[test]
+fn test_parameter_event_modify_callbacks() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node_server =
+        ctx.create_node("test_param_event_modify_server", None, Default::default())?;
+    let node_handler =
+        ctx.create_node("test_param_event_modify_handler", None, Default::default())?;
+
+    let mut handler = node_handler.create_parameter_event_handler()?;
+    let mut selector = ctx.create_selector()?;
+    assert!(selector.add_parameter_event_handler(&mut handler));
+    let handler = Arc::new(Mutex::new(handler));
+
+    // a callback invoked only once, which removes itself and adds another one
+    let added = Arc::new(Mutex::new(0));
+    let id = Arc::new(Mutex::new(None));
+    let (h, a, i) = (handler.clone(), added.clone(), id.clone());
+    *id.lock().unwrap() = Some(handler.lock().unwrap().add_parameter_callback(
+        "test_param_event_modify",
+        "/test_param_event_modify_server",
+        Box::new(move |_, _| {
+            let id = i.lock().unwrap().take().unwrap();
+            let h = h.lock().unwrap();
+            assert!(h.remove_parameter_callback(id));
+            let a = a.clone();
+            h.add_parameter_callback(
+                "test_param_event_modify",
+                "/test_param_event_modify_server",
+                Box::new(move |_, _| *a.lock().unwrap() += 1),
+            );
+        }),
+    ));
+
+    let param_server = node_server.create_parameter_server()?;
+
+    for i in 0..20 {
+        param_server.params.write().set_parameter(
+            "test_param_event_modify".to_string(),
+            Value::I64(i),
+            false,
+            None,
+        )?;
+        selector.wait_timeout(Duration::from_millis(100))?;
+        if *added.lock().unwrap() > 0 {
+            break;
+        }
+    }
+
+    assert!(id.lock().unwrap().is_none());
+    assert!(*added.lock().unwrap() > 0);
+
+    Ok(())
+}
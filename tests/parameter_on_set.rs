@@ -0,0 +1,95 @@
+use safe_drive::{
+    context::Context,
+    parameter::{Descriptor, Parameter, SetParametersResult, Value},
+};
+use std::{error::Error, time::Duration};
+
+#[test]
+fn test_on_set_parameters_callback() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node_server = ctx.create_node("test_on_set_param_server", None, Default::default())?;
+    let node_client = ctx.create_node("test_on_set_param_client", None, Default::default())?;
+
+    let param_server = node_server.create_parameter_server()?;
+    let id = {
+        let mut params = param_server.params.write();
+
+        params.add_parameter(
+            "max_speed".to_string(),
+            Parameter {
+                descriptor: Descriptor {
+                    description: "max speed".to_string(),
+                    additional_constraints: "max".to_string(),
+                    read_only: false,
+                    dynamic_typing: false,
+                    floating_point_range: None,
+                    integer_range: None,
+                },
+                value: Value::F64(1.0),
+            },
+        )?;
+
+        // enforce additional constraints
+        let id = params.add_on_set_parameters_callback(Box::new(|params, proposed| {
+            for (name, value) in proposed {
+                let Some(param) = params.get_parameter(name) else {
+                    continue;
+                };
+                if let (Value::F64(x), "max") =
+                    (value, param.descriptor.additional_constraints.as_str())
+                {
+                    if *x > 10.0 {
+                        return SetParametersResult::failure(format!("{name} is too large"));
+                    }
+                }
+            }
+            SetParametersResult::success()
+        }));
+
+        // rejected locally
+        assert!(params
+            .set_parameter("max_speed".to_string(), Value::F64(20.0), false, None)
+            .is_err());
+        assert_eq!(
+            params.get_parameter("max_speed").ok_or("not found")?.value,
+            Value::F64(1.0)
+        );
+
+        params.set_parameter("max_speed".to_string(), Value::F64(2.0), false, None)?;
+        id
+    };
+
+    // rejected remotely with the reason
+    let mut client = node_client.create_parameter_client("/test_on_set_param_server")?;
+    let dur = Duration::from_millis(500);
+    let mut results = None;
+    for _ in 0..10 {
+        if let Ok(r) = client.set_parameters_timeout(
+            &[
+                ("max_speed".to_string(), Value::F64(30.0)),
+                ("unknown".to_string(), Value::F64(1.0)),
+            ],
+            dur,
+        ) {
+            results = Some(r);
+            break;
+        }
+    }
+    let results = results.ok_or("no response")?;
+    assert_eq!(results[0], Err("max_speed is too large".to_string()));
+    assert!(results[1].is_err());
+
+    {
+        let mut params = param_server.params.write();
+        assert_eq!(
+            params.get_parameter("max_speed").ok_or("not found")?.value,
+            Value::F64(2.0)
+        );
+
+        assert!(params.remove_on_set_parameters_callback(id));
+        assert!(!params.remove_on_set_parameters_callback(id));
+        params.set_parameter("max_speed".to_string(), Value::F64(20.0), false, None)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use safe_drive::{
+    context::Context,
+    parameter::{Descriptor, Parameter, Value},
+};
+use std::error::Error;
+
+#[test]
+fn test_parameter_range() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node = ctx.create_node("test_param_range", None, Default::default())?;
+    let param_server = node.create_parameter_server()?;
+    let mut params = param_server.params.write();
+
+    // integer step
+    params.set_parameter("rate".to_string(), Value::I64(10), false, None)?;
+    params.set_integer_range("rate", 0, 25, 5)?;
+    assert!(params
+        .set_parameter("rate".to_string(), Value::I64(12), false, None)
+        .is_err());
+    params.set_parameter("rate".to_string(), Value::I64(15), false, None)?;
+    params.set_parameter("rate".to_string(), Value::I64(25), false, None)?;
+
+    // floating point step
+    params.set_parameter("gain".to_string(), Value::F64(0.5), false, None)?;
+    params.set_floating_point_range("gain", 0.0, 1.0, 0.1)?;
+    params.set_parameter("gain".to_string(), Value::F64(0.7), false, None)?;
+    assert!(params
+        .set_parameter("gain".to_string(), Value::F64(0.75), false, None)
+        .is_err());
+    assert!(params
+        .set_parameter("gain".to_string(), Value::F64(1.1), false, None)
+        .is_err());
+
+    // elements of arrays
+    params.set_parameter("ids".to_string(), Value::VecI64(vec![0, 2]), false, None)?;
+    params.set_integer_range("ids", 0, 10, 2)?;
+    params.set_parameter("ids".to_string(), Value::VecI64(vec![4, 10]), false, None)?;
+    assert!(params
+        .set_parameter("ids".to_string(), Value::VecI64(vec![4, 5]), false, None)
+        .is_err());
+
+    // ranges on non-numeric values
+    params.set_parameter("name".to_string(), Value::String("a".into()), false, None)?;
+    assert!(params.set_integer_range("name", 0, 10, 1).is_err());
+    assert!(params
+        .set_floating_point_range("rate", 0.0, 10.0, 0.0)
+        .is_err());
+
+    // the current value is not in the new range, so the old range is kept
+    assert!(params.set_integer_range("rate", 0, 20, 1).is_err());
+    let rate = params.get_parameter("rate").ok_or("not found")?;
+    assert_eq!(
+        rate.descriptor
+            .integer_range
+            .as_ref()
+            .ok_or("no range")?
+            .max,
+        25
+    );
+
+    // inconsistent descriptors
+    let mut descriptor = Descriptor::new("both")
+        .integer_range(0, 10, 1)
+        .floating_point_range(0.0, 10.0, 0.0);
+    descriptor.dynamic_typing = true;
+    assert!(params
+        .add_parameter(
+            "both".to_string(),
+            Parameter {
+                descriptor,
+                value: Value::I64(1),
+            },
+        )
+        .is_err());
+
+    assert!(params
+        .add_parameter(
+            "empty".to_string(),
+            Parameter {
+                descriptor: Descriptor::new("empty").integer_range(10, 0, 1),
+                value: Value::I64(5),
+            },
+        )
+        .is_err());
+
+    assert!(params
+        .add_parameter(
+            "flag".to_string(),
+            Parameter {
+                descriptor: Descriptor::new("flag").floating_point_range(0.0, 1.0, 0.0),
+                value: Value::Bool(true),
+            },
+        )
+        .is_err());
+
+    Ok(())
+}
@@ -0,0 +1,187 @@
+use safe_drive::{
+    context::Context,
+    error::DynError,
+    node::NodeOptions,
+    parameter::{
+        typed::{Binder, TypedParameters},
+        Descriptor, ParameterType, Value,
+    },
+};
+use std::{error::Error, time::Duration};
+
+#[derive(Debug, Default)]
+struct Motor {
+    max_speed: f64,
+    ids: Vec<i64>,
+}
+
+impl TypedParameters for Motor {
+    fn bind(&mut self, binder: &mut Binder) -> Result<(), DynError> {
+        binder.field(
+            "max_speed",
+            &mut self.max_speed,
+            Descriptor::new("max speed").floating_point_range(0.0, 10.0, 0.0),
+        )?;
+        binder.field("ids", &mut self.ids, "IDs of motors")?;
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct Config {
+    enabled: bool,
+    rate: i64,
+    name: String,
+    motor: Motor,
+}
+
+impl TypedParameters for Config {
+    fn bind(&mut self, binder: &mut Binder) -> Result<(), DynError> {
+        binder.field("enabled", &mut self.enabled, "enabled")?;
+        binder.field(
+            "rate",
+            &mut self.rate,
+            Descriptor::new("rate [Hz]").integer_range(1, 100, 1),
+        )?;
+        binder.field("name", &mut self.name, Descriptor::new("name").read_only())?;
+        binder.group("motor", &mut self.motor)?;
+        Ok(())
+    }
+}
+
+#[test]
+fn test_value_conversion() {
+    assert_eq!(bool::try_from(&Value::Bool(true)), Ok(true));
+    assert_eq!(
+        Vec::<String>::try_from(&Value::VecString(vec!["a".to_string()])),
+        Ok(vec!["a".to_string()])
+    );
+
+    let err = i64::try_from(&Value::F64(1.0)).unwrap_err();
+    assert_eq!(err.expected, ParameterType::I64);
+    assert_eq!(err.actual, ParameterType::F64);
+
+    assert_eq!(Value::from(vec![1u8, 2]), Value::VecU8(vec![1, 2]));
+}
+
+#[test]
+fn test_typed_parameters() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+
+    let options = NodeOptions::new()
+        .arguments(["--ros-args", "-p", "rate:=20", "-p", "motor.max_speed:=2.5"])
+        .use_global_arguments(false);
+    let node_server = ctx.create_node("test_param_typed_server", None, options)?;
+    let node_client = ctx.create_node("test_param_typed_client", None, Default::default())?;
+
+    let param_server = node_server.create_parameter_server()?;
+
+    let config = Config {
+        enabled: false,
+        rate: 10,
+        name: "robot".to_string(),
+        motor: Default::default(),
+    };
+    let config = param_server.params.write().bind(config)?;
+
+    // initial values are taken from the overrides
+    {
+        let config = config.read();
+        assert!(!config.enabled);
+        assert_eq!(config.rate, 20);
+        assert_eq!(config.name, "robot");
+        assert_eq!(config.motor.max_speed, 2.5);
+    }
+
+    {
+        let guard = param_server.params.read();
+        let rate = guard.get_parameter("rate").ok_or("not found")?;
+        assert_eq!(rate.descriptor.description, "rate [Hz]");
+        assert!(rate.descriptor.integer_range.is_some());
+        let ids = guard.get_parameter("motor.ids").ok_or("not found")?;
+        assert_eq!(ids.value, Value::VecI64(vec![]));
+    }
+
+    // updated locally
+    param_server.params.write().set_parameter(
+        "enabled".to_string(),
+        Value::Bool(true),
+        false,
+        None,
+    )?;
+    assert!(config.read().enabled);
+
+    // out of the range
+    assert!(param_server
+        .params
+        .write()
+        .set_parameter("rate".to_string(), Value::I64(1000), false, None)
+        .is_err());
+    assert_eq!(config.read().rate, 20);
+
+    // updated remotely
+    let mut client = node_client.create_parameter_client("/test_param_typed_server")?;
+    let dur = Duration::from_millis(500);
+    let mut results = None;
+    for _ in 0..10 {
+        if let Ok(r) = client.set_parameters_timeout(
+            &[
+                ("motor.ids".to_string(), Value::VecI64(vec![1, 2])),
+                ("name".to_string(), Value::String("other".to_string())),
+            ],
+            dur,
+        ) {
+            results = Some(r);
+            break;
+        }
+    }
+    let results = results.ok_or("no response")?;
+    assert!(results[0].is_ok());
+    assert!(results[1].is_err()); // read only
+
+    let config = config.read();
+    assert_eq!(config.motor.ids, vec![1, 2]);
+    assert_eq!(config.name, "robot");
+
+    Ok(())
+}
+
+#[test]
+fn test_typed_parameters_rollback() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+
+    // motor.max_speed is out of the range
+    let options = NodeOptions::new()
+        .arguments([
+            "--ros-args",
+            "-p",
+            "rate:=20",
+            "-p",
+            "motor.max_speed:=20.0",
+        ])
+        .use_global_arguments(false);
+    let node = ctx.create_node("test_param_typed_rollback", None, options)?;
+    let param_server = node.create_parameter_server()?;
+
+    let config = Config {
+        enabled: false,
+        rate: 10,
+        name: "robot".to_string(),
+        motor: Default::default(),
+    };
+    assert!(param_server.params.write().bind(config).is_err());
+
+    // the parameters declared by the binding are undeclared
+    let guard = param_server.params.read();
+    assert!(guard.get_parameter("enabled").is_none());
+    assert!(guard.get_parameter("name").is_none());
+
+    // the parameters declared by overrides are kept as they were
+    let rate = guard.get_parameter("rate").ok_or("not found")?;
+    assert_eq!(rate.value, Value::I64(20));
+    assert!(rate.descriptor.integer_range.is_none());
+    let max_speed = guard.get_parameter("motor.max_speed").ok_or("not found")?;
+    assert!(max_speed.descriptor.floating_point_range.is_none());
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use safe_drive::{
+    context::Context,
+    node::NodeOptions,
+    parameter::{ParameterServerOptions, Value},
+};
+use std::{error::Error, time::Duration};
+
+#[test]
+fn test_undeclare_parameter() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node = ctx.create_node("test_undeclare_param", None, Default::default())?;
+    let param_server = node.create_parameter_server()?;
+
+    let mut params = param_server.params.write();
+    params.set_parameter("static".to_string(), Value::I64(1), false, None)?;
+    params.set_dynamically_typed_parameter("read_only".to_string(), Value::I64(1), true, None)?;
+    params.set_dynamically_typed_parameter("dynamic".to_string(), Value::I64(1), false, None)?;
+
+    assert!(params.undeclare_parameter("static").is_err());
+    assert!(params.undeclare_parameter("read_only").is_err());
+    assert!(params.undeclare_parameter("unknown").is_err());
+
+    params.undeclare_parameter("dynamic")?;
+    assert!(params.get_parameter("dynamic").is_none());
+    assert!(params.undeclare_parameter("dynamic").is_err());
+
+    // declare again
+    params.set_parameter("dynamic".to_string(), Value::Bool(true), false, None)?;
+
+    Ok(())
+}
+
+#[test]
+fn test_allow_undeclared_parameters() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+
+    let node_options = NodeOptions::new()
+        .arguments(["--ros-args", "-p", "overridden:=5"])
+        .use_global_arguments(false);
+    let node_server = ctx.create_node("test_allow_undeclared_server", None, node_options)?;
+    let node_client = ctx.create_node("test_allow_undeclared_client", None, Default::default())?;
+
+    let options = ParameterServerOptions::new()
+        .allow_undeclared_parameters(true)
+        .automatically_declare_parameters_from_overrides(false);
+    assert!(options.get_allow_undeclared_parameters());
+    assert!(!options.get_automatically_declare_parameters_from_overrides());
+
+    let param_server = node_server.create_parameter_server_with_options(options)?;
+    {
+        let mut params = param_server.params.write();
+
+        // overrides are not declared automatically
+        assert!(params.get_parameter("overridden").is_none());
+
+        // but used when declared
+        params.set_parameter("overridden".to_string(), Value::I64(0), false, None)?;
+        assert_eq!(
+            params.get_parameter("overridden").ok_or("not found")?.value,
+            Value::I64(5)
+        );
+    }
+
+    let mut client = node_client.create_parameter_client("/test_allow_undeclared_server")?;
+
+    // declare remotely
+    let dur = Duration::from_millis(500);
+    let mut results = None;
+    for _ in 0..10 {
+        if let Ok(r) = client.set_parameters_timeout(&[("created".to_string(), Value::I64(1))], dur)
+        {
+            results = Some(r);
+            break;
+        }
+    }
+    let results = results.ok_or("no response")?;
+    assert!(results[0].is_ok());
+    {
+        let params = param_server.params.read();
+        let param = params.get_parameter("created").ok_or("not found")?;
+        assert_eq!(param.value, Value::I64(1));
+        assert!(param.descriptor.dynamic_typing);
+    }
+
+    // undeclare remotely
+    let results = client.set_parameters_timeout(
+        &[
+            ("created".to_string(), Value::NotSet),
+            ("overridden".to_string(), Value::NotSet),
+        ],
+        dur,
+    )?;
+    assert!(results[0].is_ok());
+    assert!(results[1].is_err()); // statically typed
+
+    let params = param_server.params.read();
+    assert!(params.get_parameter("created").is_none());
+    assert!(params.get_parameter("overridden").is_some());
+
+    Ok(())
+}
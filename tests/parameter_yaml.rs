@@ -0,0 +1,108 @@
+use safe_drive::{context::Context, parameter::Value};
+use std::error::Error;
+
+#[test]
+fn test_parameter_yaml() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node = ctx.create_node("test_param_yaml", None, Default::default())?;
+    let param_server = node.create_parameter_server()?;
+
+    {
+        let mut params = param_server.params.write();
+        params.set_parameter("flag".to_string(), Value::Bool(true), false, None)?;
+        params.set_parameter("motor.max_speed".to_string(), Value::F64(2.0), false, None)?;
+        params.set_parameter(
+            "motor.ids".to_string(),
+            Value::VecI64(vec![1, 2]),
+            false,
+            None,
+        )?;
+        params.set_parameter(
+            "name".to_string(),
+            Value::String("say \"10\"".to_string()),
+            true,
+            None,
+        )?;
+        params.set_parameter(
+            "names".to_string(),
+            Value::VecString(vec!["a".to_string(), "true".to_string()]),
+            false,
+            None,
+        )?;
+    }
+
+    let yaml = param_server.dump_yaml()?;
+    assert_eq!(
+        yaml,
+        "/test_param_yaml:
+  ros__parameters:
+    flag: true
+    motor:
+      ids: [1, 2]
+      max_speed: 2.0
+    name: \"say \\\"10\\\"\"
+    names: [\"a\", \"true\"]
+"
+    );
+
+    let path = std::env::temp_dir().join("test_param_yaml.yaml");
+    param_server.dump_file(&path)?;
+
+    {
+        let mut params = param_server.params.write();
+        params.set_parameter("flag".to_string(), Value::Bool(false), false, None)?;
+        params.set_parameter("motor.max_speed".to_string(), Value::F64(5.0), false, None)?;
+    }
+
+    // read only parameters are ignored because they are not changed
+    param_server.load_file(&path)?;
+    {
+        let params = param_server.params.read();
+        let get = |name| params.get_parameter(name).map(|p| p.value.clone());
+        assert_eq!(get("flag"), Some(Value::Bool(true)));
+        assert_eq!(get("motor.max_speed"), Some(Value::F64(2.0)));
+        assert_eq!(
+            get("names"),
+            Some(Value::VecString(vec!["a".to_string(), "true".to_string()]))
+        );
+    }
+
+    // wildcard node names, and parameters not declared yet
+    let path = std::env::temp_dir().join("test_param_yaml_wildcard.yaml");
+    std::fs::write(
+        &path,
+        "/**:
+  ros__parameters:
+    flag: false
+    later: 10
+",
+    )?;
+    param_server.load_file(&path)?;
+    {
+        let mut params = param_server.params.write();
+        assert_eq!(
+            params.get_parameter("flag").ok_or("not found")?.value,
+            Value::Bool(false)
+        );
+        assert!(params.get_parameter("later").is_none());
+
+        // the loaded value is used when declared
+        params.set_parameter("later".to_string(), Value::I64(0), false, None)?;
+        assert_eq!(
+            params.get_parameter("later").ok_or("not found")?.value,
+            Value::I64(10)
+        );
+    }
+
+    // type mismatch
+    std::fs::write(
+        &path,
+        "/test_param_yaml:
+  ros__parameters:
+    flag: 1
+",
+    )?;
+    assert!(param_server.load_file(&path).is_err());
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use safe_drive::qos::{
+    policy::{DurabilityPolicy, HistoryPolicy, ReliabilityPolicy},
+    Compatibility, Profile, DURATION_UNSPECIFIED,
+};
+use std::{error::Error, time::Duration};
+
+#[test]
+fn test_qos_builder() {
+    let profile = Profile::keep_last(5)
+        .best_effort()
+        .transient_local()
+        .deadline(Duration::from_millis(100));
+
+    assert_eq!(profile.history, HistoryPolicy::KeepLast);
+    assert_eq!(profile.depth, 5);
+    assert_eq!(profile.reliability, ReliabilityPolicy::BestEffort);
+    assert_eq!(profile.durability, DurabilityPolicy::TransientLocal);
+    assert_eq!(profile.deadline, Duration::from_millis(100));
+    assert_eq!(profile.lifespan, DURATION_UNSPECIFIED);
+
+    let rosout = Profile::rosout();
+    assert_eq!(rosout.durability, DurabilityPolicy::TransientLocal);
+    assert_eq!(rosout.lifespan, Duration::from_secs(10));
+}
+
+#[test]
+fn test_qos_check_compatible() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let reliable = Profile::default().reliable();
+    let best_effort = Profile::default().best_effort();
+
+    assert_eq!(
+        best_effort.check_compatible(&best_effort)?,
+        Compatibility::Ok
+    );
+    assert_eq!(reliable.check_compatible(&best_effort)?, Compatibility::Ok);
+    assert!(matches!(
+        best_effort.check_compatible(&reliable)?,
+        Compatibility::Error(_)
+    ));
+
+    let volatile = Profile::default().volatile();
+    let transient_local = Profile::default().transient_local();
+    assert!(matches!(
+        volatile.check_compatible(&transient_local)?,
+        Compatibility::Error(_)
+    ));
+
+    Ok(())
+}
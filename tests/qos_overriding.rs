@@ -0,0 +1,110 @@
+use safe_drive::{
+    context::Context,
+    msg::common_interfaces::std_msgs,
+    node::NodeOptions,
+    parameter::Value,
+    qos::{
+        overriding::{QosOverridingOptions, QosPolicyKind},
+        policy::{HistoryPolicy, ReliabilityPolicy},
+        Profile,
+    },
+};
+use std::error::Error;
+
+const TOPIC_NAME: &str = "test_qos_overriding";
+
+#[test]
+fn test_qos_overriding_options() {
+    let options = QosOverridingOptions::default();
+    assert_eq!(
+        options.policies,
+        vec![
+            QosPolicyKind::History,
+            QosPolicyKind::Depth,
+            QosPolicyKind::Reliability
+        ]
+    );
+    assert!(options.id.is_none());
+
+    let options = QosOverridingOptions::all_policies().id("sensor");
+    assert_eq!(options.policies.len(), 9);
+    assert_eq!(options.id.as_deref(), Some("sensor"));
+    assert_eq!(
+        QosPolicyKind::LivelinessLeaseDuration.name(),
+        "liveliness_lease_duration"
+    );
+}
+
+#[test]
+fn test_actual_qos() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node = ctx.create_node("test_qos_overriding_node", None, Default::default())?;
+
+    // without parameters, the profile is not overridden
+    let qos = Profile::keep_last(3).best_effort();
+    let publisher = node.create_publisher_with_qos_overriding::<std_msgs::msg::UInt32>(
+        TOPIC_NAME,
+        Some(qos.clone()),
+        QosOverridingOptions::default(),
+    )?;
+    let subscriber = node.create_subscriber_with_qos_overriding::<std_msgs::msg::UInt32>(
+        TOPIC_NAME,
+        Some(qos),
+        QosOverridingOptions::default(),
+    )?;
+
+    let actual = publisher.actual_qos()?;
+    assert_eq!(actual.history, HistoryPolicy::KeepLast);
+    assert_eq!(actual.depth, 3);
+    assert_eq!(actual.reliability, ReliabilityPolicy::BestEffort);
+
+    let actual = subscriber.actual_qos()?;
+    assert_eq!(actual.history, HistoryPolicy::KeepLast);
+    assert_eq!(actual.depth, 3);
+    assert_eq!(actual.reliability, ReliabilityPolicy::BestEffort);
+
+    Ok(())
+}
+
+#[test]
+fn test_qos_overriding_by_arguments() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let topic_name = "test_qos_overriding_by_arguments";
+    let ctx = Context::new()?;
+    let options = NodeOptions::new().arguments([
+        "--ros-args",
+        "-p",
+        "qos_overrides./test_qos_overriding_by_arguments.publisher.depth:=7",
+        "-p",
+        "qos_overrides./test_qos_overriding_by_arguments.publisher.reliability:=best_effort",
+    ]);
+    let node = ctx.create_node("test_qos_overriding_by_arguments_node", None, options)?;
+    let param_server = node.create_parameter_server()?;
+
+    let publisher = node.create_publisher_with_qos_overriding::<std_msgs::msg::UInt32>(
+        topic_name,
+        Some(Profile::keep_last(3)),
+        QosOverridingOptions::default(),
+    )?;
+
+    let actual = publisher.actual_qos()?;
+    assert_eq!(actual.history, HistoryPolicy::KeepLast);
+    assert_eq!(actual.depth, 7);
+    assert_eq!(actual.reliability, ReliabilityPolicy::BestEffort);
+
+    // the policies are declared as read-only parameters
+    let mut params = param_server.params.write();
+    let prefix = "qos_overrides./test_qos_overriding_by_arguments.publisher";
+
+    let depth = params.get_parameter(&format!("{prefix}.depth")).unwrap();
+    assert!(depth.descriptor.read_only);
+    assert_eq!(depth.value, Value::I64(7));
+
+    let history = params.get_parameter(&format!("{prefix}.history")).unwrap();
+    assert!(history.descriptor.read_only);
+    assert_eq!(history.value, Value::String("keep_last".to_string()));
+
+    let result = params.set_parameter(format!("{prefix}.depth"), Value::I64(10), false, None);
+    assert!(result.is_err());
+
+    Ok(())
+}
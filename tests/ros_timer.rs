@@ -0,0 +1,92 @@
+use parking_lot::Mutex;
+use safe_drive::{
+    clock::{Clock, ClockType},
+    context::Context,
+};
+use std::{cell::Cell, error::Error, rc::Rc, sync::Arc, time::Duration};
+
+const MS: i64 = 1_000_000;
+
+#[test]
+fn test_ros_timer() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node = ctx.create_node("test_ros_timer", None, Default::default())?;
+    let mut selector = ctx.create_selector()?;
+
+    let clock = node.get_clock();
+    {
+        let mut guard = clock.lock();
+        guard.enable_ros_time_override()?;
+        guard.set_ros_time_override(1000 * MS)?;
+    }
+
+    let count = Rc::new(Cell::new(0));
+    let count_cloned = count.clone();
+    let id = selector.add_ros_timer(
+        &clock,
+        Duration::from_millis(100),
+        Box::new(move || count_cloned.set(count_cloned.get() + 1)),
+    )?;
+
+    // the time does not advance while /clock stops
+    selector.wait_timeout(Duration::from_millis(200))?;
+    assert_eq!(count.get(), 0);
+
+    // step the simulated time
+    clock.lock().set_ros_time_override(1050 * MS)?;
+    selector.wait_timeout(Duration::from_millis(100))?;
+    assert_eq!(count.get(), 0);
+
+    clock.lock().set_ros_time_override(1100 * MS)?;
+    selector.wait_timeout(Duration::from_secs(1))?;
+    assert_eq!(count.get(), 1);
+
+    // missed periods are skipped
+    clock.lock().set_ros_time_override(1450 * MS)?;
+    selector.wait_timeout(Duration::from_secs(1))?;
+    assert_eq!(count.get(), 2);
+
+    // the remaining time (50ms) is kept when the time jumps backward
+    clock.lock().set_ros_time_override(0)?;
+    selector.wait_timeout(Duration::from_millis(100))?;
+    assert_eq!(count.get(), 2);
+
+    clock.lock().set_ros_time_override(40 * MS)?;
+    selector.wait_timeout(Duration::from_millis(100))?;
+    assert_eq!(count.get(), 2);
+
+    clock.lock().set_ros_time_override(50 * MS)?;
+    selector.wait_timeout(Duration::from_secs(1))?;
+    assert_eq!(count.get(), 3);
+
+    // removed timers are never called
+    selector.remove_timer(id);
+    clock.lock().set_ros_time_override(1000 * MS)?;
+    selector.wait_timeout(Duration::from_millis(100))?;
+    assert_eq!(count.get(), 3);
+
+    Ok(())
+}
+
+#[test]
+fn test_ros_timer_system_time() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let mut selector = ctx.create_selector()?;
+
+    // without the override, timers follow the system time
+    let clock = Arc::new(Mutex::new(Clock::with_type(ClockType::Steady)?));
+
+    let count = Rc::new(Cell::new(0));
+    let count_cloned = count.clone();
+    selector.add_ros_timer(
+        &clock,
+        Duration::from_millis(50),
+        Box::new(move || count_cloned.set(count_cloned.get() + 1)),
+    )?;
+
+    while count.get() < 3 {
+        selector.wait()?;
+    }
+
+    Ok(())
+}
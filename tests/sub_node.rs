@@ -0,0 +1,42 @@
+use safe_drive::{context::Context, msg::common_interfaces::std_msgs};
+use std::error::Error;
+
+#[test]
+fn test_sub_node() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node = ctx.create_node(
+        "test_sub_node",
+        Some("test_sub_node_ns"),
+        Default::default(),
+    )?;
+
+    let camera = node.create_sub_node("camera_left")?;
+    assert_eq!(camera.get_sub_namespace(), "camera_left");
+    assert_eq!(
+        camera.get_effective_namespace(),
+        "/test_sub_node_ns/camera_left"
+    );
+
+    let sensor = camera.create_sub_node("sensor")?;
+    assert_eq!(sensor.get_sub_namespace(), "camera_left/sensor");
+
+    // only relative names are extended
+    assert_eq!(camera.extend_name("image"), "camera_left/image");
+    assert_eq!(camera.extend_name("/image"), "/image");
+    assert_eq!(camera.extend_name("~/image"), "~/image");
+
+    let _publisher = camera.create_publisher::<std_msgs::msg::UInt32>("image", None)?;
+    let infos = node.get_publishers_info_by_topic("/test_sub_node_ns/camera_left/image")?;
+    assert_eq!(infos.len(), 1);
+
+    // the underlying node is shared
+    let names = node.get_node_names()?;
+    assert!(!names.iter().any(|name| name == "camera_left"));
+
+    // absolute and invalid sub-namespaces
+    assert!(node.create_sub_node("/camera").is_err());
+    assert!(node.create_sub_node("").is_err());
+    assert!(node.create_sub_node("camera left").is_err());
+
+    Ok(())
+}
@@ -0,0 +1,122 @@
+use safe_drive::{
+    context::Context,
+    msg::common_interfaces::geometry_msgs,
+    topic::synchronizer::{Policy, Synchronizer},
+    RecvResult,
+};
+use std::{error::Error, time::Duration};
+
+fn point(sec: i32, nanosec: u32, x: f64) -> geometry_msgs::msg::PointStamped {
+    let mut msg = geometry_msgs::msg::PointStamped::new().unwrap();
+    msg.header.stamp.sec = sec;
+    msg.header.stamp.nanosec = nanosec;
+    msg.point.x = x;
+    msg
+}
+
+fn vector(sec: i32, nanosec: u32, x: f64) -> geometry_msgs::msg::Vector3Stamped {
+    let mut msg = geometry_msgs::msg::Vector3Stamped::new().unwrap();
+    msg.header.stamp.sec = sec;
+    msg.header.stamp.nanosec = nanosec;
+    msg.vector.x = x;
+    msg
+}
+
+#[test]
+fn test_synchronizer_exact() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node = ctx.create_node("test_synchronizer_exact_node", None, Default::default())?;
+
+    let pub_point = node.create_publisher::<geometry_msgs::msg::PointStamped>(
+        "test_synchronizer_exact_point",
+        None,
+    )?;
+    let pub_vector = node.create_publisher::<geometry_msgs::msg::Vector3Stamped>(
+        "test_synchronizer_exact_vector",
+        None,
+    )?;
+
+    let sub_point = node.create_subscriber::<geometry_msgs::msg::PointStamped>(
+        "test_synchronizer_exact_point",
+        None,
+    )?;
+    let sub_vector = node.create_subscriber::<geometry_msgs::msg::Vector3Stamped>(
+        "test_synchronizer_exact_vector",
+        None,
+    )?;
+
+    let sync = Synchronizer::new((sub_point, sub_vector), Policy::ExactTime, 10);
+
+    // only the messages stamped at 2 sec have the same timestamp
+    pub_point.send(&point(1, 0, 1.0))?;
+    pub_point.send(&point(2, 0, 2.0))?;
+    pub_vector.send(&vector(1, 500, 1.0))?;
+    pub_vector.send(&vector(2, 0, 2.0))?;
+
+    let mut selector = ctx.create_selector()?;
+    selector.add_synchronizer(
+        sync,
+        Box::new(|(p, v)| {
+            assert_eq!(p.header.stamp.sec, 2);
+            assert_eq!(v.header.stamp.sec, 2);
+            assert_eq!(p.point.x, v.vector.x);
+        }),
+    );
+
+    selector.wait()?;
+
+    Ok(())
+}
+
+#[test]
+fn test_synchronizer_approximate() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node = ctx.create_node(
+        "test_synchronizer_approximate_node",
+        None,
+        Default::default(),
+    )?;
+
+    let pub_point = node.create_publisher::<geometry_msgs::msg::PointStamped>(
+        "test_synchronizer_approximate_point",
+        None,
+    )?;
+    let pub_vector = node.create_publisher::<geometry_msgs::msg::Vector3Stamped>(
+        "test_synchronizer_approximate_vector",
+        None,
+    )?;
+
+    let sub_point = node.create_subscriber::<geometry_msgs::msg::PointStamped>(
+        "test_synchronizer_approximate_point",
+        None,
+    )?;
+    let sub_vector = node.create_subscriber::<geometry_msgs::msg::Vector3Stamped>(
+        "test_synchronizer_approximate_vector",
+        None,
+    )?;
+
+    let mut sync = Synchronizer::new(
+        (sub_point, sub_vector),
+        Policy::ApproximateTime(Duration::from_millis(10)),
+        10,
+    );
+
+    // the point stamped at 1.000 sec is too old to be matched
+    pub_point.send(&point(1, 0, 1.0))?;
+    pub_point.send(&point(1, 100_000_000, 2.0))?;
+    pub_vector.send(&vector(1, 95_000_000, 2.0))?;
+    pub_vector.send(&vector(1, 200_000_000, 3.0))?;
+
+    std::thread::sleep(Duration::from_millis(100));
+
+    match sync.try_recv() {
+        RecvResult::Ok((p, v)) => {
+            assert_eq!(p.point.x, 2.0);
+            assert_eq!(v.vector.x, 2.0);
+        }
+        RecvResult::RetryLater(()) => panic!("no messages are matched"),
+        RecvResult::Err(e) => return Err(e),
+    }
+
+    Ok(())
+}
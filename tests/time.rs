@@ -0,0 +1,91 @@
+use safe_drive::{
+    clock::{Clock, ClockType},
+    msg::builtin_interfaces::UnsafeTime,
+    time::Time,
+};
+use std::{cmp::Ordering, error::Error, time::Duration};
+
+#[test]
+fn test_clock_types() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    for clock_type in [ClockType::Ros, ClockType::System, ClockType::Steady] {
+        let mut clock = Clock::with_type(clock_type)?;
+        assert_eq!(clock.get_clock_type(), clock_type);
+
+        let t1 = clock.now()?;
+        let t2 = clock.now()?;
+        assert_eq!(t1.clock_type(), clock_type);
+        assert!(t1 <= t2);
+    }
+
+    // only the ROS time can be overridden
+    let mut clock = Clock::with_type(ClockType::Steady)?;
+    assert!(clock.enable_ros_time_override().is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_time_arithmetic() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let t1 = Time::new(1_500_000_000, ClockType::Ros);
+    let t2 = t1 + Duration::from_millis(700);
+    assert_eq!(t2.nanoseconds(), 2_200_000_000);
+    assert_eq!(t2 - Duration::from_millis(700), t1);
+    assert_eq!(t2.duration_since(&t1)?, Duration::from_millis(700));
+    assert!(t1.duration_since(&t2).is_err());
+
+    // negative times are allowed
+    let t3 = t1 - Duration::from_secs(2);
+    assert_eq!(t3.nanoseconds(), -500_000_000);
+
+    assert!(Time::new(i64::MAX, ClockType::Ros)
+        .checked_add(Duration::from_nanos(1))
+        .is_none());
+    assert!(t1.checked_add(Duration::MAX).is_none());
+    assert!(Time::new(i64::MIN, ClockType::Ros)
+        .checked_sub(Duration::from_nanos(1))
+        .is_none());
+
+    // times of different clocks
+    let steady = Time::new(1_500_000_000, ClockType::Steady);
+    assert_ne!(t1, steady);
+    assert_eq!(t1.partial_cmp(&steady), None);
+    assert!(t1.duration_since(&steady).is_err());
+    assert!(steady.duration_since(&t1).is_err());
+    assert_eq!(t1.partial_cmp(&t2), Some(Ordering::Less));
+
+    Ok(())
+}
+
+#[test]
+fn test_time_stamp() {
+    let stamp = UnsafeTime {
+        sec: 10,
+        nanosec: 20,
+    };
+    let time = Time::from(&stamp);
+    assert_eq!(time.nanoseconds(), 10_000_000_020);
+    assert_eq!(time.clock_type(), ClockType::Ros);
+    let converted = time.to_stamp_saturating();
+    assert_eq!((converted.sec, converted.nanosec), (10, 20));
+
+    // negative times keep nanoseconds positive
+    let stamp = Time::new(-1, ClockType::Ros).to_stamp_saturating();
+    assert_eq!((stamp.sec, stamp.nanosec), (-1, 999_999_999));
+    assert_eq!(Time::from(&stamp).nanoseconds(), -1);
+
+    // after 2038
+    let stamp = Time::new(i64::MAX, ClockType::Ros).to_stamp_saturating();
+    assert_eq!((stamp.sec, stamp.nanosec), (i32::MAX, 999_999_999));
+
+    let stamp = Time::new(i64::MIN, ClockType::Ros).to_stamp_saturating();
+    assert_eq!((stamp.sec, stamp.nanosec), (i32::MIN, 0));
+
+    let max = UnsafeTime {
+        sec: i32::MAX,
+        nanosec: 999_999_999,
+    };
+    let time = Time::from_stamp(&max, ClockType::System);
+    assert_eq!(time.clock_type(), ClockType::System);
+    let converted = time.to_stamp_saturating();
+    assert_eq!((converted.sec, converted.nanosec), (i32::MAX, 999_999_999));
+}
@@ -0,0 +1,71 @@
+use safe_drive::{
+    context::Context, msg::interfaces::rosgraph_msgs, node::NodeOptions, parameter::Value,
+    qos::Profile,
+};
+use std::{error::Error, time::Duration};
+
+#[test]
+fn test_time_source() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+
+    let options = NodeOptions::new()
+        .arguments(["--ros-args", "-p", "use_sim_time:=true"])
+        .use_global_arguments(false);
+    let node = ctx.create_node("test_time_source", None, options)?;
+    let node_sim = ctx.create_node("test_time_source_sim", None, Default::default())?;
+
+    let time_source = node.create_time_source()?;
+    assert!(time_source.get_use_sim_time());
+    assert!(node.create_time_source().is_err());
+
+    let clock = node.get_clock();
+    assert!(clock.lock().is_ros_time_override_enabled()?);
+
+    // publish the simulated time
+    let publisher =
+        node_sim.create_publisher::<rosgraph_msgs::msg::Clock>("/clock", Some(Profile::clock()))?;
+    let mut msg = rosgraph_msgs::msg::Clock::new().ok_or("allocation failed")?;
+    msg.clock.sec = 100;
+    msg.clock.nanosec = 5;
+
+    let expected = 100_000_000_005;
+    for _ in 0..50 {
+        publisher.send(&msg)?;
+        std::thread::sleep(Duration::from_millis(100));
+        if clock.lock().get_now()? == expected {
+            break;
+        }
+    }
+    assert_eq!(clock.lock().get_now()?, expected);
+
+    // follow the parameter
+    let param_server = node.create_parameter_server()?;
+    time_source.attach_parameter_server(&param_server)?;
+    assert_eq!(
+        param_server
+            .params
+            .read()
+            .get_parameter("use_sim_time")
+            .ok_or("not found")?
+            .value,
+        Value::Bool(true)
+    );
+
+    assert!(param_server
+        .params
+        .write()
+        .set_parameter("use_sim_time".to_string(), Value::I64(1), false, None)
+        .is_err());
+
+    param_server.params.write().set_parameter(
+        "use_sim_time".to_string(),
+        Value::Bool(false),
+        false,
+        None,
+    )?;
+    assert!(!time_source.get_use_sim_time());
+    assert!(!clock.lock().is_ros_time_override_enabled()?);
+    assert!(clock.lock().get_now()? > expected);
+
+    Ok(())
+}
@@ -0,0 +1,89 @@
+use safe_drive::{
+    context::Context,
+    msg::{
+        common_interfaces::geometry_msgs,
+        interfaces::statistics_msgs::msg::{
+            MetricsMessage, STATISTICS_DATA_TYPE_AVERAGE, STATISTICS_DATA_TYPE_MAXIMUM,
+            STATISTICS_DATA_TYPE_MINIMUM, STATISTICS_DATA_TYPE_SAMPLE_COUNT,
+        },
+    },
+    topic::statistics::TopicStatisticsOptions,
+};
+use std::{
+    cell::RefCell,
+    collections::BTreeSet,
+    error::Error,
+    rc::Rc,
+    time::{Duration, SystemTime},
+};
+
+const TOPIC_NAME: &str = "test_topic_statistics";
+const STATISTICS_TOPIC_NAME: &str = "test_topic_statistics_metrics";
+
+#[test]
+fn test_topic_statistics() -> Result<(), Box<dyn Error + Sync + Send + 'static>> {
+    let ctx = Context::new()?;
+    let node = ctx.create_node("test_topic_statistics_node", None, Default::default())?;
+
+    let publisher = node.create_publisher::<geometry_msgs::msg::PointStamped>(TOPIC_NAME, None)?;
+    let mut subscriber =
+        node.create_subscriber::<geometry_msgs::msg::PointStamped>(TOPIC_NAME, None)?;
+    let sub_metrics = node.create_subscriber::<MetricsMessage>(STATISTICS_TOPIC_NAME, None)?;
+
+    let options = TopicStatisticsOptions {
+        publish_topic: STATISTICS_TOPIC_NAME.to_string(),
+        publish_period: Duration::from_millis(200),
+    };
+    subscriber.enable_statistics_with_message_age(options)?;
+
+    // statistics are published by a wall timer of the selector
+    let mut selector = ctx.create_selector()?;
+    selector.add_subscriber(subscriber, Box::new(|_| ()));
+
+    // metrics sources which have samples
+    let sources = Rc::new(RefCell::new(BTreeSet::new()));
+    let sources_cloned = sources.clone();
+    selector.add_subscriber(
+        sub_metrics,
+        Box::new(move |msg| {
+            assert_eq!(msg.unit.get_string(), "ms");
+            assert_eq!(msg.statistics.len(), 5);
+
+            let get = |data_type| {
+                msg.statistics
+                    .iter()
+                    .find(|p| p.data_type == data_type)
+                    .unwrap()
+                    .data
+            };
+
+            let source = msg.metrics_source.get_string();
+            assert!(
+                source == "message_age" || source == "message_period",
+                "unknown metrics source: {source}"
+            );
+
+            if get(STATISTICS_DATA_TYPE_SAMPLE_COUNT) >= 1.0 {
+                let avg = get(STATISTICS_DATA_TYPE_AVERAGE);
+                let min = get(STATISTICS_DATA_TYPE_MINIMUM);
+                let max = get(STATISTICS_DATA_TYPE_MAXIMUM);
+                assert!(min <= avg && avg <= max);
+
+                sources_cloned.borrow_mut().insert(source);
+            }
+        }),
+    );
+
+    let timeout = SystemTime::now() + Duration::from_secs(10);
+    while sources.borrow().len() < 2 {
+        assert!(SystemTime::now() < timeout, "no statistics");
+
+        let mut msg = geometry_msgs::msg::PointStamped::new().unwrap();
+        msg.header.stamp = SystemTime::now().into();
+        publisher.send(&msg)?;
+
+        selector.wait_timeout(Duration::from_millis(20))?;
+    }
+
+    Ok(())
+}